@@ -7,7 +7,7 @@
 use rustc_errors::ErrorGuaranteed;
 use rustc_feature::{find_gated_cfg, is_builtin_attr_name, Features, GatedCfg};
 use rustc_macros::HashStable_Generic;
-use rustc_session::config::ExpectedValues;
+use rustc_session::config::{ExpectedValues, OptLevel};
 use rustc_session::lint::builtin::UNEXPECTED_CFGS;
 use rustc_session::lint::BuiltinLintDiag;
 use rustc_session::parse::feature_err;
@@ -54,6 +54,45 @@ pub enum OptimizeAttr {
     None,
     Speed,
     Size,
+    /// `#[optimize(level = "...")]`
+    Level(OptimizeLevel),
+}
+
+/// The optimization level of `#[optimize(level = "...")]`, with the values of `-C opt-level`.
+#[derive(Copy, Clone, Encodable, Decodable, Debug, PartialEq, Eq, HashStable_Generic)]
+pub enum OptimizeLevel {
+    /// `"0"`
+    No,
+    /// `"1"`
+    Less,
+    /// `"2"`
+    Default,
+    /// `"3"`
+    Aggressive,
+    /// `"s"`
+    Size,
+    /// `"z"`
+    SizeMin,
+}
+
+impl OptimizeAttr {
+    /// Whether the optimizations of this function are suppressed, by `#[optimize(level = "0")]`.
+    pub fn do_not_optimize(&self) -> bool {
+        matches!(self, OptimizeAttr::Level(OptimizeLevel::No))
+    }
+
+    /// The `-C opt-level` set by `#[optimize(level = "...")]`, if any.
+    pub fn opt_level(&self) -> Option<OptLevel> {
+        let OptimizeAttr::Level(level) = self else { return None };
+        Some(match level {
+            OptimizeLevel::No => OptLevel::No,
+            OptimizeLevel::Less => OptLevel::Less,
+            OptimizeLevel::Default => OptLevel::Default,
+            OptimizeLevel::Aggressive => OptLevel::Aggressive,
+            OptimizeLevel::Size => OptLevel::Size,
+            OptimizeLevel::SizeMin => OptLevel::SizeMin,
+        })
+    }
 }
 
 /// Represents the following attributes:
@@ -267,6 +267,7 @@ fn fat_lto(
                 module_llvm: GccContext::parse(cgcx, &name, buffer.data(), dcx)?,
                 name: name.into_string().unwrap(),
                 kind: ModuleKind::Regular,
+                opt_level: None,
             }*/
         }
     };
@@ -207,6 +207,7 @@ fn module_codegen(
             name: cgu_name.to_string(),
             module_llvm: GccContext { context, should_combine_object_files: false, temp_dir: None },
             kind: ModuleKind::Regular,
+            opt_level: cgu.opt_level(),
         }
     }
 
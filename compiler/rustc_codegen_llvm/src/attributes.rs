@@ -15,7 +15,7 @@
 use crate::llvm::AttributePlace::Function;
 use crate::llvm::{self, AllocKindFlags, Attribute, AttributeKind, AttributePlace, MemoryEffects};
 use crate::llvm_util;
-pub use rustc_attr::{InlineAttr, InstructionSetAttr, OptimizeAttr};
+pub use rustc_attr::{InlineAttr, InstructionSetAttr, OptimizeAttr, OptimizeLevel};
 
 use crate::context::CodegenCx;
 use crate::value::Value;
@@ -307,15 +307,36 @@ pub fn from_fn_attrs<'ll, 'tcx>(
             to_add.push(llvm::AttributeKind::OptimizeForSize.create_attr(cx.llcx));
         }
         OptimizeAttr::Speed => {}
+        OptimizeAttr::Level(level) => match level {
+            OptimizeLevel::No => {
+                to_add.push(llvm::AttributeKind::OptimizeNone.create_attr(cx.llcx));
+            }
+            // LLVM has no attribute for these levels: the function is placed in a codegen unit
+            // optimized at its level instead.
+            OptimizeLevel::Less | OptimizeLevel::Default | OptimizeLevel::Aggressive => {}
+            OptimizeLevel::Size => {
+                to_add.push(llvm::AttributeKind::OptimizeForSize.create_attr(cx.llcx));
+            }
+            OptimizeLevel::SizeMin => {
+                to_add.push(llvm::AttributeKind::MinSize.create_attr(cx.llcx));
+                to_add.push(llvm::AttributeKind::OptimizeForSize.create_attr(cx.llcx));
+            }
+        },
     }
 
-    let inline =
-        if codegen_fn_attrs.inline == InlineAttr::None && instance.def.requires_inline(cx.tcx) {
+    if codegen_fn_attrs.optimize.do_not_optimize() {
+        // LLVM requires the functions that aren't optimized to never be inlined.
+        to_add.push(AttributeKind::NoInline.create_attr(cx.llcx));
+    } else {
+        let inline = if codegen_fn_attrs.inline == InlineAttr::None
+            && instance.def.requires_inline(cx.tcx)
+        {
             InlineAttr::Hint
         } else {
             codegen_fn_attrs.inline
         };
-    to_add.extend(inline_attr(cx, inline));
+        to_add.extend(inline_attr(cx, inline));
+    }
 
     // The `uwtable` attribute according to LLVM is:
     //
@@ -306,6 +306,7 @@ fn fat_lto(
                 module_llvm: ModuleLlvm::parse(cgcx, &name, buffer.data(), dcx)?,
                 name: name.into_string().unwrap(),
                 kind: ModuleKind::Regular,
+                opt_level: None,
             }
         }
     };
@@ -718,6 +719,7 @@ pub unsafe fn optimize_thin_module(
         module_llvm: ModuleLlvm { llmod_raw, llcx, tm: ManuallyDrop::new(tm) },
         name: thin_module.name().to_string(),
         kind: ModuleKind::Regular,
+        opt_level: None,
     };
     {
         let target = &*module.module_llvm.tm;
@@ -623,8 +623,16 @@ pub(crate) unsafe fn optimize(
     }
 
     if let Some(opt_level) = config.opt_level {
+        let opt_level = module.opt_level.unwrap_or(opt_level);
         let opt_stage = match cgcx.lto {
             Lto::Fat => llvm::OptStage::PreLinkFatLTO,
+            // The modules optimized at their own level are left out of the local ThinLTO, see
+            // `compute_per_cgu_lto_type`.
+            Lto::ThinLocal
+                if module.opt_level.is_some() && !cgcx.opts.cg.linker_plugin_lto.enabled() =>
+            {
+                llvm::OptStage::PreLinkNoLTO
+            }
             Lto::Thin | Lto::ThinLocal => llvm::OptStage::PreLinkThinLTO,
             _ if cgcx.opts.cg.linker_plugin_lto.enabled() => llvm::OptStage::PreLinkThinLTO,
             _ => llvm::OptStage::PreLinkNoLTO,
@@ -0,0 +1,213 @@
+//! The report of `-Z dump-code-size`: the size of the code in the object files, attributed to the
+//! crates, definitions and codegen units it was generated for.
+//!
+//! The symbols of the mono items are mapped to their definitions while the `TyCtxt` is available,
+//! and the sizes are read from the symbol tables of the object files before they are linked.
+
+use std::cmp;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use object::{Object, ObjectSection, ObjectSymbol, SectionIndex};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::unord::UnordMap;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::DumpMonoStatsFormat;
+use rustc_session::Session;
+use rustc_span::symbol::Symbol;
+
+use crate::CodegenResults;
+
+/// The definition a symbol was generated for.
+#[derive(Clone, Debug, Encodable, Decodable)]
+pub struct SymbolOrigin {
+    /// The name of the crate of the definition.
+    pub krate: Symbol,
+    /// The path of the definition, shared by all its instantiations.
+    pub definition: String,
+}
+
+/// Maps the symbols of the mono items of all the codegen units to their definitions.
+pub fn symbol_origins(tcx: TyCtxt<'_>) -> UnordMap<String, SymbolOrigin> {
+    let (_, codegen_units) = tcx.collect_and_partition_mono_items(());
+    let mut origins = UnordMap::default();
+    for cgu in codegen_units {
+        for item in cgu.items().keys() {
+            let def_id = item.def_id();
+            let origin = SymbolOrigin {
+                krate: tcx.crate_name(def_id.krate),
+                definition: with_no_trimmed_paths!(tcx.def_path_str(def_id)),
+            };
+            origins.insert(item.symbol_name(tcx).name.to_string(), origin);
+        }
+    }
+    origins
+}
+
+/// The size of a defined symbol of an object file.
+struct SymbolSize {
+    name: String,
+    size: u64,
+}
+
+/// Reads the sizes of the symbols defined by an object file.
+fn symbol_sizes(data: &[u8]) -> Result<Vec<SymbolSize>, object::Error> {
+    let file = object::File::parse(data)?;
+    let symbols: Vec<_> = file.symbols().filter(|symbol| symbol.is_definition()).collect();
+
+    // Some formats, like Mach-O, don't record the size of symbols: it's then the distance to the
+    // next symbol of the section, or to the end of the section.
+    let mut addresses: FxHashMap<SectionIndex, Vec<u64>> = FxHashMap::default();
+    for symbol in &symbols {
+        if let Some(section) = symbol.section_index() {
+            addresses.entry(section).or_default().push(symbol.address());
+        }
+    }
+    for addresses in addresses.values_mut() {
+        addresses.sort_unstable();
+    }
+
+    let mut sizes = Vec::with_capacity(symbols.len());
+    for symbol in symbols {
+        let mut size = symbol.size();
+        if size == 0
+            && let Some(section) = symbol.section_index()
+        {
+            let start = symbol.address();
+            let addresses = &addresses[&section];
+            let next = match addresses[addresses.partition_point(|&address| address <= start)..] {
+                [next, ..] => Some(next),
+                [] => file.section_by_index(section).ok().map(|s| s.address() + s.size()),
+            };
+            size = next.map_or(0, |end| end.saturating_sub(start));
+        }
+        sizes.push(SymbolSize { name: symbol.name()?.to_string(), size });
+    }
+    Ok(sizes)
+}
+
+/// The total size and number of symbols of a group of symbols.
+#[derive(Default)]
+struct Total {
+    size: u64,
+    symbols: usize,
+}
+
+impl Total {
+    fn add(&mut self, size: u64) {
+        self.size += size;
+        self.symbols += 1;
+    }
+}
+
+/// Sorts the groups from the largest to the smallest, and by name for equal sizes.
+fn sorted<K: Ord>(totals: FxHashMap<K, Total>) -> Vec<(K, Total)> {
+    let mut totals: Vec<_> = totals.into_iter().collect();
+    totals.sort_unstable_by(|(a_key, a), (b_key, b)| {
+        cmp::Reverse(a.size).cmp(&cmp::Reverse(b.size)).then_with(|| a_key.cmp(b_key))
+    });
+    totals
+}
+
+/// Writes the code size report of the object files of `codegen_results` to the output directory.
+pub fn dump_code_size(
+    sess: &Session,
+    codegen_results: &CodegenResults,
+    output_directory: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let crate_info = &codegen_results.crate_info;
+    let mut total = Total::default();
+    let mut crates: FxHashMap<String, Total> = FxHashMap::default();
+    let mut definitions: FxHashMap<(String, String), Total> = FxHashMap::default();
+    let mut codegen_units: FxHashMap<String, Total> = FxHashMap::default();
+    for module in codegen_results.modules.iter().chain(&codegen_results.allocator_module) {
+        let Some(object) = &module.object else { continue };
+        for SymbolSize { name, size } in symbol_sizes(&fs::read(object)?)? {
+            // The symbols of Mach-O object files have a leading underscore.
+            let origin = crate_info.symbol_origins.get(&name).or_else(|| {
+                name.strip_prefix('_').and_then(|name| crate_info.symbol_origins.get(name))
+            });
+            let (krate, definition) = match origin {
+                Some(origin) => (origin.krate.to_string(), origin.definition.clone()),
+                None => ("<unknown>".to_string(), name),
+            };
+            total.add(size);
+            crates.entry(krate.clone()).or_default().add(size);
+            definitions.entry((definition, krate)).or_default().add(size);
+            codegen_units.entry(module.name.clone()).or_default().add(size);
+        }
+    }
+
+    let output_directory = if let Some(ref directory) = output_directory {
+        fs::create_dir_all(directory)?;
+        directory
+    } else {
+        Path::new(".")
+    };
+    let format = sess.opts.unstable_opts.dump_code_size_format;
+    let crate_name = crate_info.local_crate_name;
+    let filename = format!("{crate_name}.code_size.{}", format.extension());
+    let mut file = BufWriter::new(File::create(output_directory.join(filename))?);
+
+    let (crates, definitions, codegen_units) =
+        (sorted(crates), sorted(definitions), sorted(codegen_units));
+    match format {
+        DumpMonoStatsFormat::Json => {
+            let group = |name: &str, total: &Total| {
+                serde_json::json!({ "name": name, "size": total.size, "symbols": total.symbols })
+            };
+            let report = serde_json::json!({
+                "crate": crate_name.as_str(),
+                "size": total.size,
+                "symbols": total.symbols,
+                "crates": crates.iter().map(|(name, total)| group(name, total)).collect::<Vec<_>>(),
+                "definitions": definitions
+                    .iter()
+                    .map(|((name, krate), total)| {
+                        let mut definition = group(name, total);
+                        definition["crate"] = krate.as_str().into();
+                        definition
+                    })
+                    .collect::<Vec<_>>(),
+                "codegen_units": codegen_units
+                    .iter()
+                    .map(|(name, total)| group(name, total))
+                    .collect::<Vec<_>>(),
+            });
+            serde_json::to_writer(&mut file, &report)?;
+        }
+        DumpMonoStatsFormat::Markdown => {
+            writeln!(file, "# Code size of `{crate_name}`")?;
+            writeln!(file)?;
+            writeln!(file, "{} bytes in {} symbols.", total.size, total.symbols)?;
+            writeln!(file)?;
+            writeln!(file, "## Crates")?;
+            writeln!(file)?;
+            writeln!(file, "| Crate | Size | Symbols |")?;
+            writeln!(file, "| --- | ---: | ---: |")?;
+            for (name, Total { size, symbols }) in crates {
+                writeln!(file, "| `{name}` | {size} | {symbols} |")?;
+            }
+            writeln!(file)?;
+            writeln!(file, "## Definitions")?;
+            writeln!(file)?;
+            writeln!(file, "| Definition | Crate | Size | Symbols |")?;
+            writeln!(file, "| --- | --- | ---: | ---: |")?;
+            for ((name, krate), Total { size, symbols }) in definitions {
+                writeln!(file, "| `{name}` | `{krate}` | {size} | {symbols} |")?;
+            }
+            writeln!(file)?;
+            writeln!(file, "## Codegen units")?;
+            writeln!(file)?;
+            writeln!(file, "| Codegen unit | Size | Symbols |")?;
+            writeln!(file, "| --- | ---: | ---: |")?;
+            for (name, Total { size, symbols }) in codegen_units {
+                writeln!(file, "| `{name}` | {size} | {symbols} |")?;
+            }
+        }
+    }
+    file.flush()?;
+    Ok(())
+}
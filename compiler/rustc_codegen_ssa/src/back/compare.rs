@@ -0,0 +1,102 @@
+//! Localizes the differences between two builds of the same output file, for
+//! `-Zverify-reproducible`.
+
+use object::read::archive::ArchiveFile;
+use object::{Object, ObjectSection};
+use rustc_data_structures::fx::FxIndexMap;
+
+/// Returns where two builds of an archive or an object file differ: the archive members, and the
+/// sections of the object files. Returns nothing for other kinds of files.
+pub fn artifact_differences(a: &[u8], b: &[u8]) -> Vec<String> {
+    let (Ok(a_archive), Ok(b_archive)) = (ArchiveFile::parse(a), ArchiveFile::parse(b)) else {
+        return object_differences(a, b);
+    };
+    let mut differences = Vec::new();
+    for (name, a_data, b_data) in pair_up(members(&a_archive, a), members(&b_archive, b)) {
+        match (a_data, b_data) {
+            (Some(a_data), Some(b_data)) if a_data != b_data => {
+                let member_differences = object_differences(a_data, b_data);
+                if member_differences.is_empty() {
+                    differences.push(format!("archive member `{name}`"));
+                }
+                differences.extend(
+                    member_differences
+                        .into_iter()
+                        .map(|difference| format!("archive member `{name}`, {difference}")),
+                );
+            }
+            (Some(_), Some(_)) => {}
+            _ => differences.push(format!("archive member `{name}`, only in one of the builds")),
+        }
+    }
+    if differences.is_empty() {
+        differences.push("archive headers".to_owned());
+    }
+    differences
+}
+
+fn object_differences(a: &[u8], b: &[u8]) -> Vec<String> {
+    let (Ok(a), Ok(b)) = (object::File::parse(a), object::File::parse(b)) else {
+        return Vec::new();
+    };
+    let mut differences = Vec::new();
+    for (name, a_data, b_data) in pair_up(sections(&a), sections(&b)) {
+        match (a_data, b_data) {
+            (Some(a_data), Some(b_data)) if a_data != b_data => {
+                differences.push(format!("section `{name}`"))
+            }
+            (Some(_), Some(_)) => {}
+            _ => differences.push(format!("section `{name}`, only in one of the builds")),
+        }
+    }
+    if differences.is_empty() {
+        differences.push("object file headers".to_owned());
+    }
+    differences
+}
+
+fn members<'a>(archive: &ArchiveFile<'a>, data: &'a [u8]) -> FxIndexMap<(String, usize), &'a [u8]> {
+    number_duplicates(archive.members().filter_map(|member| {
+        let member = member.ok()?;
+        Some((String::from_utf8_lossy(member.name()).into_owned(), member.data(data).ok()?))
+    }))
+}
+
+fn sections<'a>(file: &object::File<'a>) -> FxIndexMap<(String, usize), &'a [u8]> {
+    number_duplicates(file.sections().map(|section| {
+        let name = String::from_utf8_lossy(section.name_bytes().unwrap_or_default());
+        (name.into_owned(), section.data().unwrap_or_default())
+    }))
+}
+
+/// Keys the named parts of a file by their name and how many parts with that name come before
+/// them, as names aren't necessarily unique.
+fn number_duplicates<'a>(
+    parts: impl Iterator<Item = (String, &'a [u8])>,
+) -> FxIndexMap<(String, usize), &'a [u8]> {
+    let mut counts = FxIndexMap::default();
+    parts
+        .map(|(name, data)| {
+            let count = counts.entry(name.clone()).or_insert(0);
+            *count += 1;
+            ((name, *count - 1), data)
+        })
+        .collect()
+}
+
+/// Pairs up the parts of two builds of a file, in the order of the first build followed by the
+/// parts only in the second one.
+fn pair_up<'a>(
+    a: FxIndexMap<(String, usize), &'a [u8]>,
+    mut b: FxIndexMap<(String, usize), &'a [u8]>,
+) -> Vec<(String, Option<&'a [u8]>, Option<&'a [u8]>)> {
+    let mut pairs: Vec<_> = a
+        .into_iter()
+        .map(|(key, a_data)| {
+            let b_data = b.shift_remove(&key);
+            (key.0, Some(a_data), b_data)
+        })
+        .collect();
+    pairs.extend(b.into_iter().map(|((name, _), b_data)| (name, None, Some(b_data))));
+    pairs
+}
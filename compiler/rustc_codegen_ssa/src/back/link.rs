@@ -13,7 +13,9 @@
 use rustc_middle::middle::exported_symbols::SymbolExportKind;
 use rustc_session::config::LinkerFeaturesCli;
 use rustc_session::config::{self, CFGuard, CrateType, DebugInfo, OutFileName, Strip};
-use rustc_session::config::{OutputFilenames, OutputType, PrintKind, SplitDwarfKind};
+use rustc_session::config::{
+    OutputFilenames, OutputType, PrintKind, SplitDwarfKind, SwitchWithOptPath,
+};
 use rustc_session::cstore::DllImport;
 use rustc_session::output::{check_file_is_writeable, invalid_output_for_target, out_filename};
 use rustc_session::search_paths::PathKind;
@@ -30,6 +32,7 @@
 use rustc_target::spec::{RelocModel, RelroLevel, SanitizerSet, SplitDebuginfo};
 
 use super::archive::{ArchiveBuilder, ArchiveBuilderBuilder};
+use super::code_size::dump_code_size;
 use super::command::Command;
 use super::linker::{self, Linker};
 use super::metadata::{create_wrapper_file, MetadataPosition};
@@ -69,6 +72,12 @@ pub fn link_binary(
     outputs: &OutputFilenames,
 ) -> Result<(), ErrorGuaranteed> {
     let _timer = sess.timer("link_binary");
+    if let SwitchWithOptPath::Enabled(ref path) = sess.opts.unstable_opts.dump_code_size {
+        // The object files are read before linking, which may remove them.
+        if let Err(err) = dump_code_size(sess, codegen_results, path) {
+            sess.dcx().emit_fatal(errors::CouldntDumpCodeSize { error: err.to_string() });
+        }
+    }
     let output_metadata = sess.opts.output_types.contains_key(&OutputType::Metadata);
     let mut tempfiles_for_stdout_output: Vec<PathBuf> = Vec::new();
     for &crate_type in &codegen_results.crate_info.crate_types {
@@ -1,5 +1,7 @@
 pub mod archive;
+pub mod code_size;
 pub mod command;
+pub mod compare;
 pub mod link;
 pub mod linker;
 pub mod lto;
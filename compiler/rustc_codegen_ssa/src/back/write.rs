@@ -815,6 +815,7 @@ pub fn compute_per_cgu_lto_type(
     opts: &config::Options,
     sess_crate_types: &[CrateType],
     module_kind: ModuleKind,
+    module_opt_level: Option<config::OptLevel>,
 ) -> ComputedLtoType {
     // Metadata modules never participate in LTO regardless of the lto
     // settings.
@@ -833,6 +834,11 @@ pub fn compute_per_cgu_lto_type(
     // we'll encounter later.
     let is_allocator = module_kind == ModuleKind::Allocator;
 
+    // The ThinLTO of the codegen units of a crate would optimize again the modules optimized at
+    // another level than the crate's with `#[optimize(level = "...")]`, so they're left out of it.
+    // They're still optimized at the crate's level when LTO is requested.
+    let has_own_opt_level = module_opt_level.is_some();
+
     // We ignore a request for full crate graph LTO if the crate type
     // is only an rlib, as there is no full crate graph to process,
     // that'll happen later.
@@ -844,7 +850,9 @@ pub fn compute_per_cgu_lto_type(
     let is_rlib = sess_crate_types.len() == 1 && sess_crate_types[0] == CrateType::Rlib;
 
     match sess_lto {
-        Lto::ThinLocal if !linker_does_lto && !is_allocator => ComputedLtoType::Thin,
+        Lto::ThinLocal if !linker_does_lto && !is_allocator && !has_own_opt_level => {
+            ComputedLtoType::Thin
+        }
         Lto::Thin if !linker_does_lto && !is_rlib => ComputedLtoType::Thin,
         Lto::Fat if !is_rlib => ComputedLtoType::Fat,
         _ => ComputedLtoType::No,
@@ -867,7 +875,13 @@ fn execute_optimize_work_item<B: ExtraBackendMethods>(
     // back to the coordinator thread for further LTO processing (which
     // has to wait for all the initial modules to be optimized).
 
-    let lto_type = compute_per_cgu_lto_type(&cgcx.lto, &cgcx.opts, &cgcx.crate_types, module.kind);
+    let lto_type = compute_per_cgu_lto_type(
+        &cgcx.lto,
+        &cgcx.opts,
+        &cgcx.crate_types,
+        module.kind,
+        module.opt_level,
+    );
 
     // If we're doing some form of incremental LTO then we need to be sure to
     // save our module to disk first.
@@ -1,4 +1,5 @@
 use crate::assert_module_sources::CguReuse;
+use crate::back::code_size::symbol_origins;
 use crate::back::link::are_upstream_rust_objects_already_included;
 use crate::back::metadata::create_compressed_metadata_file;
 use crate::back::write::{
@@ -656,7 +657,12 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
         submit_codegened_module_to_llvm(
             &backend,
             &ongoing_codegen.coordinator.sender,
-            ModuleCodegen { name: llmod_id, module_llvm, kind: ModuleKind::Allocator },
+            ModuleCodegen {
+                name: llmod_id,
+                module_llvm,
+                kind: ModuleKind::Allocator,
+                opt_level: None,
+            },
             cost,
         );
     }
@@ -672,7 +678,7 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
     // Instead, we can compromise by ordering CGUs such that the largest and
     // smallest are first, second largest and smallest are next, etc. If there
     // are large size variations, this can reduce memory usage significantly.
-    let codegen_units: Vec<_> = {
+    let mut codegen_units: Vec<_> = {
         let mut sorted_cgus = codegen_units.iter().collect::<Vec<_>>();
         sorted_cgus.sort_by_key(|cgu| cmp::Reverse(cgu.size_estimate()));
 
@@ -680,6 +686,12 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
         first_half.iter().interleave(second_half.iter().rev()).copied().collect()
     };
 
+    // The second compilation of `-Zverify-reproducible` codegens the units in the opposite order,
+    // so that the outputs are checked not to depend on it.
+    if tcx.sess.opts.perturbed_build {
+        codegen_units.reverse();
+    }
+
     // Calculate the CGU reuse
     let cgu_reuse = tcx.sess.time("find_cgu_reuse", || {
         codegen_units.iter().map(|cgu| determine_cgu_reuse(tcx, cgu)).collect::<Vec<_>>()
@@ -867,6 +879,11 @@ pub fn new(tcx: TyCtxt<'_>, target_cpu: String) -> CrateInfo {
             dependency_formats: tcx.dependency_formats(()).clone(),
             windows_subsystem,
             natvis_debugger_visualizers: Default::default(),
+            symbol_origins: if tcx.sess.opts.unstable_opts.dump_code_size.enabled() {
+                symbol_origins(tcx)
+            } else {
+                Default::default()
+            },
         };
 
         info.native_libraries.reserve(n_crates);
@@ -996,7 +1013,9 @@ pub fn provide(providers: &mut Providers) {
         let any_for_speed = defids.items().any(|id| {
             let CodegenFnAttrs { optimize, .. } = tcx.codegen_fn_attrs(*id);
             match optimize {
-                attr::OptimizeAttr::None | attr::OptimizeAttr::Size => false,
+                attr::OptimizeAttr::None
+                | attr::OptimizeAttr::Size
+                | attr::OptimizeAttr::Level(_) => false,
                 attr::OptimizeAttr::Speed => true,
             }
         });
@@ -1043,6 +1062,7 @@ pub fn determine_cgu_reuse<'tcx>(tcx: TyCtxt<'tcx>, cgu: &CodegenUnit<'tcx>) ->
             &tcx.sess.opts,
             tcx.crate_types(),
             ModuleKind::Regular,
+            cgu.opt_level(),
         ) {
             ComputedLtoType::No => CguReuse::PostLto,
             _ => CguReuse::PreLto,
@@ -1,5 +1,5 @@
 use rustc_ast::{ast, attr, MetaItemKind, NestedMetaItem};
-use rustc_attr::{list_contains_name, InlineAttr, InstructionSetAttr, OptimizeAttr};
+use rustc_attr::{list_contains_name, InlineAttr, InstructionSetAttr, OptimizeAttr, OptimizeLevel};
 use rustc_errors::{codes::*, struct_span_code_err};
 use rustc_hir as hir;
 use rustc_hir::def::DefKind;
@@ -10,7 +10,7 @@
 use rustc_middle::query::Providers;
 use rustc_middle::ty::{self as ty, TyCtxt};
 use rustc_session::{lint, parse::feature_err};
-use rustc_span::symbol::Ident;
+use rustc_span::symbol::{Ident, Symbol};
 use rustc_span::{sym, Span};
 use rustc_target::spec::{abi, SanitizerSet};
 
@@ -505,6 +505,20 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: LocalDefId) -> CodegenFnAttrs {
                     OptimizeAttr::Size
                 } else if list_contains_name(items, sym::speed) {
                     OptimizeAttr::Speed
+                } else if items[0].has_name(sym::level) {
+                    let level = match items[0].value_str().as_ref().map(Symbol::as_str) {
+                        Some("0") => OptimizeLevel::No,
+                        Some("1") => OptimizeLevel::Less,
+                        Some("2") => OptimizeLevel::Default,
+                        Some("3") => OptimizeLevel::Aggressive,
+                        Some("s") => OptimizeLevel::Size,
+                        Some("z") => OptimizeLevel::SizeMin,
+                        _ => {
+                            err(items[0].span(), "invalid optimization level");
+                            return OptimizeAttr::None;
+                        }
+                    };
+                    OptimizeAttr::Level(level)
                 } else {
                     err(items[0].span(), "invalid argument");
                     OptimizeAttr::None
@@ -175,6 +175,12 @@ pub struct IgnoringOutput {
     pub extension: String,
 }
 
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_couldnt_dump_code_size)]
+pub struct CouldntDumpCodeSize {
+    pub error: String,
+}
+
 #[derive(Diagnostic)]
 #[diag(codegen_ssa_create_temp_dir)]
 pub struct CreateTempDir {
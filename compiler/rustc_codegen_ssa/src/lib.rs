@@ -23,6 +23,7 @@
 #[macro_use]
 extern crate rustc_middle;
 
+use crate::back::code_size::SymbolOrigin;
 use rustc_ast as ast;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::fx::FxIndexMap;
@@ -36,7 +37,7 @@
 use rustc_middle::util::Providers;
 use rustc_serialize::opaque::{FileEncoder, MemDecoder};
 use rustc_serialize::{Decodable, Decoder, Encodable, Encoder};
-use rustc_session::config::{CrateType, OutputFilenames, OutputType, RUST_CGU_EXT};
+use rustc_session::config::{CrateType, OptLevel, OutputFilenames, OutputType, RUST_CGU_EXT};
 use rustc_session::cstore::{self, CrateSource};
 use rustc_session::utils::NativeLibKind;
 use rustc_session::Session;
@@ -71,6 +72,9 @@ pub struct ModuleCodegen<M> {
     pub name: String,
     pub module_llvm: M,
     pub kind: ModuleKind,
+    /// The level to optimize the module at instead of the one of the session, for the codegen
+    /// units of the functions with another `#[optimize(level = "...")]`.
+    pub opt_level: Option<OptLevel>,
 }
 
 impl<M> ModuleCodegen<M> {
@@ -183,6 +187,8 @@ pub struct CrateInfo {
     pub dependency_formats: Lrc<Dependencies>,
     pub windows_subsystem: Option<String>,
     pub natvis_debugger_visualizers: BTreeSet<DebuggerVisualizerFile>,
+    /// The definitions of the symbols of the mono items, only computed for `-Z dump-code-size`.
+    pub symbol_origins: UnordMap<String, SymbolOrigin>,
 }
 
 #[derive(Encodable, Decodable)]
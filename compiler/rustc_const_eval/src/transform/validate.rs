@@ -35,90 +35,114 @@ pub struct Validator {
 
 impl<'tcx> MirPass<'tcx> for Validator {
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
-        // FIXME(JakobDegen): These bodies never instantiated in codegend anyway, so it's not
-        // terribly important that they pass the validator. However, I think other passes might
-        // still see them, in which case they might be surprised. It would probably be better if we
-        // didn't put this through the MIR pipeline at all.
-        if matches!(body.source.instance, InstanceDef::Intrinsic(..) | InstanceDef::Virtual(..)) {
-            return;
+        for (location, msg) in validate_body(tcx, &self.when, self.mir_phase, body) {
+            // We might see broken MIR when other errors have already occurred.
+            assert!(
+                tcx.dcx().has_errors().is_some(),
+                "broken MIR in {:?} ({}) at {:?}:\n{}",
+                body.source.instance,
+                self.when,
+                location,
+                msg,
+            );
         }
-        let def_id = body.source.def_id();
-        let mir_phase = self.mir_phase;
-        let param_env = match mir_phase.reveal() {
-            Reveal::UserFacing => tcx.param_env(def_id),
-            Reveal::All => tcx.param_env_reveal_all_normalized(def_id),
-        };
-
-        let can_unwind = if mir_phase <= MirPhase::Runtime(RuntimePhase::Initial) {
-            // In this case `AbortUnwindingCalls` haven't yet been executed.
-            true
-        } else if !tcx.def_kind(def_id).is_fn_like() {
-            true
-        } else {
-            let body_ty = tcx.type_of(def_id).skip_binder();
-            let body_abi = match body_ty.kind() {
-                ty::FnDef(..) => body_ty.fn_sig(tcx).abi(),
-                ty::Closure(..) => Abi::RustCall,
-                ty::CoroutineClosure(..) => Abi::RustCall,
-                ty::Coroutine(..) => Abi::Rust,
-                // No need to do MIR validation on error bodies
-                ty::Error(_) => return,
-                _ => {
-                    span_bug!(body.span, "unexpected body ty: {:?} phase {:?}", body_ty, mir_phase)
-                }
-            };
-
-            ty::layout::fn_can_unwind(tcx, Some(def_id), body_abi)
-        };
+    }
+}
 
-        let mut cfg_checker = CfgChecker {
-            when: &self.when,
-            body,
-            tcx,
-            mir_phase,
-            unwind_edge_count: 0,
-            reachable_blocks: traversal::reachable_as_bitset(body),
-            value_cache: FxHashSet::default(),
-            can_unwind,
+/// Checks that `body` upholds the invariants of `mir_phase`, like the [`Validator`] pass, but
+/// returns the violations instead of reporting them as bugs: for MIR which doesn't come from
+/// the compiler.
+pub fn validate_body<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    when: &str,
+    mir_phase: MirPhase,
+    body: &Body<'tcx>,
+) -> Vec<(Location, String)> {
+    // FIXME(JakobDegen): These bodies never instantiated in codegend anyway, so it's not
+    // terribly important that they pass the validator. However, I think other passes might
+    // still see them, in which case they might be surprised. It would probably be better if we
+    // didn't put this through the MIR pipeline at all.
+    if matches!(body.source.instance, InstanceDef::Intrinsic(..) | InstanceDef::Virtual(..)) {
+        return Vec::new();
+    }
+    let def_id = body.source.def_id();
+    let param_env = match mir_phase.reveal() {
+        Reveal::UserFacing => tcx.param_env(def_id),
+        Reveal::All => tcx.param_env_reveal_all_normalized(def_id),
+    };
+
+    let can_unwind = if mir_phase <= MirPhase::Runtime(RuntimePhase::Initial) {
+        // In this case `AbortUnwindingCalls` haven't yet been executed.
+        true
+    } else if !tcx.def_kind(def_id).is_fn_like() {
+        true
+    } else {
+        let body_ty = tcx.type_of(def_id).skip_binder();
+        let body_abi = match body_ty.kind() {
+            ty::FnDef(..) => body_ty.fn_sig(tcx).abi(),
+            ty::Closure(..) => Abi::RustCall,
+            ty::CoroutineClosure(..) => Abi::RustCall,
+            ty::Coroutine(..) => Abi::Rust,
+            // No need to do MIR validation on error bodies
+            ty::Error(_) => return Vec::new(),
+            _ => {
+                span_bug!(body.span, "unexpected body ty: {:?} phase {:?}", body_ty, mir_phase)
+            }
         };
-        cfg_checker.visit_body(body);
-        cfg_checker.check_cleanup_control_flow();
 
-        // Also run the TypeChecker.
-        for (location, msg) in validate_types(tcx, self.mir_phase, param_env, body, body) {
-            cfg_checker.fail(location, msg);
-        }
-
-        if let MirPhase::Runtime(_) = body.phase {
-            if let ty::InstanceDef::Item(_) = body.source.instance {
-                if body.has_free_regions() {
-                    cfg_checker.fail(
-                        Location::START,
-                        format!("Free regions in optimized {} MIR", body.phase.name()),
-                    );
-                }
-            }
-        }
+        ty::layout::fn_can_unwind(tcx, Some(def_id), body_abi)
+    };
+
+    let mut cfg_checker = CfgChecker {
+        when,
+        body,
+        tcx,
+        mir_phase,
+        unwind_edge_count: 0,
+        reachable_blocks: traversal::reachable_as_bitset(body),
+        value_cache: FxHashSet::default(),
+        can_unwind,
+        failures: Vec::new(),
+    };
+    cfg_checker.visit_body(body);
+    cfg_checker.check_cleanup_control_flow();
+
+    // Also run the TypeChecker.
+    for (location, msg) in validate_types(tcx, mir_phase, param_env, body, body) {
+        cfg_checker.fail(location, msg);
+    }
 
-        // Enforce that coroutine-closure layouts are identical.
-        if let Some(layout) = body.coroutine_layout_raw()
-            && let Some(by_move_body) = body.coroutine_by_move_body()
-            && let Some(by_move_layout) = by_move_body.coroutine_layout_raw()
-        {
-            // FIXME(async_closures): We could do other validation here?
-            if layout.variant_fields.len() != by_move_layout.variant_fields.len() {
+    if let MirPhase::Runtime(_) = body.phase {
+        if let ty::InstanceDef::Item(_) = body.source.instance {
+            if body.has_free_regions() {
                 cfg_checker.fail(
                     Location::START,
-                    format!(
-                        "Coroutine layout has different number of variant fields from \
-                        by-move coroutine layout:\n\
-                        layout: {layout:#?}\n\
-                        by_move_layout: {by_move_layout:#?}",
-                    ),
+                    format!("Free regions in optimized {} MIR", body.phase.name()),
                 );
             }
         }
     }
+
+    // Enforce that coroutine-closure layouts are identical.
+    if let Some(layout) = body.coroutine_layout_raw()
+        && let Some(by_move_body) = body.coroutine_by_move_body()
+        && let Some(by_move_layout) = by_move_body.coroutine_layout_raw()
+    {
+        // FIXME(async_closures): We could do other validation here?
+        if layout.variant_fields.len() != by_move_layout.variant_fields.len() {
+            cfg_checker.fail(
+                Location::START,
+                format!(
+                    "Coroutine layout has different number of variant fields from \
+                    by-move coroutine layout:\n\
+                    layout: {layout:#?}\n\
+                    by_move_layout: {by_move_layout:#?}",
+                ),
+            );
+        }
+    }
+
+    cfg_checker.failures
 }
 
 struct CfgChecker<'a, 'tcx> {
@@ -132,20 +156,12 @@ struct CfgChecker<'a, 'tcx> {
     // If `false`, then the MIR must not contain `UnwindAction::Continue` or
     // `TerminatorKind::Resume`.
     can_unwind: bool,
+    failures: Vec<(Location, String)>,
 }
 
 impl<'a, 'tcx> CfgChecker<'a, 'tcx> {
-    #[track_caller]
-    fn fail(&self, location: Location, msg: impl AsRef<str>) {
-        // We might see broken MIR when other errors have already occurred.
-        assert!(
-            self.tcx.dcx().has_errors().is_some(),
-            "broken MIR in {:?} ({}) at {:?}:\n{}",
-            self.body.source.instance,
-            self.when,
-            location,
-            msg.as_ref(),
-        );
+    fn fail(&mut self, location: Location, msg: impl Into<String>) {
+        self.failures.push((location, msg.into()));
     }
 
     fn check_edge(&mut self, location: Location, bb: BasicBlock, edge_kind: EdgeKind) {
@@ -182,11 +198,12 @@ fn check_edge(&mut self, location: Location, bb: BasicBlock, edge_kind: EdgeKind
         }
     }
 
-    fn check_cleanup_control_flow(&self) {
+    fn check_cleanup_control_flow(&mut self) {
         if self.unwind_edge_count <= 1 {
             return;
         }
-        let doms = self.body.basic_blocks.dominators();
+        let body = self.body;
+        let doms = body.basic_blocks.dominators();
         let mut post_contract_node = FxHashMap::default();
         // Reusing the allocation across invocations of the closure
         let mut dom_path = vec![];
@@ -197,7 +214,7 @@ fn check_cleanup_control_flow(&self) {
                 }
                 let parent = doms.immediate_dominator(bb).unwrap();
                 dom_path.push(bb);
-                if !self.body.basic_blocks[parent].is_cleanup {
+                if !body.basic_blocks[parent].is_cleanup {
                     break bb;
                 }
                 bb = parent;
@@ -208,8 +225,8 @@ fn check_cleanup_control_flow(&self) {
             root
         };
 
-        let mut parent = IndexVec::from_elem(None, &self.body.basic_blocks);
-        for (bb, bb_data) in self.body.basic_blocks.iter_enumerated() {
+        let mut parent = IndexVec::from_elem(None, &body.basic_blocks);
+        for (bb, bb_data) in body.basic_blocks.iter_enumerated() {
             if !bb_data.is_cleanup || !self.reachable_blocks.contains(bb) {
                 continue;
             }
@@ -2,13 +2,15 @@
 //! ordering. This is a useful property for deterministic computations, such
 //! as required by the query system.
 
-use rustc_hash::{FxHashMap, FxHashSet};
+use rustc_hash::{FxHashSet, FxHasher};
 use std::{
     borrow::{Borrow, BorrowMut},
     collections::hash_map::Entry,
-    hash::Hash,
+    collections::{HashMap, HashSet},
+    hash::{BuildHasher, Hash, Hasher},
     iter::{Product, Sum},
     ops::Index,
+    sync::atomic::{AtomicU64, Ordering},
 };
 
 use crate::{
@@ -212,6 +214,49 @@ pub fn collect_stable_ord_by_key<K, C, P>(self, project_to_key: P) -> C
 /// A marker trait specifying that `Self` can consume `UnordItems<_>` without
 /// exposing any internal ordering.
 ///
+/// The seed of the hashers of the unordered collections created from now on, see
+/// [`perturb_iteration_order`].
+static ITERATION_ORDER_SEED: AtomicU64 = AtomicU64::new(0);
+
+/// Makes the unordered collections created from now on iterate over their items in another order,
+/// by seeding the hashes of their keys with `seed`. A `seed` of zero restores the usual order.
+///
+/// This is used by `-Zverify-reproducible` to check that the output of the compiler doesn't depend
+/// on the order in which it iterates over these collections.
+pub fn perturb_iteration_order(seed: u64) {
+    ITERATION_ORDER_SEED.store(seed, Ordering::Relaxed);
+}
+
+/// The hasher of the unordered collections: `FxHasher`, seeded with the value of
+/// [`ITERATION_ORDER_SEED`] when the collection was created.
+#[derive(Debug, Clone, Copy)]
+struct UnordBuildHasher {
+    seed: u64,
+}
+
+impl Default for UnordBuildHasher {
+    #[inline]
+    fn default() -> Self {
+        UnordBuildHasher { seed: ITERATION_ORDER_SEED.load(Ordering::Relaxed) }
+    }
+}
+
+impl BuildHasher for UnordBuildHasher {
+    type Hasher = FxHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> FxHasher {
+        let mut hasher = FxHasher::default();
+        if self.seed != 0 {
+            hasher.write_u64(self.seed);
+        }
+        hasher
+    }
+}
+
+type UnordHashMap<K, V> = HashMap<K, V, UnordBuildHasher>;
+type UnordHashSet<V> = HashSet<V, UnordBuildHasher>;
+
 /// Note: right now this is just a marker trait. It could be extended to contain
 /// some useful, common methods though, like `len`, `clear`, or the various
 /// kinds of `to_sorted`.
@@ -228,7 +273,7 @@ trait UnordCollection {}
 /// for more information.
 #[derive(Debug, Eq, PartialEq, Clone, Encodable_Generic, Decodable_Generic)]
 pub struct UnordSet<V: Eq + Hash> {
-    inner: FxHashSet<V>,
+    inner: UnordHashSet<V>,
 }
 
 impl<V: Eq + Hash> UnordCollection for UnordSet<V> {}
@@ -236,7 +281,7 @@ impl<V: Eq + Hash> UnordCollection for UnordSet<V> {}
 impl<V: Eq + Hash> Default for UnordSet<V> {
     #[inline]
     fn default() -> Self {
-        Self { inner: FxHashSet::default() }
+        Self { inner: UnordHashSet::default() }
     }
 }
 
@@ -248,7 +293,7 @@ pub fn new() -> Self {
 
     #[inline]
     pub fn with_capacity(capacity: usize) -> Self {
-        Self { inner: FxHashSet::with_capacity_and_hasher(capacity, Default::default()) }
+        Self { inner: UnordHashSet::with_capacity_and_hasher(capacity, Default::default()) }
     }
 
     #[inline]
@@ -385,19 +430,19 @@ fn extend<T: IntoIterator<Item = V>>(&mut self, iter: T) {
 impl<V: Hash + Eq> FromIterator<V> for UnordSet<V> {
     #[inline]
     fn from_iter<T: IntoIterator<Item = V>>(iter: T) -> Self {
-        UnordSet { inner: FxHashSet::from_iter(iter) }
+        UnordSet { inner: UnordHashSet::from_iter(iter) }
     }
 }
 
 impl<V: Hash + Eq> From<FxHashSet<V>> for UnordSet<V> {
     fn from(value: FxHashSet<V>) -> Self {
-        UnordSet { inner: value }
+        UnordSet { inner: UnordHashSet::from_iter(value) }
     }
 }
 
 impl<V: Hash + Eq, I: Iterator<Item = V>> From<UnordItems<V, I>> for UnordSet<V> {
     fn from(value: UnordItems<V, I>) -> Self {
-        UnordSet { inner: FxHashSet::from_iter(value.0) }
+        UnordSet { inner: UnordHashSet::from_iter(value.0) }
     }
 }
 
@@ -419,7 +464,7 @@ fn hash_stable(&self, hcx: &mut HCX, hasher: &mut StableHasher) {
 /// for more information.
 #[derive(Debug, Eq, PartialEq, Clone, Encodable_Generic, Decodable_Generic)]
 pub struct UnordMap<K: Eq + Hash, V> {
-    inner: FxHashMap<K, V>,
+    inner: UnordHashMap<K, V>,
 }
 
 impl<K: Eq + Hash, V> UnordCollection for UnordMap<K, V> {}
@@ -427,7 +472,7 @@ impl<K: Eq + Hash, V> UnordCollection for UnordMap<K, V> {}
 impl<K: Eq + Hash, V> Default for UnordMap<K, V> {
     #[inline]
     fn default() -> Self {
-        Self { inner: FxHashMap::default() }
+        Self { inner: UnordHashMap::default() }
     }
 }
 
@@ -441,21 +486,21 @@ fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
 impl<K: Hash + Eq, V> FromIterator<(K, V)> for UnordMap<K, V> {
     #[inline]
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
-        UnordMap { inner: FxHashMap::from_iter(iter) }
+        UnordMap { inner: UnordHashMap::from_iter(iter) }
     }
 }
 
 impl<K: Hash + Eq, V, I: Iterator<Item = (K, V)>> From<UnordItems<(K, V), I>> for UnordMap<K, V> {
     #[inline]
     fn from(items: UnordItems<(K, V), I>) -> Self {
-        UnordMap { inner: FxHashMap::from_iter(items.0) }
+        UnordMap { inner: UnordHashMap::from_iter(items.0) }
     }
 }
 
 impl<K: Eq + Hash, V> UnordMap<K, V> {
     #[inline]
     pub fn with_capacity(capacity: usize) -> Self {
-        Self { inner: FxHashMap::with_capacity_and_hasher(capacity, Default::default()) }
+        Self { inner: UnordHashMap::with_capacity_and_hasher(capacity, Default::default()) }
     }
 
     #[inline]
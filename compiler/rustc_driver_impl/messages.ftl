@@ -1,3 +1,5 @@
+driver_impl_dump_stable_mir_failed = failed to write the StableMIR to `{$path}`: {$error}
+
 driver_impl_ice = the compiler unexpectedly panicked. this is a bug.
 driver_impl_ice_bug_report = we would appreciate a bug report: {$bug_report_url}
 driver_impl_ice_bug_report_internal_feature = using internal features is not supported and expected to cause internal compiler errors when used incorrectly
@@ -10,6 +12,12 @@ driver_impl_ice_path_error = the ICE couldn't be written to `{$path}`: {$error}
 driver_impl_ice_path_error_env = the environment variable `RUSTC_ICE` is set to `{$env_var}`
 driver_impl_ice_version = rustc {$version} running on {$triple}
 
+driver_impl_mir_pass_stats_failed = failed to write the MIR pass statistics to `{$path}`: {$error}
+
+driver_impl_not_reproducible = `{$path}` differs between two compilations of the crate
+driver_impl_not_reproducible_differences = the differences are in: {$differences}
+driver_impl_not_reproducible_missing = it wasn't produced by the first compilation
+
 driver_impl_rlink_empty_version_number = The input does not contain version number
 
 driver_impl_rlink_encoding_version_mismatch = .rlink file was produced with encoding version `{$version_array}`, but the current version is `{$rlink_version}`
@@ -21,3 +29,7 @@ driver_impl_rlink_rustc_version_mismatch = .rlink file was produced by rustc ver
 driver_impl_rlink_unable_to_read = failed to read rlink file: `{$err}`
 
 driver_impl_rlink_wrong_file_type = The input does not look like a .rlink file
+
+driver_impl_verify_reproducible_failed = the second compilation of `-Zverify-reproducible` failed: {$error}
+
+driver_impl_verify_reproducible_stdin = `-Zverify-reproducible` can't be used when the crate is read from the standard input
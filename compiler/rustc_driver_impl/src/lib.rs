@@ -20,6 +20,7 @@
 use rustc_ast as ast;
 use rustc_codegen_ssa::{traits::CodegenBackend, CodegenErrors, CodegenResults};
 use rustc_const_eval::CTRL_C_RECEIVED;
+use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::profiling::{
     get_resident_set_size, print_time_passes_entry, TimePassesFormat,
 };
@@ -35,9 +36,11 @@
 use rustc_metadata::creader::MetadataLoader;
 use rustc_metadata::locator;
 use rustc_session::config::{nightly_options, CG_OPTIONS, Z_OPTIONS};
-use rustc_session::config::{ErrorOutputType, Input, OutFileName, OutputType};
+use rustc_session::config::{ErrorOutputType, Input, OutFileName, OutputType, SwitchWithOptPath};
 use rustc_session::getopts::{self, Matches};
-use rustc_session::lint::{Lint, LintId};
+use rustc_session::cstore::CrateDepKind;
+use rustc_session::lint::builtin::WARNINGS;
+use rustc_session::lint::{Level, Lint, LintId};
 use rustc_session::output::collect_crate_types;
 use rustc_session::{config, filesearch, EarlyDiagCtxt, Session};
 use rustc_span::def_id::LOCAL_CRATE;
@@ -55,7 +58,7 @@
 use std::fs::{self, File};
 use std::io::{self, IsTerminal, Read, Write};
 use std::panic::{self, catch_unwind, PanicInfo};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{self, Command, Stdio};
 use std::str;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -82,9 +85,12 @@
 use {do_not_use_print as print, do_not_use_print as println};
 
 pub mod args;
+mod mir_pass_stats;
 pub mod pretty;
 #[macro_use]
 mod print;
+mod reproducible;
+mod server;
 mod session_diagnostics;
 #[cfg(all(unix, any(target_env = "gnu", target_os = "macos")))]
 mod signal_handler;
@@ -97,8 +103,8 @@ pub(super) fn install() {}
 }
 
 use crate::session_diagnostics::{
-    RLinkEmptyVersionNumber, RLinkEncodingVersionMismatch, RLinkRustcVersionMismatch,
-    RLinkWrongFileType, RlinkNotAFile, RlinkUnableToRead,
+    DumpStableMirFailed, RLinkEmptyVersionNumber, RLinkEncodingVersionMismatch,
+    RLinkRustcVersionMismatch, RLinkWrongFileType, RlinkNotAFile, RlinkUnableToRead,
 };
 
 rustc_fluent_macro::fluent_messages! { "../messages.ftl" }
@@ -298,6 +304,18 @@ fn run_compiler(
 
     let Some(matches) = handle_options(&default_early_dcx, &args) else { return Ok(()) };
 
+    if let Some(socket) = matches.opt_str("server") {
+        if !matches.free.is_empty() {
+            default_early_dcx.early_fatal("`--server` does not take an input file");
+        }
+        return server::run(
+            &default_early_dcx,
+            Path::new(&socket),
+            callbacks,
+            using_internal_features,
+        );
+    }
+
     let sopts = config::build_session_options(&mut default_early_dcx, &matches);
 
     if let Some(ref code) = matches.opt_str("explain") {
@@ -305,6 +323,11 @@ fn run_compiler(
         return Ok(());
     }
 
+    let verify_reproducible = sopts
+        .unstable_opts
+        .verify_reproducible
+        .then(|| (at_args.to_vec(), Arc::clone(&using_internal_features)));
+
     let (odir, ofile) = make_output(&matches);
     let mut config = interface::Config {
         opts: sopts,
@@ -440,6 +463,18 @@ fn run_compiler(
 
             queries.global_ctxt()?.enter(|tcx| tcx.analysis(()))?;
 
+            if let Some(path) = &sess.opts.unstable_opts.dump_stable_mir {
+                queries
+                    .global_ctxt()?
+                    .enter(|tcx| rustc_smir::rustc_internal::dump::write_smir_dump(tcx, path))
+                    .map_err(|error| {
+                        sess.dcx().emit_err(DumpStableMirFailed {
+                            path: path.display().to_string(),
+                            error: error.to_string(),
+                        })
+                    })?;
+            }
+
             if callbacks.after_analysis(compiler, queries) == Compilation::Stop {
                 return early_exit();
             }
@@ -458,14 +493,27 @@ fn run_compiler(
                 sess.code_stats.print_vtable_sizes(crate_name);
             }
 
+            if let SwitchWithOptPath::Enabled(ref directory) =
+                sess.opts.unstable_opts.mir_pass_stats
+            {
+                let crate_name = queries.global_ctxt()?.enter(|tcx| tcx.crate_name(LOCAL_CRATE));
+
+                mir_pass_stats::write(sess, crate_name, directory);
+            }
+
             Ok(Some(linker))
         })?;
 
         // Linking is done outside the `compiler.enter()` so that the
         // `GlobalCtxt` within `Queries` can be freed as early as possible.
         if let Some(linker) = linker {
-            let _timer = sess.timer("link");
-            linker.link(sess, codegen_backend)?
+            {
+                let _timer = sess.timer("link");
+                linker.link(sess, codegen_backend)?;
+            }
+            if let Some((at_args, using_internal_features)) = verify_reproducible {
+                reproducible::verify(sess, codegen_backend, &at_args, using_internal_features)?;
+            }
         }
 
         if sess.opts.unstable_opts.print_fuel.is_some() {
@@ -693,7 +741,7 @@ fn print_crate_info(
     early_dcx: &EarlyDiagCtxt,
     codegen_backend: &dyn CodegenBackend,
     sess: &Session,
-    parse_attrs: bool,
+    has_input: bool,
 ) -> Compilation {
     use rustc_session::config::PrintKind::*;
 
@@ -709,6 +757,9 @@ fn print_crate_info(
         return Compilation::Continue;
     }
 
+    // The input of `--print=crate-info` is a crate file rather than source code.
+    let parse_attrs =
+        has_input && !sess.opts.prints.iter().any(|p| matches!(p.kind, CrateInfo | CrateInfoJson));
     let attrs = if parse_attrs {
         let result = parse_crate_attrs(sess);
         match result {
@@ -841,6 +892,31 @@ fn print_crate_info(
                         .early_fatal("only Apple targets currently support deployment version info")
                 }
             }
+            Lints | LintsJson => write_lints(sess, req.kind == LintsJson, &mut crate_info),
+            UnstableFeatures | UnstableFeaturesJson => {
+                write_unstable_features(req.kind == UnstableFeaturesJson, &mut crate_info)
+            }
+            CrateInfo | CrateInfoJson => {
+                let path = match &sess.io.input {
+                    Input::File(path) if has_input => path,
+                    Input::File(_) => early_dcx.early_fatal("no input filename given"),
+                    Input::Str { .. } => early_dcx.early_fatal("cannot print crate info for stdin"),
+                };
+                let metadata_loader = codegen_backend.metadata_loader();
+                let info = locator::crate_file_info(
+                    &sess.target,
+                    path,
+                    &*metadata_loader,
+                    sess.cfg_version,
+                )
+                .unwrap_or_else(|err| {
+                    early_dcx.early_fatal(format!(
+                        "failed to read the metadata of `{}`: {err}",
+                        path.display()
+                    ))
+                });
+                write_crate_file_info(&info, req.kind == CrateInfoJson, &mut crate_info);
+            }
         }
 
         req.out.overwrite(&crate_info, sess);
@@ -848,6 +924,174 @@ fn print_crate_info(
     Compilation::Stop
 }
 
+/// Writes every lint with its default level, the lint groups it is part of, and its level under
+/// the command-line flags, for `--print=lints`.
+fn write_lints(sess: &Session, json: bool, out: &mut String) {
+    let lint_store = unerased_lint_store(sess);
+
+    let mut groups: FxHashMap<LintId, Vec<&'static str>> = FxHashMap::default();
+    for (group, lints, _) in lint_store.get_lint_groups() {
+        for lint in lints {
+            groups.entry(lint).or_default().push(group);
+        }
+    }
+
+    // Mirrors how the lint levels set on the command line (and in the `[lints]` table of the
+    // `--lint-config` file) are applied, before any attribute of the crate is taken into account.
+    let command_line_level = |lint: LintId| {
+        let mut level = lint.lint.default_level(sess.edition());
+        let lint_config_levels = sess.opts.lint_config.iter().flat_map(|config| &config.levels);
+        for (name, opt_level) in lint_config_levels.chain(&sess.opts.lint_opts) {
+            // `--force-warn` and `-F` can't be overridden by the following flags.
+            if matches!(level, Level::ForceWarn(_) | Level::Forbid) {
+                break;
+            }
+            if lint_store.find_lints(name).is_ok_and(|lints| lints.contains(&lint)) {
+                level = *opt_level;
+            }
+        }
+        level
+    };
+    let warnings = LintId::of(WARNINGS);
+    let warnings_level = command_line_level(warnings);
+
+    let mut lints = lint_store.get_lints().to_vec();
+    lints.sort_by_key(|lint| lint.name);
+    let lints = lints.into_iter().map(|lint| {
+        let id = LintId::of(lint);
+        let mut level = command_line_level(id);
+        if level == Level::Warn && id != warnings {
+            level = warnings_level;
+        }
+        if let Some(cap) = sess.opts.lint_cap {
+            level = level.min(cap);
+        }
+        let mut groups = groups.remove(&id).unwrap_or_default();
+        groups.sort();
+        (lint, level, groups)
+    });
+
+    if json {
+        let lints: Vec<_> = lints
+            .map(|(lint, level, groups)| {
+                serde_json::json!({
+                    "name": lint.name_lower(),
+                    "description": lint.desc,
+                    "default_level": lint.default_level(sess.edition()).as_str(),
+                    "level": level.as_str(),
+                    "active": level != Level::Allow,
+                    "groups": groups,
+                })
+            })
+            .collect();
+        writeln!(out, "{}", serde_json::to_string_pretty(&lints).unwrap()).unwrap();
+    } else {
+        for (lint, level, groups) in lints {
+            write!(
+                out,
+                "{} default={} level={}",
+                lint.name_lower(),
+                lint.default_level(sess.edition()).as_str(),
+                level.as_str()
+            )
+            .unwrap();
+            if !groups.is_empty() {
+                write!(out, " groups={}", groups.join(",")).unwrap();
+            }
+            writeln!(out).unwrap();
+        }
+    }
+}
+
+/// Writes every unstable language feature with its tracking issue, for
+/// `--print=unstable-features`.
+fn write_unstable_features(json: bool, out: &mut String) {
+    let features = rustc_feature::Features::default();
+    let mut unstable_features: Vec<_> =
+        rustc_feature::UNSTABLE_FEATURES.iter().map(|f| &f.feature).collect();
+    unstable_features.sort_by(|a, b| a.name.as_str().cmp(b.name.as_str()));
+    let unstable_features = unstable_features.into_iter().map(|feature| {
+        let status = if features.incomplete(feature.name) {
+            "incomplete"
+        } else if features.internal(feature.name) {
+            "internal"
+        } else {
+            "unstable"
+        };
+        (feature, status)
+    });
+
+    if json {
+        let unstable_features: Vec<_> = unstable_features
+            .map(|(feature, status)| {
+                serde_json::json!({
+                    "name": feature.name.as_str(),
+                    "since": feature.since,
+                    "status": status,
+                    "issue": feature.issue(),
+                })
+            })
+            .collect();
+        writeln!(out, "{}", serde_json::to_string_pretty(&unstable_features).unwrap()).unwrap();
+    } else {
+        for (feature, status) in unstable_features {
+            write!(out, "{} status={status}", feature.name).unwrap();
+            if let Some(issue) = feature.issue() {
+                write!(out, " issue=https://github.com/rust-lang/rust/issues/{issue}").unwrap();
+            }
+            writeln!(out).unwrap();
+        }
+    }
+}
+
+/// Writes the information read from the metadata of a crate file, for `--print=crate-info`.
+fn write_crate_file_info(info: &locator::CrateFileInfo, json: bool, out: &mut String) {
+    let dep_kind = |kind| match kind {
+        CrateDepKind::MacrosOnly => "macros-only",
+        CrateDepKind::Implicit => "implicit",
+        CrateDepKind::Explicit => "explicit",
+    };
+
+    if json {
+        let dependencies: Vec<_> = info
+            .dependencies
+            .iter()
+            .map(|dep| {
+                serde_json::json!({
+                    "name": dep.name.as_str(),
+                    "hash": dep.hash.to_string(),
+                    "kind": dep_kind(dep.kind),
+                    "public": !dep.is_private,
+                })
+            })
+            .collect();
+        let info = serde_json::json!({
+            "name": info.name.as_str(),
+            "edition": info.edition.to_string(),
+            "hash": info.hash.to_string(),
+            "triple": info.triple.triple(),
+            "dependencies": dependencies,
+        });
+        writeln!(out, "{}", serde_json::to_string_pretty(&info).unwrap()).unwrap();
+    } else {
+        writeln!(out, "name {}", info.name).unwrap();
+        writeln!(out, "edition {}", info.edition).unwrap();
+        writeln!(out, "hash {}", info.hash).unwrap();
+        writeln!(out, "triple {}", info.triple.triple()).unwrap();
+        for dep in &info.dependencies {
+            writeln!(
+                out,
+                "dependency {} hash {} kind {} {}",
+                dep.name,
+                dep.hash,
+                dep_kind(dep.kind),
+                if dep.is_private { "private" } else { "public" }
+            )
+            .unwrap();
+        }
+    }
+}
+
 /// Prints version information
 ///
 /// NOTE: this is a macro to support drivers built at a different time than the main `rustc_driver` crate.
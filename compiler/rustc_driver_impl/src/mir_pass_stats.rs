@@ -0,0 +1,126 @@
+//! `-Zmir-pass-stats`, which reports the changes made by each MIR pass to each body, and the time
+//! it took, to find out which pass is responsible for a change of the MIR.
+
+use crate::session_diagnostics::MirPassStatsFailed;
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_session::code_stats::{MirBodySize, MirPassInfo};
+use rustc_session::Session;
+use rustc_span::Symbol;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The changes made by all the runs of a pass.
+#[derive(Default)]
+struct PassTotal {
+    runs: usize,
+    duration: Duration,
+    /// The number of basic blocks added by the pass, negative when it removed more than it added.
+    basic_blocks: i64,
+    statements_added: usize,
+    statements_removed: usize,
+    /// The number of locals added by the pass, negative when it removed more than it added.
+    locals: i64,
+}
+
+/// The change of a count, negative when it decreased.
+fn change(before: usize, after: usize) -> i64 {
+    after as i64 - before as i64
+}
+
+fn size_json(size: &MirBodySize) -> serde_json::Value {
+    serde_json::json!({
+        "basic_blocks": size.basic_blocks,
+        "statements": size.statements,
+        "locals": size.locals,
+    })
+}
+
+/// Writes the runs of MIR passes recorded during the compilation to
+/// `<directory>/<crate_name>.mir_pass_stats.json`.
+pub(crate) fn write(sess: &Session, crate_name: Symbol, directory: &Option<PathBuf>) {
+    let directory = match directory {
+        Some(directory) => directory.clone(),
+        None => PathBuf::from(&sess.opts.unstable_opts.dump_mir_dir),
+    };
+    let path = directory.join(format!("{crate_name}.mir_pass_stats.json"));
+    if let Err(error) = write_to(sess, crate_name, &path) {
+        sess.dcx().emit_err(MirPassStatsFailed {
+            path: path.display().to_string(),
+            error: error.to_string(),
+        });
+    }
+}
+
+fn write_to(
+    sess: &Session,
+    crate_name: Symbol,
+    path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let runs = sess.code_stats.take_mir_passes();
+
+    let mut passes: FxIndexMap<&str, PassTotal> = FxIndexMap::default();
+    let mut bodies: FxIndexMap<&str, Vec<&MirPassInfo>> = FxIndexMap::default();
+    for run in &runs {
+        let total = passes.entry(run.pass_name).or_default();
+        total.runs += 1;
+        total.duration += run.duration;
+        total.basic_blocks += change(run.before.basic_blocks, run.after.basic_blocks);
+        total.statements_added += run.statements_added;
+        total.statements_removed += run.statements_removed;
+        total.locals += change(run.before.locals, run.after.locals);
+        // The runs on a body are recorded in the order they happened.
+        bodies.entry(run.body.as_str()).or_default().push(run);
+    }
+    // The slowest passes first, and the bodies by name.
+    passes.sort_by(|a_name, a, b_name, b| {
+        b.duration.cmp(&a.duration).then_with(|| a_name.cmp(b_name))
+    });
+    bodies.sort_keys();
+
+    let stats = serde_json::json!({
+        "crate": crate_name.as_str(),
+        "passes": passes
+            .iter()
+            .map(|(name, total)| {
+                serde_json::json!({
+                    "name": name,
+                    "runs": total.runs,
+                    "time_ns": total.duration.as_nanos() as u64,
+                    "basic_blocks": total.basic_blocks,
+                    "statements_added": total.statements_added,
+                    "statements_removed": total.statements_removed,
+                    "locals": total.locals,
+                })
+            })
+            .collect::<Vec<_>>(),
+        "bodies": bodies
+            .iter()
+            .map(|(body, runs)| {
+                let runs = runs
+                    .iter()
+                    .map(|run| {
+                        serde_json::json!({
+                            "pass": run.pass_name,
+                            "time_ns": run.duration.as_nanos() as u64,
+                            "before": size_json(&run.before),
+                            "after": size_json(&run.after),
+                            "statements_added": run.statements_added,
+                            "statements_removed": run.statements_removed,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                serde_json::json!({ "body": body, "runs": runs })
+            })
+            .collect::<Vec<_>>(),
+    });
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = BufWriter::new(File::create(path)?);
+    serde_json::to_writer(&mut file, &stats)?;
+    file.flush()?;
+    Ok(())
+}
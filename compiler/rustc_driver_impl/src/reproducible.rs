@@ -0,0 +1,183 @@
+//! `-Zverify-reproducible`, which compiles the crate a second time in the same process after
+//! the requested compilation, and reports the output files that differ between the two.
+//!
+//! The second compilation writes its outputs to a temporary directory, and is perturbed in ways
+//! which mustn't change the outputs:
+//! - it doesn't use the incremental compilation cache;
+//! - it codegens the codegen units in the opposite order;
+//! - its unordered collections (`UnordMap` and `UnordSet`) iterate in another order, as their
+//!   hashers are seeded differently;
+//! - its working directory is given another local path, mapped to the same path as the working
+//!   directory of the first compilation with an additional `--remap-path-prefix`. The working
+//!   directory can only be changed this way if it is remapped to begin with, as otherwise its
+//!   local path is part of the outputs: only the additional path mapping is then added.
+
+use crate::session_diagnostics::{
+    NotReproducible, NotReproducibleNote, VerifyReproducibleFailed, VerifyReproducibleStdin,
+};
+use crate::{catch_with_exit_code, run_compiler, Callbacks, EXIT_SUCCESS};
+use rustc_codegen_ssa::back::compare::artifact_differences;
+use rustc_codegen_ssa::traits::CodegenBackend;
+use rustc_data_structures::unord;
+use rustc_interface::interface;
+use rustc_metadata::locator;
+use rustc_session::config::{Input, OutFileName, OutputTypes};
+use rustc_session::lint::Level;
+use rustc_session::Session;
+use rustc_span::RealFileName;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+/// The seed of the hashers of the unordered collections of the second compilation. Any other
+/// value than zero, the seed of the first compilation, changes their iteration order.
+const PERTURBED_ITERATION_ORDER_SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// The callbacks of the second compilation, which perturb its configuration.
+struct PerturbedBuild {
+    out_dir: PathBuf,
+}
+
+impl Callbacks for PerturbedBuild {
+    // JUSTIFICATION: the session doesn't exist at this point.
+    #[allow(rustc::bad_opt_access)]
+    fn config(&mut self, config: &mut interface::Config) {
+        match &mut config.output_file {
+            Some(OutFileName::Real(path)) => *path = self.out_dir.join(path.file_name().unwrap()),
+            Some(OutFileName::Stdout) => {}
+            None => config.output_dir = Some(self.out_dir.clone()),
+        }
+
+        let opts = &mut config.opts;
+        let output_types: Vec<_> = opts
+            .output_types
+            .iter()
+            .map(|(&output_type, path)| match path {
+                Some(OutFileName::Real(path)) => {
+                    let path = self.out_dir.join(path.file_name().unwrap());
+                    (output_type, Some(OutFileName::Real(path)))
+                }
+                path => (output_type, path.clone()),
+            })
+            .collect();
+        opts.output_types = OutputTypes::new(&output_types);
+        opts.unstable_opts.verify_reproducible = false;
+        opts.perturbed_build = true;
+        opts.incremental = None;
+        let perturbed_working_dir = self.out_dir.join("working-dir");
+        match &opts.working_dir {
+            RealFileName::LocalPath(path) => {
+                opts.remap_path_prefix.push((perturbed_working_dir, path.clone()));
+            }
+            RealFileName::Remapped { virtual_name, .. } => {
+                let virtual_name = virtual_name.clone();
+                opts.remap_path_prefix.push((perturbed_working_dir.clone(), virtual_name.clone()));
+                opts.working_dir = RealFileName::Remapped {
+                    local_path: Some(perturbed_working_dir),
+                    virtual_name,
+                };
+            }
+        }
+        // Everything the first compilation printed would be printed again.
+        opts.prints.clear();
+        opts.json_artifact_notifications = false;
+        opts.lint_cap = Some(Level::Allow);
+    }
+}
+
+/// Compiles the crate a second time, and reports the outputs which differ from the ones of the
+/// compilation of `sess`.
+pub(crate) fn verify(
+    sess: &Session,
+    codegen_backend: &dyn CodegenBackend,
+    at_args: &[String],
+    using_internal_features: Arc<AtomicBool>,
+) -> interface::Result<()> {
+    if let Input::Str { .. } = sess.io.input {
+        return Err(sess.dcx().emit_err(VerifyReproducibleStdin));
+    }
+
+    // The directory is removed when `out_dir` is dropped.
+    let out_dir = tempfile::Builder::new().prefix("rustc-verify-reproducible").tempdir();
+    let result = out_dir.map_err(|err| err.to_string()).and_then(|out_dir| {
+        let at_args: Vec<_> =
+            std::iter::once("rustc".to_owned()).chain(at_args.iter().cloned()).collect();
+        let mut callbacks = PerturbedBuild { out_dir: out_dir.path().to_owned() };
+        unord::perturb_iteration_order(PERTURBED_ITERATION_ORDER_SEED);
+        let exit_code = catch_with_exit_code(|| {
+            run_compiler(&at_args, &mut callbacks, None, None, using_internal_features)
+        });
+        unord::perturb_iteration_order(0);
+        if exit_code != EXIT_SUCCESS {
+            return Err("it reported errors".to_owned());
+        }
+        compare_outputs(sess, codegen_backend, out_dir.path()).map_err(|err| err.to_string())
+    });
+
+    match result {
+        Err(error) => Err(sess.dcx().emit_err(VerifyReproducibleFailed { error })),
+        Ok(()) => match sess.dcx().has_errors() {
+            Some(guar) => Err(guar),
+            None => Ok(()),
+        },
+    }
+}
+
+/// Compares the outputs of the second compilation, in `out_dir`, to the ones of the first.
+fn compare_outputs(
+    sess: &Session,
+    codegen_backend: &dyn CodegenBackend,
+    out_dir: &Path,
+) -> std::io::Result<()> {
+    let first_out_dir = match &sess.io.output_file {
+        Some(OutFileName::Real(path)) => path.parent().unwrap_or(Path::new("")).to_owned(),
+        _ => sess.io.output_dir.clone().unwrap_or_default(),
+    };
+    let mut names: Vec<_> = fs::read_dir(out_dir)?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            entry.file_type().ok()?.is_file().then(|| entry.file_name())
+        })
+        .collect();
+    names.sort();
+
+    for name in names {
+        // The outputs given an explicit path with `--emit` are written in the same directory as
+        // the other outputs by the second compilation.
+        let explicit_path = sess.opts.output_types.values().find_map(|path| match path {
+            Some(OutFileName::Real(path)) if path.file_name() == Some(&name) => Some(path),
+            _ => None,
+        });
+        let first = explicit_path.cloned().unwrap_or_else(|| first_out_dir.join(&name));
+        let second = out_dir.join(&name);
+        let second_bytes = fs::read(&second)?;
+        let note = match fs::read(&first) {
+            Ok(first_bytes) if first_bytes == second_bytes => continue,
+            Ok(first_bytes) => {
+                let mut differences = artifact_differences(&first_bytes, &second_bytes);
+                let name = name.to_string_lossy();
+                let is_crate_file = name.ends_with(".rlib")
+                    || name.ends_with(".rmeta")
+                    || name.ends_with(&*sess.target.dll_suffix);
+                if is_crate_file
+                    && let Ok(metadata_differences) = locator::metadata_differences(
+                        &sess.target,
+                        &first,
+                        &second,
+                        &*codegen_backend.metadata_loader(),
+                        sess.cfg_version,
+                    )
+                {
+                    differences.extend(metadata_differences);
+                }
+                (!differences.is_empty()).then(|| NotReproducibleNote::Differences {
+                    differences: differences.join(", "),
+                })
+            }
+            Err(_) => Some(NotReproducibleNote::Missing),
+        };
+        sess.dcx().emit_err(NotReproducible { path: first.display().to_string(), note });
+    }
+    Ok(())
+}
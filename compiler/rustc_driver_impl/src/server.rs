@@ -0,0 +1,241 @@
+//! `rustc --server=SOCKET -Z unstable-options`, which keeps running and compiles the crates it is
+//! asked to over a Unix domain socket, so that build systems invoking the compiler many times
+//! don't pay for starting it and reading the metadata of the dependencies each time.
+//!
+//! Each connection carries a single request: a line with a JSON object of the form
+//!
+//! ```json
+//! {"args": ["--crate-type=lib", "src/lib.rs"], "cwd": "/path/to/crate", "env": {"KEY": "VALUE"}}
+//! ```
+//!
+//! where `args` are the arguments of a `rustc` invocation (without the name of the binary), and
+//! `cwd` and `env` are optional. The working directory and the environment of a process can't be
+//! changed soundly while it runs, so the compiler runs in those of the server: a request with a
+//! `cwd` or `env` which doesn't match them is rejected, and clients start a server per working
+//! directory and environment. The server answers with a line with a JSON object of the form
+//!
+//! ```json
+//! {"exit_code": 0, "stdout": "...", "stderr": "..."}
+//! ```
+//!
+//! holding what the invocation would have exited with and printed, including its diagnostics.
+//! A request of the form `{"shutdown": true}` stops the server. A client which doesn't send its
+//! request within [`REQUEST_TIMEOUT`] is disconnected, so that it can't hold up the others.
+//!
+//! Requests are handled one at a time, each in a new session. The raw metadata of the crates
+//! loaded by a session is kept mapped for the following ones, see
+//! [`rustc_metadata::locator::enable_metadata_blob_cache`]. The crate store itself (`CStore`),
+//! with the metadata decoded, is not kept: each session loads its crates and decodes their
+//! metadata again, as the decoded metadata refers to the session's crate numbers and interned
+//! symbols.
+
+use crate::Callbacks;
+#[cfg(unix)]
+use crate::{catch_with_exit_code, run_compiler};
+use rustc_interface::interface;
+use rustc_session::EarlyDiagCtxt;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+#[cfg(unix)]
+use std::time::Duration;
+
+/// How long the server waits for the request of a client which has connected.
+#[cfg(unix)]
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[cfg(unix)]
+pub(crate) fn run(
+    early_dcx: &EarlyDiagCtxt,
+    socket: &Path,
+    callbacks: &mut (dyn Callbacks + Send),
+    using_internal_features: Arc<AtomicBool>,
+) -> interface::Result<()> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixListener;
+
+    let listener = UnixListener::bind(socket).unwrap_or_else(|e| {
+        early_dcx.early_fatal(format!("failed to listen on `{}`: {e}", socket.display()))
+    });
+    rustc_metadata::locator::enable_metadata_blob_cache();
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                early_dcx.early_warn(format!("failed to accept a connection: {e}"));
+                continue;
+            }
+        };
+        let mut line = String::new();
+        if let Err(e) = stream
+            .set_read_timeout(Some(REQUEST_TIMEOUT))
+            .and_then(|()| BufReader::new(&stream).read_line(&mut line))
+        {
+            early_dcx.early_warn(format!("failed to read a request: {e}"));
+            continue;
+        }
+
+        let (response, shutdown) = match serde_json::from_str(&line) {
+            Ok(serde_json::Value::Object(request))
+                if request.get("shutdown") == Some(&serde_json::Value::Bool(true)) =>
+            {
+                (serde_json::json!({ "exit_code": 0, "stdout": "", "stderr": "" }), true)
+            }
+            Ok(serde_json::Value::Object(request)) => {
+                (handle_request(&request, callbacks, &using_internal_features), false)
+            }
+            _ => (serde_json::json!({ "error": "expected a JSON object" }), false),
+        };
+        if let Err(e) = writeln!(&stream, "{response}") {
+            early_dcx.early_warn(format!("failed to send a response: {e}"));
+        }
+        if shutdown {
+            break;
+        }
+    }
+
+    let _ = std::fs::remove_file(socket);
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn run(
+    early_dcx: &EarlyDiagCtxt,
+    _socket: &Path,
+    _callbacks: &mut (dyn Callbacks + Send),
+    _using_internal_features: Arc<AtomicBool>,
+) -> interface::Result<()> {
+    early_dcx.early_fatal("`--server` is only supported on Unix platforms")
+}
+
+/// Runs the compiler for a request, and returns the response to send back.
+#[cfg(unix)]
+fn handle_request(
+    request: &serde_json::Map<String, serde_json::Value>,
+    callbacks: &mut (dyn Callbacks + Send),
+    using_internal_features: &Arc<AtomicBool>,
+) -> serde_json::Value {
+    use std::panic::{self, AssertUnwindSafe};
+    use std::{env, fs};
+
+    let strings = |value: Option<&serde_json::Value>| -> Option<Vec<(String, String)>> {
+        match value {
+            None => Some(Vec::new()),
+            Some(serde_json::Value::Object(map)) => map
+                .iter()
+                .map(|(key, value)| Some((key.clone(), value.as_str()?.to_owned())))
+                .collect(),
+            Some(_) => None,
+        }
+    };
+    let args: Option<Vec<String>> = request.get("args").and_then(|args| {
+        args.as_array()?.iter().map(|arg| Some(arg.as_str()?.to_owned())).collect()
+    });
+    let Some(args) = args else {
+        return serde_json::json!({ "error": "`args` must be an array of strings" });
+    };
+    let Some(vars) = strings(request.get("env")) else {
+        return serde_json::json!({ "error": "`env` must be an object with string values" });
+    };
+    let cwd = match request.get("cwd") {
+        None => None,
+        Some(serde_json::Value::String(cwd)) => Some(cwd),
+        Some(_) => return serde_json::json!({ "error": "`cwd` must be a string" }),
+    };
+
+    if let Some(cwd) = cwd {
+        let server_cwd = env::current_dir().and_then(fs::canonicalize);
+        match (fs::canonicalize(cwd), server_cwd) {
+            (Ok(cwd), Ok(server_cwd)) if cwd == server_cwd => {}
+            _ => {
+                let error =
+                    format!("`cwd` must be the working directory of the server, not `{cwd}`");
+                return serde_json::json!({ "error": error });
+            }
+        }
+    }
+    if let Some((key, _)) =
+        vars.iter().find(|(key, value)| env::var(key).ok().as_ref() != Some(value))
+    {
+        let error =
+            format!("`env` must match the environment of the server, which differs on `{key}`");
+        return serde_json::json!({ "error": error });
+    }
+
+    let at_args: Vec<String> = std::iter::once("rustc".to_owned()).chain(args).collect();
+    let captured = capture_output(|| {
+        // An ICE shouldn't bring down the server: the panic hook has already reported it.
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            catch_with_exit_code(|| {
+                run_compiler(&at_args, callbacks, None, None, Arc::clone(using_internal_features))
+            })
+        }))
+        .unwrap_or(101)
+    });
+
+    match captured {
+        Ok((exit_code, stdout, stderr)) => {
+            serde_json::json!({ "exit_code": exit_code, "stdout": stdout, "stderr": stderr })
+        }
+        Err(e) => serde_json::json!({ "error": format!("failed to capture the output: {e}") }),
+    }
+}
+
+/// Runs `f` with the standard output and error of the process redirected to temporary files, and
+/// returns what was written to them.
+#[cfg(unix)]
+fn capture_output<R>(f: impl FnOnce() -> R) -> std::io::Result<(R, String, String)> {
+    use std::fs::{self, File, OpenOptions};
+    use std::io::{self, Read, Seek, Write};
+    use std::os::fd::AsRawFd;
+
+    fn temp_file(name: &str) -> io::Result<File> {
+        let path = std::env::temp_dir().join(format!("rustc-server-{}-{name}", std::process::id()));
+        let file =
+            OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&path)?;
+        // The file stays usable through its descriptor.
+        fs::remove_file(&path)?;
+        Ok(file)
+    }
+
+    fn redirect(fd: i32, to: &File) -> io::Result<i32> {
+        let saved = unsafe { libc::dup(fd) };
+        if saved == -1 || unsafe { libc::dup2(to.as_raw_fd(), fd) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(saved)
+    }
+
+    fn restore(fd: i32, saved: i32) {
+        unsafe {
+            libc::dup2(saved, fd);
+            libc::close(saved);
+        }
+    }
+
+    fn read_back(mut file: File) -> io::Result<String> {
+        let mut output = String::new();
+        file.rewind()?;
+        file.read_to_string(&mut output)?;
+        Ok(output)
+    }
+
+    let (stdout, stderr) = (temp_file("stdout")?, temp_file("stderr")?);
+    let _ = io::stdout().flush();
+    let saved_stdout = redirect(libc::STDOUT_FILENO, &stdout)?;
+    let saved_stderr = match redirect(libc::STDERR_FILENO, &stderr) {
+        Ok(saved) => saved,
+        Err(e) => {
+            restore(libc::STDOUT_FILENO, saved_stdout);
+            return Err(e);
+        }
+    };
+
+    let result = f();
+
+    let _ = io::stdout().flush();
+    restore(libc::STDOUT_FILENO, saved_stdout);
+    restore(libc::STDERR_FILENO, saved_stderr);
+    Ok((result, read_back(stdout)?, read_back(stderr)?))
+}
@@ -87,3 +87,43 @@ pub(crate) struct IceFlags {
 #[derive(Diagnostic)]
 #[diag(driver_impl_ice_exclude_cargo_defaults)]
 pub(crate) struct IceExcludeCargoDefaults;
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_dump_stable_mir_failed)]
+pub(crate) struct DumpStableMirFailed {
+    pub path: String,
+    pub error: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_mir_pass_stats_failed)]
+pub(crate) struct MirPassStatsFailed {
+    pub path: String,
+    pub error: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_not_reproducible)]
+pub(crate) struct NotReproducible {
+    pub path: String,
+    #[subdiagnostic]
+    pub note: Option<NotReproducibleNote>,
+}
+
+#[derive(Subdiagnostic)]
+pub(crate) enum NotReproducibleNote {
+    #[note(driver_impl_not_reproducible_differences)]
+    Differences { differences: String },
+    #[note(driver_impl_not_reproducible_missing)]
+    Missing,
+}
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_verify_reproducible_failed)]
+pub(crate) struct VerifyReproducibleFailed {
+    pub error: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_verify_reproducible_stdin)]
+pub(crate) struct VerifyReproducibleStdin;
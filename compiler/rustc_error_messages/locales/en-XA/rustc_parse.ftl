@@ -0,0 +1,15 @@
+# Pseudo-localized messages of `rustc_parse`, used to test the translation of diagnostics.
+
+parse_expected_identifier = ȩxþȩçţȩđ ĩđȩñţĩƒĩȩȓ
+
+parse_missing_fn_params = ɱĩšşĩñğ þàȓàɱȩţȩȓš ƒöȓ ƒũñçţĩöñ đȩƒĩñĩţĩöñ
+    .suggestion = àđđ à þàȓàɱȩţȩȓ ĺĩšţ
+
+parse_missing_struct_for_struct_definition = ɱĩšşĩñğ `struct` ƒöȓ šţȓũçţ đȩƒĩñĩţĩöñ
+    .suggestion = àđđ `struct` ĥȩȓȩ ţö þàȓšȩ `{$ident}` àš à þũƀĺĩç šţȓũçţ
+
+parse_struct_literal_body_without_path =
+    šţȓũçţ ĺĩţȩȓàĺ ƀöđŷ ŵĩţĥöũţ þàţĥ
+    .suggestion = ŷöũ ɱĩğĥţ ĥàṽȩ ƒöȓğöţţȩñ ţö àđđ ţĥȩ šţȓũçţ ĺĩţȩȓàĺ ĩñšĩđȩ ţĥȩ ƀĺöçķ
+
+parse_unexpected_token_after_dot = ũñȩxþȩçţȩđ ţöķȩñ: `{$actual}`
@@ -9,8 +9,10 @@
 extern crate tracing;
 
 use fluent_bundle::FluentResource;
+use fluent_syntax::ast::{Entry, Expression, InlineExpression, Pattern, PatternElement};
 use fluent_syntax::parser::ParserError;
 use icu_provider_adapters::fallback::{LocaleFallbackProvider, LocaleFallbacker};
+use rustc_data_structures::fx::FxIndexSet;
 use rustc_data_structures::sync::{IntoDynSyncSend, Lrc};
 use rustc_macros::{Decodable, Encodable};
 use rustc_span::Span;
@@ -55,6 +57,8 @@ pub enum TranslationBundleError {
     ParseFtl(ParserError),
     /// Failed to add `FluentResource` to `FluentBundle`.
     AddResource(FluentError),
+    /// A message of the `.ftl` file is already translated by another file of the locale.
+    ConflictingMessage(String),
     /// `$sysroot/share/locale/$locale` does not exist.
     MissingLocale,
     /// Cannot read directory entries of `$sysroot/share/locale/$locale`.
@@ -73,6 +77,9 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 write!(f, "could not parse ftl file: {e}")
             }
             TranslationBundleError::AddResource(e) => write!(f, "failed to add resource: {e}"),
+            TranslationBundleError::ConflictingMessage(id) => {
+                write!(f, "message `{id}` is already translated by another ftl file")
+            }
             TranslationBundleError::MissingLocale => write!(f, "missing locale directory"),
             TranslationBundleError::ReadLocalesDir(e) => {
                 write!(f, "could not read locales dir: {e}")
@@ -93,6 +100,7 @@ fn source(&self) -> Option<&(dyn Error + 'static)> {
             TranslationBundleError::ReadFtl(e) => Some(e),
             TranslationBundleError::ParseFtl(e) => Some(e),
             TranslationBundleError::AddResource(e) => Some(e),
+            TranslationBundleError::ConflictingMessage(_) => None,
             TranslationBundleError::MissingLocale => None,
             TranslationBundleError::ReadLocalesDir(e) => Some(e),
             TranslationBundleError::ReadLocalesDirEntry(e) => Some(e),
@@ -115,28 +123,55 @@ fn from(mut errs: Vec<FluentError>) -> Self {
     }
 }
 
+/// Locales shipped with the compiler, used when no sysroot provides them: the locale and the
+/// resources of each translated crate.
+///
+/// `en-XA` is a pseudo-locale, English with accented letters, which is used to test translations
+/// without depending on a real translation being installed.
+const BUILTIN_LOCALES: &[(&str, &[&str])] =
+    &[("en-XA", &[include_str!("../locales/en-XA/rustc_parse.ftl")])];
+
+/// A resource of the requested locale which couldn't be loaded. The messages of the crate it
+/// translates are taken from the parent locales and the fallback bundle instead.
+#[derive(Debug)]
+pub struct SkippedResource {
+    pub path: PathBuf,
+    pub error: TranslationBundleError,
+}
+
 /// Returns Fluent bundle with the user's locale resources from
-/// `$sysroot/share/locale/$requested_locale/*.ftl`.
+/// `$sysroot/share/locale/$requested_locale/*.ftl`, or from the locales shipped with the
+/// compiler if no sysroot has the requested locale.
+///
+/// Each resource translates the messages of one crate, e.g. `rustc_parse.ftl`. A resource which
+/// can't be read or parsed, or which conflicts with the messages of another resource of the same
+/// locale, is skipped and returned alongside the bundle, so that only the messages of its crate
+/// fall back.
+///
+/// Messages missing from the requested locale are taken from its parent locales, if they exist in
+/// the sysroot (e.g. `$sysroot/share/locale/de/*.ftl` for `de-AT`), and from the fallback bundle
+/// otherwise.
 ///
 /// If `-Z additional-ftl-path` was provided, load that resource and add it  to the bundle
 /// (overriding any conflicting messages).
 #[instrument(level = "trace")]
 pub fn fluent_bundle(
-    mut user_provided_sysroot: Option<PathBuf>,
-    mut sysroot_candidates: Vec<PathBuf>,
+    user_provided_sysroot: Option<PathBuf>,
+    sysroot_candidates: Vec<PathBuf>,
     requested_locale: Option<LanguageIdentifier>,
     additional_ftl_path: Option<&Path>,
     with_directionality_markers: bool,
-) -> Result<Option<Lrc<FluentBundle>>, TranslationBundleError> {
+) -> Result<(Option<Lrc<FluentBundle>>, Vec<SkippedResource>), TranslationBundleError> {
+    let mut skipped = Vec::new();
     if requested_locale.is_none() && additional_ftl_path.is_none() {
-        return Ok(None);
+        return Ok((None, skipped));
     }
 
     let fallback_locale = langid!("en-US");
     let requested_fallback_locale = requested_locale.as_ref() == Some(&fallback_locale);
     trace!(?requested_fallback_locale);
     if requested_fallback_locale && additional_ftl_path.is_none() {
-        return Ok(None);
+        return Ok((None, skipped));
     }
     // If there is only `-Z additional-ftl-path`, assume locale is "en-US", otherwise use user
     // provided locale.
@@ -157,37 +192,64 @@ pub fn fluent_bundle(
     // If the user requests the default locale then don't try to load anything.
     if let Some(requested_locale) = requested_locale {
         let mut found_resources = false;
-        for sysroot in user_provided_sysroot.iter_mut().chain(sysroot_candidates.iter_mut()) {
-            sysroot.push("share");
-            sysroot.push("locale");
-            sysroot.push(requested_locale.to_string());
-            trace!(?sysroot);
-
-            if !sysroot.exists() {
-                trace!("skipping");
-                continue;
-            }
-
-            if !sysroot.is_dir() {
-                return Err(TranslationBundleError::LocaleIsNotDir);
-            }
-
-            for entry in sysroot.read_dir().map_err(TranslationBundleError::ReadLocalesDir)? {
-                let entry = entry.map_err(TranslationBundleError::ReadLocalesDirEntry)?;
-                let path = entry.path();
-                trace!(?path);
-                if path.extension().and_then(|s| s.to_str()) != Some("ftl") {
+        for (i, locale) in locale_fallback_chain(&requested_locale).iter().enumerate() {
+            let is_parent_locale = i > 0;
+            let mut found_locale = false;
+            for sysroot in user_provided_sysroot.iter().chain(sysroot_candidates.iter()) {
+                let dir = sysroot.join("share").join("locale").join(locale.to_string());
+                trace!(?dir);
+
+                if !dir.exists() {
                     trace!("skipping");
                     continue;
                 }
 
-                let resource_str =
-                    fs::read_to_string(path).map_err(TranslationBundleError::ReadFtl)?;
-                let resource =
-                    FluentResource::try_new(resource_str).map_err(TranslationBundleError::from)?;
-                trace!(?resource);
-                bundle.add_resource(resource).map_err(TranslationBundleError::from)?;
-                found_resources = true;
+                if !dir.is_dir() {
+                    return Err(TranslationBundleError::LocaleIsNotDir);
+                }
+
+                for entry in dir.read_dir().map_err(TranslationBundleError::ReadLocalesDir)? {
+                    let entry = entry.map_err(TranslationBundleError::ReadLocalesDirEntry)?;
+                    let path = entry.path();
+                    trace!(?path);
+                    if path.extension().and_then(|s| s.to_str()) != Some("ftl") {
+                        trace!("skipping");
+                        continue;
+                    }
+
+                    found_resources = true;
+                    found_locale = true;
+                    let resource = fs::read_to_string(&path)
+                        .map_err(TranslationBundleError::ReadFtl)
+                        .and_then(|s| {
+                            FluentResource::try_new(s).map_err(TranslationBundleError::from)
+                        });
+                    let error = match resource {
+                        Ok(resource) => {
+                            add_locale_resource(&mut bundle, resource, is_parent_locale)
+                        }
+                        Err(error) => Err(error),
+                    };
+                    if let Err(error) = error {
+                        debug!(?path, ?error, "skipping resource");
+                        skipped.push(SkippedResource { path, error });
+                    }
+                }
+            }
+
+            if found_locale {
+                continue;
+            }
+            let builtin = BUILTIN_LOCALES.iter().find(|(name, _)| *name == locale.to_string());
+            if let Some((_, resources)) = builtin {
+                trace!(?locale, "using built-in locale");
+                for resource in *resources {
+                    let resource = FluentResource::try_new(resource.to_string())
+                        .expect("failed to parse built-in fluent resource");
+                    add_locale_resource(&mut bundle, resource, is_parent_locale)
+                        .expect("failed to add built-in fluent resource");
+                    found_resources = true;
+                }
             }
         }
 
@@ -206,7 +268,187 @@ pub fn fluent_bundle(
     }
 
     let bundle = Lrc::new(bundle);
-    Ok(Some(bundle))
+    Ok((Some(bundle), skipped))
+}
+
+/// Adds the resource of a crate to the bundle of the requested locale, unless it conflicts with
+/// the resource of another crate.
+fn add_locale_resource(
+    bundle: &mut FluentBundle,
+    resource: FluentResource,
+    is_parent_locale: bool,
+) -> Result<(), TranslationBundleError> {
+    trace!(?resource);
+    if is_parent_locale {
+        // The messages of a parent locale are only used for the messages that the more specific
+        // locales don't translate. The other messages are still added to the bundle when there
+        // are conflicts.
+        return match bundle.add_resource(resource) {
+            Err(errs) if !errs.iter().all(|e| matches!(e, FluentError::Overriding { .. })) => {
+                Err(errs.into())
+            }
+            _ => Ok(()),
+        };
+    }
+
+    // Check for conflicts before adding the resource, so that a conflicting crate is skipped as a
+    // whole instead of being partially translated.
+    let conflict = resource.entries().find_map(|entry| match entry {
+        Entry::Message(message) if bundle.has_message(message.id.name) => Some(message.id.name),
+        _ => None,
+    });
+    if let Some(id) = conflict {
+        return Err(TranslationBundleError::ConflictingMessage(id.to_string()));
+    }
+    bundle.add_resource(resource).map_err(TranslationBundleError::from)
+}
+
+/// Returns `locale` followed by its parent locales, from the most to the least specific, e.g.
+/// `sr-Latn-RS`, `sr-Latn` and `sr` for `sr-Latn-RS`.
+fn locale_fallback_chain(locale: &LanguageIdentifier) -> Vec<LanguageIdentifier> {
+    let mut chain = vec![locale.clone()];
+    let parents = [
+        LanguageIdentifier::from_parts(locale.language, locale.script, locale.region, &[]),
+        LanguageIdentifier::from_parts(locale.language, locale.script, None, &[]),
+        LanguageIdentifier::from_parts(locale.language, None, None, &[]),
+    ];
+    for parent in parents {
+        if !chain.contains(&parent) {
+            chain.push(parent);
+        }
+    }
+    chain
+}
+
+/// A difference between a translated message and the English message it translates, which would
+/// make the translation fail or be incomplete. Found by `validate_fluent_bundle`.
+#[derive(Debug)]
+pub enum TranslationMismatch {
+    /// The translation has an attribute that the English message doesn't have.
+    UnknownAttribute { id: String, attr: String },
+    /// The translation refers to a variable that the English message doesn't, and which the
+    /// diagnostic therefore doesn't provide.
+    UnknownVariable { id: String, attr: Option<String>, variable: String },
+}
+
+impl fmt::Display for TranslationMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranslationMismatch::UnknownAttribute { id, attr } => {
+                write!(f, "translation of `{id}` has an unknown attribute `.{attr}`")
+            }
+            TranslationMismatch::UnknownVariable { id, attr: None, variable } => {
+                write!(f, "translation of `{id}` refers to an unknown variable `${variable}`")
+            }
+            TranslationMismatch::UnknownVariable { id, attr: Some(attr), variable } => write!(
+                f,
+                "translation of `{id}.{attr}` refers to an unknown variable `${variable}`"
+            ),
+        }
+    }
+}
+
+/// Checks the translated messages of `bundle` against the English messages of
+/// `fallback_resources`, which are the resources of the fallback bundle.
+///
+/// All the attributes of a message are translated with the same arguments as the message itself,
+/// so a translation may refer to any variable that the English message or its attributes refer
+/// to.
+pub fn validate_fluent_bundle(
+    bundle: &FluentBundle,
+    fallback_resources: &[&'static str],
+) -> Vec<TranslationMismatch> {
+    let mut mismatches = Vec::new();
+    for resource in fallback_resources {
+        let resource = FluentResource::try_new(resource.to_string())
+            .expect("failed to parse fallback fluent resource");
+        for entry in resource.entries() {
+            let Entry::Message(english) = entry else { continue };
+            let id = english.id.name;
+            let Some(translated) = bundle.get_message(id) else { continue };
+
+            let mut variables = FxIndexSet::default();
+            if let Some(value) = &english.value {
+                pattern_variables(value, &mut variables);
+            }
+            for attr in &english.attributes {
+                pattern_variables(&attr.value, &mut variables);
+            }
+
+            let check = |attr: Option<&str>, pattern, mismatches: &mut Vec<_>| {
+                let mut translated_variables = FxIndexSet::default();
+                pattern_variables(pattern, &mut translated_variables);
+                for variable in translated_variables {
+                    if !variables.contains(variable) {
+                        mismatches.push(TranslationMismatch::UnknownVariable {
+                            id: id.to_string(),
+                            attr: attr.map(str::to_string),
+                            variable: variable.to_string(),
+                        });
+                    }
+                }
+            };
+            if let Some(value) = translated.value() {
+                check(None, value, &mut mismatches);
+            }
+            for attr in translated.attributes() {
+                if english.attributes.iter().any(|english| english.id.name == attr.id()) {
+                    check(Some(attr.id()), attr.value(), &mut mismatches);
+                } else {
+                    mismatches.push(TranslationMismatch::UnknownAttribute {
+                        id: id.to_string(),
+                        attr: attr.id().to_string(),
+                    });
+                }
+            }
+        }
+    }
+    mismatches
+}
+
+/// Collects the variables a pattern refers to.
+fn pattern_variables<'a>(pattern: &Pattern<&'a str>, variables: &mut FxIndexSet<&'a str>) {
+    for element in &pattern.elements {
+        if let PatternElement::Placeable { expression } = element {
+            expression_variables(expression, variables);
+        }
+    }
+}
+
+fn expression_variables<'a>(expression: &Expression<&'a str>, variables: &mut FxIndexSet<&'a str>) {
+    match expression {
+        Expression::Inline(inline) => inline_expression_variables(inline, variables),
+        Expression::Select { selector, variants } => {
+            inline_expression_variables(selector, variables);
+            for variant in variants {
+                pattern_variables(&variant.value, variables);
+            }
+        }
+    }
+}
+
+fn inline_expression_variables<'a>(
+    expression: &InlineExpression<&'a str>,
+    variables: &mut FxIndexSet<&'a str>,
+) {
+    match expression {
+        InlineExpression::VariableReference { id } => {
+            variables.insert(id.name);
+        }
+        InlineExpression::FunctionReference { arguments, .. } => {
+            for argument in &arguments.positional {
+                inline_expression_variables(argument, variables);
+            }
+            for argument in &arguments.named {
+                inline_expression_variables(&argument.value, variables);
+            }
+        }
+        InlineExpression::Placeable { expression } => expression_variables(expression, variables),
+        InlineExpression::StringLiteral { .. }
+        | InlineExpression::NumberLiteral { .. }
+        | InlineExpression::MessageReference { .. }
+        | InlineExpression::TermReference { .. } => {}
+    }
 }
 
 fn register_functions(bundle: &mut FluentBundle) {
@@ -0,0 +1,156 @@
+//! Support for `-Zdiagnostic-baseline`, which lets a crate adopt new lints gradually.
+//!
+//! With `-Zdiagnostic-baseline-record`, lint warnings are emitted as usual and recorded, and the
+//! baseline file is written, or replaced, once compilation finishes. Otherwise, the warnings the
+//! baseline file lists are suppressed, so that only warnings introduced since the baseline was
+//! recorded are reported.
+//!
+//! Warnings are keyed by lint name, the path of the item they were emitted in, and a fingerprint
+//! of their message and of the source code they point at. The key doesn't include the position of
+//! the warning, so that it stays the same when unrelated code is added or removed around it. The
+//! exception is warnings emitted within impls, whose paths name the impl by its location.
+
+use crate::emitter::DynEmitter;
+use crate::translation::to_fluent_args;
+use crate::DiagInner;
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::stable_hasher::StableHasher;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::hash::Hash;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+mod tests;
+
+/// The version of the baseline file format, bumped whenever the way keys are computed changes.
+const BASELINE_VERSION: u32 = 2;
+
+pub struct DiagnosticBaseline {
+    path: PathBuf,
+    mode: Mode,
+}
+
+enum Mode {
+    /// Record the warnings that are emitted, to write them to the baseline file.
+    Record(Vec<BaselineEntry>),
+    /// Suppress warnings matching an entry of the baseline file. The value is the number of
+    /// matching warnings that may still be suppressed, so that a new warning identical to a
+    /// recorded one is reported.
+    Suppress(FxHashMap<BaselineKey, usize>),
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+struct BaselineKey {
+    lint: String,
+    /// The path of the item the lint was emitted in, or an empty string for lints that aren't
+    /// emitted in a particular item (e.g. those emitted before macro expansion).
+    def_path: String,
+    fingerprint: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BaselineEntry {
+    #[serde(flatten)]
+    key: BaselineKey,
+    /// The message of the warning. This is not part of the key, and only makes the baseline
+    /// easier to review.
+    message: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BaselineFile {
+    version: u32,
+    warnings: Vec<BaselineEntry>,
+}
+
+impl DiagnosticBaseline {
+    /// Loads the baseline at `path`, to suppress the warnings it lists.
+    pub fn load(path: PathBuf) -> io::Result<DiagnosticBaseline> {
+        let contents = fs::read_to_string(&path)?;
+        let file: BaselineFile = serde_json::from_str(&contents)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if file.version != BASELINE_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "unsupported baseline version {} (expected {BASELINE_VERSION})",
+                    file.version
+                ),
+            ));
+        }
+        let mut counts = FxHashMap::default();
+        for entry in file.warnings {
+            *counts.entry(entry.key).or_default() += 1;
+        }
+        Ok(DiagnosticBaseline { path, mode: Mode::Suppress(counts) })
+    }
+
+    /// Prepares to record the warnings that are emitted, to write them to a baseline at `path`.
+    pub fn record(path: PathBuf) -> DiagnosticBaseline {
+        DiagnosticBaseline { path, mode: Mode::Record(Vec::new()) }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether warnings are being recorded, rather than suppressed.
+    pub fn is_recording(&self) -> bool {
+        matches!(self.mode, Mode::Record(_))
+    }
+
+    /// Called for every lint warning about to be emitted. Records it, or returns whether it is
+    /// part of the baseline and should be suppressed.
+    pub(crate) fn suppress(&mut self, diag: &DiagInner, emitter: &DynEmitter) -> bool {
+        let Some(is_lint) = &diag.is_lint else { return false };
+
+        // The messages are hashed untranslated, so that the baseline doesn't depend on the
+        // language diagnostics are emitted in.
+        let mut hasher = StableHasher::new();
+        diag.messages.hash(&mut hasher);
+        let mut args: Vec<_> = diag.args.iter().collect();
+        args.sort_by(|(a, _), (b, _)| a.cmp(b));
+        args.hash(&mut hasher);
+        if let Some(span) = diag.span.primary_span()
+            && let Some(sm) = emitter.source_map()
+            && let Ok(snippet) = sm.span_to_snippet(span)
+        {
+            snippet.hash(&mut hasher);
+        }
+        let fingerprint: Fingerprint = hasher.finish();
+
+        let key = BaselineKey {
+            lint: is_lint.name.clone(),
+            def_path: is_lint.def_path.clone().unwrap_or_default(),
+            fingerprint: fingerprint.to_hex(),
+        };
+        match &mut self.mode {
+            Mode::Record(entries) => {
+                let args = to_fluent_args(diag.args.iter());
+                let message = emitter.translate_messages(&diag.messages, &args).into_owned();
+                entries.push(BaselineEntry { key, message });
+                false
+            }
+            Mode::Suppress(counts) => match counts.get_mut(&key) {
+                Some(count @ 1..) => {
+                    *count -= 1;
+                    true
+                }
+                _ => false,
+            },
+        }
+    }
+
+    /// Writes out the recorded warnings. Does nothing if the warnings weren't recorded.
+    pub fn write(self) -> io::Result<()> {
+        let Mode::Record(mut warnings) = self.mode else { return Ok(()) };
+        warnings.sort_by(|a, b| a.key.cmp(&b.key));
+        let file = BaselineFile { version: BASELINE_VERSION, warnings };
+        let mut contents = serde_json::to_string_pretty(&file)?;
+        contents.push('\n');
+        fs::write(&self.path, contents)
+    }
+}
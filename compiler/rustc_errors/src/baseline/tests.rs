@@ -0,0 +1,110 @@
+use super::*;
+
+use crate::emitter::{ColorConfig, HumanReadableErrorType};
+use crate::json::JsonEmitter;
+use crate::test_helpers::{with_source_map, Shared};
+use crate::DiagCtxt;
+use rustc_span::{BytePos, Span};
+
+use std::sync::{Arc, Mutex};
+
+/// Compiles `code` with the baseline at `path`, recording it if `record` is set, emitting an
+/// `unused_variables` warning for each of the given spans, and returns the spans of the warnings
+/// that were reported.
+fn run(path: &Path, record: bool, code: &str, spans: &[(u32, u32)]) -> Vec<(u32, u32)> {
+    with_source_map(|sm, fallback_bundle| {
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let je = JsonEmitter::new(
+            Box::new(Shared { data: output.clone() }),
+            sm,
+            fallback_bundle,
+            false,
+            HumanReadableErrorType::Short(ColorConfig::Never),
+        );
+        let baseline = if record {
+            DiagnosticBaseline::record(path.to_owned())
+        } else {
+            DiagnosticBaseline::load(path.to_owned()).unwrap()
+        };
+        let dcx = DiagCtxt::new(Box::new(je)).with_diagnostic_baseline(baseline);
+        for &(lo, hi) in spans {
+            let span = Span::with_root_ctxt(BytePos(lo), BytePos(hi));
+            let mut diag = dcx.struct_span_warn(span, "unused variable");
+            diag.is_lint("unused_variables".to_owned(), false);
+            diag.lint_def_path("test::main".to_owned());
+            diag.emit();
+        }
+        dcx.write_diagnostic_baseline().unwrap();
+        drop(dcx);
+
+        let bytes = output.lock().unwrap();
+        std::str::from_utf8(&bytes)
+            .unwrap()
+            .lines()
+            .map(|line| {
+                let diag: serde_json::Value = serde_json::from_str(line).unwrap();
+                let span = &diag["spans"][0];
+                (
+                    span["byte_start"].as_u64().unwrap() as u32,
+                    span["byte_end"].as_u64().unwrap() as u32,
+                )
+            })
+            .collect()
+    })
+}
+
+#[test]
+fn record_then_suppress() {
+    let path =
+        std::env::temp_dir().join(format!("rustc-diagnostic-baseline-{}.json", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    // Recording reports every warning.
+    let code = "let a = 1;\nlet b = 2;\n";
+    assert_eq!(run(&path, true, code, &[(4, 5), (15, 16)]), [(4, 5), (15, 16)]);
+    let recorded = fs::read_to_string(&path).unwrap();
+    assert!(recorded.contains("\"lint\": \"unused_variables\""));
+    assert!(recorded.contains("\"def_path\": \"test::main\""));
+
+    // The recorded warnings are suppressed, even after moving code around, but a new one is
+    // still reported.
+    let code = "\n\nlet a = 1;\nlet b = 2;\nlet c = 3;\n";
+    assert_eq!(run(&path, false, code, &[(6, 7), (17, 18), (28, 29)]), [(28, 29)]);
+
+    // The baseline is left untouched when it isn't recorded.
+    assert_eq!(fs::read_to_string(&path).unwrap(), recorded);
+
+    // Recording again replaces it.
+    run(&path, true, code, &[(6, 7)]);
+    assert_ne!(fs::read_to_string(&path).unwrap(), recorded);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn duplicate_warnings_are_counted() {
+    let path = std::env::temp_dir()
+        .join(format!("rustc-diagnostic-baseline-dup-{}.json", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let code = "let a = 1;\nlet a = 1;\n";
+    assert_eq!(run(&path, true, code, &[(4, 5)]), [(4, 5)]);
+    // Only one of the two identical warnings was part of the baseline.
+    assert_eq!(run(&path, false, code, &[(4, 5), (15, 16)]), [(15, 16)]);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn invalid_baseline() {
+    let path = std::env::temp_dir()
+        .join(format!("rustc-diagnostic-baseline-invalid-{}.json", std::process::id()));
+    fs::write(&path, "{\"version\": 0, \"warnings\": []}").unwrap();
+    let err = DiagnosticBaseline::load(path.clone()).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    fs::remove_file(&path).unwrap();
+
+    // A missing baseline isn't recorded implicitly.
+    let err = DiagnosticBaseline::load(path.clone()).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+}
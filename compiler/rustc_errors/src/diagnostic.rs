@@ -229,6 +229,37 @@ pub struct IsLint {
     pub(crate) name: String,
     /// Indicates whether this lint should show up in cargo's future breakage report.
     has_future_breakage: bool,
+    /// The path of the item the lint was emitted in, if known. Only recorded with
+    /// `-Z diagnostic-baseline`, where it is part of the key identifying the warning.
+    pub(crate) def_path: Option<String>,
+    /// How the level of the lint was set, if known.
+    pub(crate) level_source: Option<DiagLintLevelSource>,
+}
+
+/// How the level a lint is emitted at was set, for machine-readable diagnostics.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Encodable, Decodable)]
+pub struct DiagLintLevelSource {
+    pub kind: DiagLintLevelSourceKind,
+    /// The name of the lint, or lint group, whose level was set.
+    pub name: String,
+    /// The level the lint is emitted at, e.g. `deny`.
+    pub level: String,
+    /// The level set with `--cap-lints`, if the lint is emitted at that level (and so may have
+    /// been lowered to it).
+    pub cap_lints: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Encodable, Decodable)]
+pub enum DiagLintLevelSourceKind {
+    /// The default level of the lint.
+    Default,
+    /// A lint attribute, e.g. `#[deny(warnings)]`.
+    Attribute { span: Span, reason: Option<String> },
+    /// A command-line flag, e.g. `-D warnings`.
+    CommandLine { flag: String },
+    /// The `[lints]` table of the `--lint-config` file, or its table for the modules matching
+    /// `pattern`.
+    LintConfig { pattern: Option<String> },
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -1218,7 +1249,27 @@ pub fn span(&mut self, sp: impl Into<MultiSpan>) -> &mut Self {
 
     #[rustc_lint_diagnostics]
     pub fn is_lint(&mut self, name: String, has_future_breakage: bool) -> &mut Self {
-        self.is_lint = Some(IsLint { name, has_future_breakage });
+        self.is_lint =
+            Some(IsLint { name, has_future_breakage, def_path: None, level_source: None });
+        self
+    }
+
+    /// Records how the level of a lint was set. Does nothing for diagnostics that aren't lints.
+    #[rustc_lint_diagnostics]
+    pub fn lint_level_source(&mut self, source: DiagLintLevelSource) -> &mut Self {
+        if let Some(is_lint) = &mut self.is_lint {
+            is_lint.level_source = Some(source);
+        }
+        self
+    }
+
+    /// Records the path of the item a lint was emitted in. Does nothing for diagnostics that
+    /// aren't lints.
+    #[rustc_lint_diagnostics]
+    pub fn lint_def_path(&mut self, def_path: String) -> &mut Self {
+        if let Some(is_lint) = &mut self.is_lint {
+            is_lint.def_path = Some(def_path);
+        }
         self
     }
 
@@ -199,6 +199,10 @@ fn emit_unused_externs(
     ) {
     }
 
+    /// Tells the emitter about the lints of the lint store, once it has been created.
+    /// Currently only used by the SARIF format, to describe the lints of its log.
+    fn register_lints(&mut self, _lints: &[&'static rustc_lint_defs::Lint]) {}
+
     /// Checks if should show explanations about "rustc --explain"
     fn should_show_explain(&self) -> bool {
         true
@@ -0,0 +1,282 @@
+//! Support for `-Zemit-fix-patch`, which writes the machine-applicable suggestions of all emitted
+//! diagnostics as a unified diff, so that they can be applied with `patch -p0` or `git apply -p0`
+//! where `cargo fix` can't be used.
+//!
+//! Only suggestions with a single substitution are applied: the others need someone to pick one
+//! of the alternatives. Overlapping suggestions are resolved deterministically: suggestions are
+//! considered in the order of their first edit in each file (and then in the order they were
+//! emitted), and a suggestion overlapping one that was already accepted is dropped. Running the
+//! compiler again after applying the patch will suggest the dropped fixes again, if they still
+//! apply.
+//!
+//! The patch is made against the files as they are on disk. As the compiler only keeps their
+//! normalized source, without a byte order mark and with CRLF line endings turned into LF, files
+//! which needed normalizing are read again, and their suggestions dropped if they changed since
+//! they were compiled.
+
+use crate::{Applicability, DiagInner};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::sync::Lrc;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{SourceFile, SourceFileHash, StableSourceFileId};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+mod tests;
+
+/// The number of unchanged lines shown before and after each change.
+const CONTEXT: usize = 3;
+
+pub struct FixPatch {
+    path: PathBuf,
+    /// The edits of each machine-applicable suggestion, in the order they were emitted.
+    suggestions: Vec<Vec<Edit>>,
+    /// The source of the files with suggestions as it is on disk, if available.
+    sources: FxHashMap<StableSourceFileId, Option<OriginalSource>>,
+}
+
+#[derive(Clone)]
+struct OriginalSource {
+    src: Lrc<String>,
+    /// Whether the file has CRLF line endings, which the suggestions should use too.
+    crlf: bool,
+}
+
+impl OriginalSource {
+    fn of(sf: &SourceFile) -> Option<OriginalSource> {
+        let src = if sf.normalized_pos.is_empty() {
+            sf.src.clone()?
+        } else {
+            let path = sf.name.clone().into_local_path()?;
+            let src = fs::read_to_string(path).ok()?;
+            // The hash is computed before normalizing, so this checks that the file wasn't
+            // changed since it was compiled.
+            if SourceFileHash::new(sf.src_hash.kind, &src) != sf.src_hash {
+                return None;
+            }
+            Lrc::new(src)
+        };
+        let crlf = src.contains("\r\n");
+        Some(OriginalSource { src, crlf })
+    }
+}
+
+struct Edit {
+    /// The name of the file, as displayed in diagnostics.
+    file: String,
+    src: Lrc<String>,
+    /// The byte range to replace, relative to the start of the file.
+    range: Range<usize>,
+    snippet: String,
+}
+
+impl Edit {
+    fn conflicts_with(&self, other: &Edit) -> bool {
+        // Two insertions at the same position conflict too, as their order is ambiguous.
+        self.file == other.file
+            && (self.range.start == other.range.start
+                || self.range.start < other.range.end && other.range.start < self.range.end)
+    }
+}
+
+impl FixPatch {
+    pub fn new(path: PathBuf) -> FixPatch {
+        FixPatch { path, suggestions: Vec::new(), sources: FxHashMap::default() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Collects the machine-applicable suggestions of a diagnostic that is being emitted.
+    pub(crate) fn add_suggestions(&mut self, diag: &DiagInner, sm: &SourceMap) {
+        let Ok(suggestions) = &diag.suggestions else { return };
+        let sources = &mut self.sources;
+        for suggestion in suggestions {
+            if suggestion.applicability != Applicability::MachineApplicable {
+                continue;
+            }
+            let [substitution] = &suggestion.substitutions[..] else { continue };
+            let edits: Option<Vec<_>> = substitution
+                .parts
+                .iter()
+                .map(|part| {
+                    if part.span.is_dummy() {
+                        return None;
+                    }
+                    let lo = sm.lookup_byte_offset(part.span.lo());
+                    let hi = sm.lookup_byte_offset(part.span.hi());
+                    // Suggestions can only be applied to the files we have the source of.
+                    if !Lrc::ptr_eq(&lo.sf, &hi.sf) {
+                        return None;
+                    }
+                    let OriginalSource { src, crlf } = sources
+                        .entry(lo.sf.stable_id)
+                        .or_insert_with(|| OriginalSource::of(&lo.sf))
+                        .clone()?;
+                    // The positions of the span are in the normalized source.
+                    let range = lo.sf.original_relative_byte_pos(part.span.lo()).to_usize()
+                        ..lo.sf.original_relative_byte_pos(part.span.hi()).to_usize();
+                    let snippet = if crlf {
+                        part.snippet.replace('\n', "\r\n")
+                    } else {
+                        part.snippet.clone()
+                    };
+                    Some(Edit {
+                        file: sm.filename_for_diagnostics(&lo.sf.name).to_string(),
+                        src,
+                        range,
+                        snippet,
+                    })
+                })
+                .collect();
+            if let Some(edits) = edits
+                && !edits.is_empty()
+            {
+                self.suggestions.push(edits);
+            }
+        }
+    }
+
+    /// Writes out the patch, which is empty if there were no suggestions to apply.
+    pub fn write(self) -> io::Result<()> {
+        fs::write(&self.path, self.to_patch())
+    }
+
+    fn to_patch(mut self) -> String {
+        // The sort is stable, so suggestions starting at the same position stay in the order they
+        // were emitted.
+        self.suggestions
+            .sort_by(|a, b| (&a[0].file, a[0].range.start).cmp(&(&b[0].file, b[0].range.start)));
+        let mut files: BTreeMap<String, Vec<Edit>> = BTreeMap::new();
+        for suggestion in self.suggestions {
+            let conflicts = suggestion.iter().any(|edit| {
+                files
+                    .get(&edit.file)
+                    .is_some_and(|edits| edits.iter().any(|accepted| accepted.conflicts_with(edit)))
+            });
+            if !conflicts {
+                for edit in suggestion {
+                    files.entry(edit.file.clone()).or_default().push(edit);
+                }
+            }
+        }
+
+        let mut patch = String::new();
+        for (file, mut edits) in files {
+            edits.sort_by_key(|edit| edit.range.start);
+            unified_diff(&mut patch, &file, &edits[0].src, &edits);
+        }
+        patch
+    }
+}
+
+/// Appends the unified diff of applying `edits`, which are sorted and don't overlap, to `src`.
+fn unified_diff(out: &mut String, file: &str, src: &str, edits: &[Edit]) {
+    let mut line_starts = vec![0];
+    line_starts.extend(src.match_indices('\n').map(|(i, _)| i + 1));
+    let line_of = |pos: usize| line_starts.partition_point(|&start| start <= pos) - 1;
+    let line_start = |line: usize| line_starts.get(line).copied().unwrap_or(src.len());
+
+    // Group the edits touching the same lines into changes, each replacing a range of lines.
+    let mut changes: Vec<(Range<usize>, Vec<&Edit>)> = Vec::new();
+    for edit in edits {
+        let (start, end) = (edit.range.start, edit.range.end);
+        let first = line_of(start);
+        let mut last = line_of(if end > start { end - 1 } else { start });
+        // An edit removing a line break, without removing the whole line, joins the following
+        // line to the changed ones.
+        if end > start && src.as_bytes()[end - 1] == b'\n' {
+            let new_line = format!("{}{}", &src[line_start(first)..start], edit.snippet);
+            if !new_line.is_empty() && !new_line.ends_with('\n') {
+                last = line_of(end);
+            }
+        }
+        match changes.last_mut() {
+            Some((lines, edits)) if first < lines.end => {
+                lines.end = lines.end.max(last + 1);
+                edits.push(edit);
+            }
+            _ => changes.push((first..last + 1, vec![edit])),
+        }
+    }
+
+    out.push_str(&format!("--- {file}\n+++ {file}\n"));
+    // The difference between the line numbers of the new and old files before the current hunk.
+    let mut offset = 0isize;
+    let mut i = 0;
+    while i < changes.len() {
+        // Changes whose contexts would overlap are shown in the same hunk.
+        let mut j = i + 1;
+        while j < changes.len() && changes[j].0.start - changes[j - 1].0.end <= 2 * CONTEXT {
+            j += 1;
+        }
+        let hunk = &changes[i..j];
+        let start = hunk[0].0.start.saturating_sub(CONTEXT);
+        let end = (hunk[hunk.len() - 1].0.end + CONTEXT).min(line_starts.len());
+
+        let mut body = String::new();
+        let (mut old_len, mut new_len) = (0, 0);
+        let mut line = start;
+        for (lines, edits) in hunk {
+            let context =
+                push_lines(&mut body, ' ', &src[line_start(line)..line_start(lines.start)]);
+            old_len += context;
+            new_len += context;
+
+            let old = &src[line_start(lines.start)..line_start(lines.end)];
+            let mut new = String::new();
+            let mut pos = line_start(lines.start);
+            for edit in edits {
+                new.push_str(&src[pos..edit.range.start]);
+                new.push_str(&edit.snippet);
+                pos = edit.range.end;
+            }
+            new.push_str(&src[pos..line_start(lines.end)]);
+            old_len += push_lines(&mut body, '-', old);
+            new_len += push_lines(&mut body, '+', &new);
+            line = lines.end;
+        }
+        let context = push_lines(&mut body, ' ', &src[line_start(line)..line_start(end)]);
+        old_len += context;
+        new_len += context;
+
+        let new_start = (start as isize + offset) as usize;
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(start, old_len),
+            hunk_range(new_start, new_len)
+        ));
+        out.push_str(&body);
+        offset += new_len as isize - old_len as isize;
+        i = j;
+    }
+}
+
+/// Formats the range of a hunk, from the 0-based index of its first line.
+fn hunk_range(start: usize, len: usize) -> String {
+    match len {
+        // An empty range refers to the line before it.
+        0 => format!("{start},0"),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{len}", start + 1),
+    }
+}
+
+/// Appends the lines of `text` prefixed with `prefix`, and returns how many there were.
+fn push_lines(out: &mut String, prefix: char, text: &str) -> usize {
+    let mut count = 0;
+    for line in text.split_inclusive('\n') {
+        out.push(prefix);
+        out.push_str(line);
+        if !line.ends_with('\n') {
+            out.push_str("\n\\ No newline at end of file\n");
+        }
+        count += 1;
+    }
+    count
+}
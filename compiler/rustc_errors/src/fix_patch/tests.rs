@@ -0,0 +1,234 @@
+use super::*;
+
+use crate::emitter::{ColorConfig, HumanReadableErrorType};
+use crate::json::JsonEmitter;
+use crate::test_helpers::with_source_map;
+use crate::DiagCtxt;
+use rustc_span::{BytePos, Span};
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Runs `f` with a `DiagCtxt` writing a fix patch, and returns the patch. `load` adds the source
+/// file to the `SourceMap`, and `f` is given a function returning the span of the first
+/// occurrence of a string in its normalized source.
+fn emit_patch_for(
+    load: impl FnOnce(&SourceMap) -> Lrc<SourceFile>,
+    f: impl FnOnce(&DiagCtxt, &dyn Fn(&str) -> Span),
+) -> String {
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+    let path = std::env::temp_dir().join(format!(
+        "rustc-fix-patch-{}-{}.diff",
+        std::process::id(),
+        COUNT.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    with_source_map(|sm, fallback_bundle| {
+        let sf = load(&sm);
+        let je = JsonEmitter::new(
+            Box::new(io::sink()),
+            sm,
+            fallback_bundle,
+            false,
+            HumanReadableErrorType::Short(ColorConfig::Never),
+        );
+        let dcx = DiagCtxt::new(Box::new(je)).with_fix_patch(FixPatch::new(path.clone()));
+        let src = sf.src.as_deref().unwrap();
+        let span = |s: &str| {
+            let lo = sf.start_pos.to_usize() + src.find(s).unwrap();
+            Span::with_root_ctxt(BytePos(lo as u32), BytePos((lo + s.len()) as u32))
+        };
+        f(&dcx, &span);
+        dcx.write_fix_patch().unwrap();
+    });
+
+    let patch = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    patch
+}
+
+/// Like `emit_patch_for`, with `code` as the source of `src/lib.rs`.
+fn emit_patch(code: &str, f: impl FnOnce(&DiagCtxt, &dyn Fn(&str) -> Span)) -> String {
+    emit_patch_for(
+        |sm| sm.new_source_file(Path::new("src/lib.rs").to_owned().into(), code.to_owned()),
+        f,
+    )
+}
+
+/// Writes `code` to a new file in the temporary directory, and returns its path.
+fn write_temp_file(code: &str) -> PathBuf {
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+    let path = std::env::temp_dir().join(format!(
+        "rustc-fix-patch-{}-{}.rs",
+        std::process::id(),
+        COUNT.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&path, code).unwrap();
+    path
+}
+
+#[test]
+fn single_hunk() {
+    let code = "fn main() {\n    let x = 1;\n    let mut y = 2;\n    println!(\"{y}\");\n}\n";
+    let patch = emit_patch(code, |dcx, span| {
+        dcx.struct_span_warn(span("x"), "unused variable")
+            .with_span_suggestion(span("x"), "prefix it", "_x", Applicability::MachineApplicable)
+            .emit();
+        dcx.struct_span_warn(span("mut "), "unused mut")
+            .with_span_suggestion(span("mut "), "remove it", "", Applicability::MachineApplicable)
+            .emit();
+    });
+    assert_eq!(
+        patch,
+        concat!(
+            "--- src/lib.rs\n",
+            "+++ src/lib.rs\n",
+            "@@ -1,5 +1,5 @@\n",
+            " fn main() {\n",
+            "-    let x = 1;\n",
+            "+    let _x = 1;\n",
+            "-    let mut y = 2;\n",
+            "+    let y = 2;\n",
+            "     println!(\"{y}\");\n",
+            " }\n",
+        )
+    );
+}
+
+#[test]
+fn several_hunks() {
+    let code: String = (1..=12).map(|i| format!("a{i}\n")).collect();
+    let patch = emit_patch(&code, |dcx, span| {
+        dcx.struct_span_warn(span("a2\n"), "unneeded line")
+            .with_span_suggestion(span("a2\n"), "remove it", "", Applicability::MachineApplicable)
+            .emit();
+        dcx.struct_span_warn(span("a11"), "bad name")
+            .with_span_suggestion(span("a11"), "rename it", "b11", Applicability::MachineApplicable)
+            .emit();
+    });
+    assert_eq!(
+        patch,
+        "--- src/lib.rs\n+++ src/lib.rs\n\
+         @@ -1,5 +1,4 @@\n a1\n-a2\n a3\n a4\n a5\n\
+         @@ -8,5 +7,5 @@\n a8\n a9\n a10\n-a11\n+b11\n a12\n"
+    );
+}
+
+#[test]
+fn overlapping_suggestions() {
+    let code = "let v = 1;";
+    let patch = emit_patch(code, |dcx, span| {
+        let mut diag = dcx.struct_span_warn(span("v"), "bad name");
+        diag.span_suggestion(span("v"), "rename it", "_v", Applicability::MachineApplicable);
+        // Not applied, as they aren't machine-applicable or have several alternatives.
+        diag.span_suggestion(span("1"), "change it", "2", Applicability::MaybeIncorrect);
+        diag.span_suggestions(
+            span("1"),
+            "change it",
+            ["3".to_owned(), "4".to_owned()],
+            Applicability::MachineApplicable,
+        );
+        diag.emit();
+        // Emitted later, but applied first as it starts earlier, so the first suggestion is
+        // dropped.
+        dcx.struct_span_warn(span("let v"), "not mutable")
+            .with_span_suggestion(
+                span("let v"),
+                "make it mutable",
+                "let mut v",
+                Applicability::MachineApplicable,
+            )
+            .emit();
+        dcx.struct_span_warn(span("1"), "too small")
+            .with_span_suggestion(span("1"), "increase it", "10", Applicability::MachineApplicable)
+            .emit();
+    });
+    assert_eq!(
+        patch,
+        "--- src/lib.rs\n+++ src/lib.rs\n@@ -1 +1 @@\n\
+         -let v = 1;\n\\ No newline at end of file\n\
+         +let mut v = 10;\n\\ No newline at end of file\n"
+    );
+}
+
+#[test]
+fn crlf_and_bom() {
+    let path =
+        write_temp_file("\u{feff}fn main() {\r\n    let x = 1;\r\n    let mut y = 2;\r\n}\r\n");
+    let patch = emit_patch_for(
+        |sm| sm.load_file(&path).unwrap(),
+        |dcx, span| {
+            dcx.struct_span_warn(span("fn"), "missing comment")
+                .with_span_suggestion(
+                    span("fn").shrink_to_lo(),
+                    "add one",
+                    "// a\n",
+                    Applicability::MachineApplicable,
+                )
+                .emit();
+            dcx.struct_span_warn(span("x"), "unused variable")
+                .with_span_suggestion(
+                    span("x"),
+                    "prefix it",
+                    "_x",
+                    Applicability::MachineApplicable,
+                )
+                .emit();
+            dcx.struct_span_warn(span("mut "), "unused mut")
+                .with_span_suggestion(
+                    span("mut "),
+                    "remove it",
+                    "",
+                    Applicability::MachineApplicable,
+                )
+                .emit();
+        },
+    );
+    fs::remove_file(&path).unwrap();
+    // The context and the offsets are those of the file on disk, and the added line uses its line
+    // endings too.
+    let file = path.display();
+    assert_eq!(
+        patch,
+        format!(
+            concat!(
+                "--- {file}\n",
+                "+++ {file}\n",
+                "@@ -1,4 +1,5 @@\n",
+                "-\u{feff}fn main() {{\r\n",
+                "+\u{feff}// a\r\n",
+                "+fn main() {{\r\n",
+                "-    let x = 1;\r\n",
+                "+    let _x = 1;\r\n",
+                "-    let mut y = 2;\r\n",
+                "+    let y = 2;\r\n",
+                " }}\r\n",
+            ),
+            file = file
+        )
+    );
+}
+
+#[test]
+fn changed_file() {
+    let path = write_temp_file("fn main() {\r\n    let x = 1;\r\n}\r\n");
+    let patch = emit_patch_for(
+        |sm| {
+            let sf = sm.load_file(&path).unwrap();
+            fs::write(&path, "fn main() {\r\n    let y = 1;\r\n}\r\n").unwrap();
+            sf
+        },
+        |dcx, span| {
+            dcx.struct_span_warn(span("x"), "unused variable")
+                .with_span_suggestion(
+                    span("x"),
+                    "prefix it",
+                    "_x",
+                    Applicability::MachineApplicable,
+                )
+                .emit();
+        },
+    );
+    fs::remove_file(&path).unwrap();
+    // The file can't be patched from the positions in its old contents.
+    assert_eq!(patch, "");
+}
@@ -16,8 +16,8 @@
 use crate::registry::Registry;
 use crate::translation::{to_fluent_args, Translate};
 use crate::{
-    diagnostic::IsLint, CodeSuggestion, FluentBundle, LazyFallbackBundle, MultiSpan, SpanLabel,
-    Subdiag, TerminalUrl,
+    diagnostic::IsLint, CodeSuggestion, DiagLintLevelSource, DiagLintLevelSourceKind, FluentBundle,
+    LazyFallbackBundle, MultiSpan, SpanLabel, Subdiag, TerminalUrl,
 };
 use derive_setters::Setters;
 use rustc_data_structures::sync::{IntoDynSyncSend, Lrc};
@@ -184,6 +184,9 @@ struct Diagnostic {
     children: Vec<Diagnostic>,
     /// The message as rustc would render it.
     rendered: Option<String>,
+    /// How the level of the lint was set, for lints.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lint_level_source: Option<DiagnosticLintLevelSource>,
 }
 
 #[derive(Serialize)]
@@ -237,6 +240,33 @@ struct DiagnosticSpanMacroExpansion {
     def_site_span: DiagnosticSpan,
 }
 
+#[derive(Serialize)]
+struct DiagnosticLintLevelSource {
+    /// "default", "attribute", "command_line" or "lint_config".
+    kind: &'static str,
+    /// The name of the lint, or lint group, whose level was set.
+    name: String,
+    /// The level the lint is emitted at, e.g. "deny".
+    level: String,
+    /// The lint attribute, for "attribute".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    span: Option<DiagnosticSpan>,
+    /// The reason given in the lint attribute, for "attribute".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    /// The command-line flag, e.g. "-D warnings", for "command_line".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flag: Option<String>,
+    /// The module pattern of the `--lint-config` table, for "lint_config" (absent for the
+    /// `[lints]` table).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pattern: Option<String>,
+    /// The level set with `--cap-lints`, if the lint is emitted at that level (and so may have
+    /// been lowered to it).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cap_lints: Option<String>,
+}
+
 #[derive(Serialize)]
 struct DiagnosticCode {
     /// The error code (e.g. "E1234"), if the diagnostic has one. Or the lint
@@ -292,6 +322,7 @@ fn from_errors_diagnostic(diag: crate::DiagInner, je: &JsonEmitter) -> Diagnosti
                 spans: DiagnosticSpan::from_suggestion(sugg, &args, je),
                 children: vec![],
                 rendered: None,
+                lint_level_source: None,
             }
         });
 
@@ -335,6 +366,11 @@ fn reset(&mut self) -> io::Result<()> {
         } else {
             None
         };
+        let lint_level_source = diag
+            .is_lint
+            .as_ref()
+            .and_then(|is_lint| is_lint.level_source.clone())
+            .map(|source| DiagnosticLintLevelSource::from_diag(source, je));
         let level = diag.level.to_str();
         let spans = DiagnosticSpan::from_multispan(&diag.span, &args, je);
         let children = diag
@@ -372,6 +408,7 @@ fn reset(&mut self) -> io::Result<()> {
             spans,
             children,
             rendered: Some(buf),
+            lint_level_source,
         }
     }
 
@@ -388,7 +425,41 @@ fn from_sub_diagnostic(
             spans: DiagnosticSpan::from_multispan(&subdiag.span, args, je),
             children: vec![],
             rendered: None,
+            lint_level_source: None,
+        }
+    }
+}
+
+impl DiagnosticLintLevelSource {
+    fn from_diag(source: DiagLintLevelSource, je: &JsonEmitter) -> DiagnosticLintLevelSource {
+        let DiagLintLevelSource { kind, name, level, cap_lints } = source;
+        let mut json = DiagnosticLintLevelSource {
+            kind: "default",
+            name,
+            level,
+            span: None,
+            reason: None,
+            flag: None,
+            pattern: None,
+            cap_lints,
+        };
+        match kind {
+            DiagLintLevelSourceKind::Default => {}
+            DiagLintLevelSourceKind::Attribute { span, reason } => {
+                json.kind = "attribute";
+                json.span = Some(DiagnosticSpan::from_span_etc(span, true, None, None, je));
+                json.reason = reason;
+            }
+            DiagLintLevelSourceKind::CommandLine { flag } => {
+                json.kind = "command_line";
+                json.flag = Some(flag);
+            }
+            DiagLintLevelSourceKind::LintConfig { pattern } => {
+                json.kind = "lint_config";
+                json.pattern = pattern;
+            }
         }
+        json
     }
 }
 
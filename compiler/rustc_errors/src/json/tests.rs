@@ -1,7 +1,7 @@
 use super::*;
 
+use crate::test_helpers::{with_source_map, Shared};
 use crate::DiagCtxt;
-use rustc_span::source_map::FilePathMapping;
 use rustc_span::BytePos;
 
 use std::str;
@@ -23,27 +23,10 @@ struct SpanTestData {
     pub column_end: u32,
 }
 
-struct Shared<T> {
-    data: Arc<Mutex<T>>,
-}
-
-impl<T: Write> Write for Shared<T> {
-    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.data.lock().unwrap().write(buf)
-    }
-
-    fn flush(&mut self) -> io::Result<()> {
-        self.data.lock().unwrap().flush()
-    }
-}
-
 /// Test the span yields correct positions in JSON.
 fn test_positions(code: &str, span: (u32, u32), expected_output: SpanTestData) {
-    rustc_span::create_default_session_globals_then(|| {
-        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+    with_source_map(|sm, fallback_bundle| {
         sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
-        let fallback_bundle =
-            crate::fallback_fluent_bundle(vec![crate::DEFAULT_LOCALE_RESOURCE], false);
 
         let output = Arc::new(Mutex::new(Vec::new()));
         let je = JsonEmitter::new(
@@ -195,3 +178,47 @@ fn span_after_crlf() {
         },
     )
 }
+
+#[test]
+fn lint_level_source() {
+    with_source_map(|sm, fallback_bundle| {
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let je = JsonEmitter::new(
+            Box::new(Shared { data: output.clone() }),
+            sm,
+            fallback_bundle,
+            false,
+            HumanReadableErrorType::Short(ColorConfig::Never),
+        );
+
+        let dcx = DiagCtxt::new(Box::new(je));
+        let mut diag = dcx.struct_warn("unused variable: `x`");
+        diag.is_lint("unused_variables".to_owned(), false);
+        diag.lint_level_source(DiagLintLevelSource {
+            kind: DiagLintLevelSourceKind::CommandLine { flag: "-W unused".to_owned() },
+            name: "unused".to_owned(),
+            level: "warn".to_owned(),
+            cap_lints: Some("warn".to_owned()),
+        });
+        diag.emit();
+        dcx.struct_warn("not a lint").emit();
+
+        let bytes = output.lock().unwrap();
+        let diags: Vec<serde_json::Value> = str::from_utf8(&bytes)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            diags[0]["lint_level_source"],
+            serde_json::json!({
+                "kind": "command_line",
+                "name": "unused",
+                "level": "warn",
+                "flag": "-W unused",
+                "cap_lints": "warn",
+            })
+        );
+        assert_eq!(diags[1].get("lint_level_source"), None);
+    })
+}
@@ -36,9 +36,9 @@
 
 pub use codes::*;
 pub use diagnostic::{
-    BugAbort, Diag, DiagArg, DiagArgMap, DiagArgName, DiagArgValue, DiagInner, DiagStyledString,
-    Diagnostic, EmissionGuarantee, FatalAbort, IntoDiagArg, LintDiagnostic, StringPart, Subdiag,
-    SubdiagMessageOp, Subdiagnostic,
+    BugAbort, Diag, DiagArg, DiagArgMap, DiagArgName, DiagArgValue, DiagInner, DiagLintLevelSource,
+    DiagLintLevelSourceKind, DiagStyledString, Diagnostic, EmissionGuarantee, FatalAbort,
+    IntoDiagArg, LintDiagnostic, StringPart, Subdiag, SubdiagMessageOp, Subdiagnostic,
 };
 pub use diagnostic_impls::{
     DiagArgFromDisplay, DiagSymbolList, ExpectedLifetimeParameter, IndicateAnonymousLifetime,
@@ -46,8 +46,9 @@
 };
 pub use emitter::ColorConfig;
 pub use rustc_error_messages::{
-    fallback_fluent_bundle, fluent_bundle, DelayDm, DiagMessage, FluentBundle, LanguageIdentifier,
-    LazyFallbackBundle, MultiSpan, SpanLabel, SubdiagMessage,
+    fallback_fluent_bundle, fluent_bundle, validate_fluent_bundle, DelayDm, DiagMessage,
+    FluentBundle, LanguageIdentifier, LazyFallbackBundle, MultiSpan, SkippedResource, SpanLabel,
+    SubdiagMessage, TranslationMismatch,
 };
 pub use rustc_lint_defs::{pluralize, Applicability};
 pub use rustc_span::fatal_error::{FatalError, FatalErrorMarker};
@@ -58,7 +59,9 @@
 // See https://github.com/rust-lang/rust/pull/115393.
 pub use termcolor::{Color, ColorSpec, WriteColor};
 
+use baseline::DiagnosticBaseline;
 use emitter::{is_case_difference, DynEmitter, Emitter};
+use fix_patch::FixPatch;
 use registry::Registry;
 use rustc_data_structures::fx::{FxHashSet, FxIndexMap, FxIndexSet};
 use rustc_data_structures::stable_hasher::{Hash128, StableHasher};
@@ -81,18 +84,23 @@
 use Level::*;
 
 pub mod annotate_snippet_emitter_writer;
+pub mod baseline;
 pub mod codes;
 mod diagnostic;
 mod diagnostic_impls;
 pub mod emitter;
 pub mod error;
+pub mod fix_patch;
 pub mod json;
 mod lock;
 pub mod markdown;
 pub mod registry;
+pub mod sarif;
 mod snippet;
 mod styled_buffer;
 #[cfg(test)]
+mod test_helpers;
+#[cfg(test)]
 mod tests;
 pub mod translation;
 
@@ -500,6 +508,12 @@ struct DiagCtxtInner {
     /// The file where the ICE information is stored. This allows delayed_span_bug backtraces to be
     /// stored along side the main panic backtrace.
     ice_file: Option<PathBuf>,
+
+    /// The baseline of known warnings to record or suppress, from `-Z diagnostic-baseline`.
+    baseline: Option<DiagnosticBaseline>,
+
+    /// Where to write the machine-applicable suggestions, from `-Z emit-fix-patch`.
+    fix_patch: Option<FixPatch>,
 }
 
 /// A key denoting where from a diagnostic was stashed.
@@ -608,6 +622,16 @@ pub fn with_ice_file(mut self, ice_file: PathBuf) -> Self {
         self
     }
 
+    pub fn with_diagnostic_baseline(mut self, baseline: DiagnosticBaseline) -> Self {
+        self.inner.get_mut().baseline = Some(baseline);
+        self
+    }
+
+    pub fn with_fix_patch(mut self, fix_patch: FixPatch) -> Self {
+        self.inner.get_mut().fix_patch = Some(fix_patch);
+        self
+    }
+
     pub fn new(emitter: Box<DynEmitter>) -> Self {
         Self { inner: Lock::new(DiagCtxtInner::new(emitter)) }
     }
@@ -1013,6 +1037,32 @@ pub fn emit_artifact_notification(&self, path: &Path, artifact_type: &str) {
         self.inner.borrow_mut().emitter.emit_artifact_notification(path, artifact_type);
     }
 
+    pub fn register_lints(&self, lints: &[&'static rustc_lint_defs::Lint]) {
+        self.inner.borrow_mut().emitter.register_lints(lints);
+    }
+
+    /// Writes out the warnings recorded for `-Z diagnostic-baseline`, if they were recorded. Nothing
+    /// is written if errors were emitted, as compilation may have stopped before all warnings were
+    /// emitted.
+    pub fn write_diagnostic_baseline(&self) -> std::io::Result<()> {
+        let mut inner = self.inner.borrow_mut();
+        if inner.has_errors().is_some() {
+            return Ok(());
+        }
+        match inner.baseline.take() {
+            Some(baseline) => baseline.write(),
+            None => Ok(()),
+        }
+    }
+
+    /// Writes out the machine-applicable suggestions collected for `-Z emit-fix-patch`.
+    pub fn write_fix_patch(&self) -> std::io::Result<()> {
+        match self.inner.borrow_mut().fix_patch.take() {
+            Some(fix_patch) => fix_patch.write(),
+            None => Ok(()),
+        }
+    }
+
     pub fn emit_future_breakage_report(&self) {
         let mut inner = self.inner.borrow_mut();
         let diags = std::mem::take(&mut inner.future_breakage_diagnostics);
@@ -1407,6 +1457,8 @@ fn new(emitter: Box<DynEmitter>) -> Self {
             unstable_expect_diagnostics: Vec::new(),
             fulfilled_expectations: Default::default(),
             ice_file: None,
+            baseline: None,
+            fix_patch: None,
         }
     }
 
@@ -1492,6 +1544,16 @@ fn emit_diagnostic(&mut self, mut diagnostic: DiagInner) -> Option<ErrorGuarante
                     }
                     return None;
                 }
+                if let Some(baseline) = &mut self.baseline
+                    && baseline.suppress(&diagnostic, &*self.emitter)
+                {
+                    // The warning is part of the baseline. It is still tracked, because the
+                    // baseline is not part of the incremental state, and treated like an
+                    // expected lint for the `must_produce_diag` check.
+                    TRACK_DIAGNOSTIC(diagnostic, &mut |_| None);
+                    self.suppressed_expected_diag = true;
+                    return None;
+                }
             }
             Note | Help | FailureNote => {}
             OnceNote | OnceHelp => panic!("bad level: {:?}", diagnostic.level),
@@ -1571,6 +1633,11 @@ fn emit_diagnostic(&mut self, mut diagnostic: DiagInner) -> Option<ErrorGuarante
                 }
                 self.has_printed = true;
 
+                if let Some(fix_patch) = &mut self.fix_patch
+                    && let Some(sm) = self.emitter.source_map()
+                {
+                    fix_patch.add_suggestions(&diagnostic, sm);
+                }
                 self.emitter.emit_diagnostic(diagnostic);
             }
 
@@ -0,0 +1,515 @@
+//! A SARIF 2.1.0 emitter for errors.
+//!
+//! [SARIF] is a standard format for the output of static analysis tools, understood by many code
+//! scanning services. Unlike the JSON emitter, which prints every diagnostic as soon as it is
+//! emitted, a SARIF log describes a whole analysis run in a single document. Diagnostics are
+//! therefore collected as they are emitted, and the log is written out once the emitter is
+//! dropped.
+//!
+//! The diagnostics emitted before the session exists, through an `EarlyDiagCtxt`, are handed over
+//! to the session's emitter so that a compilation writes a single log.
+//!
+//! [SARIF]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use crate::registry::Registry;
+use crate::translation::{to_fluent_args, Translate};
+use crate::{
+    diagnostic::IsLint, emitter::Emitter, CodeSuggestion, DiagInner, FluentBundle,
+    LazyFallbackBundle, Level, SpanLabel, Subdiag,
+};
+use derive_setters::Setters;
+use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
+use rustc_data_structures::sync::{IntoDynSyncSend, Lrc};
+use rustc_error_messages::FluentArgs;
+use rustc_lint_defs::{Applicability, Lint};
+use rustc_span::source_map::SourceMap;
+use rustc_span::Span;
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::mem;
+use std::sync::Mutex;
+
+#[cfg(test)]
+mod tests;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// The diagnostics emitted by early emitters, which the next session emitter to be dropped adds to
+/// its log.
+static EARLY_LOG: Mutex<EarlyLog> =
+    Mutex::new(EarlyLog { diagnostics: Vec::new(), session_emitters: 0 });
+
+struct EarlyLog {
+    diagnostics: Vec<DiagInner>,
+    /// The number of live emitters which aren't early emitters.
+    session_emitters: usize,
+}
+
+#[derive(Setters)]
+pub struct SarifEmitter {
+    #[setters(skip)]
+    dst: IntoDynSyncSend<Box<dyn Write + Send>>,
+    registry: Option<Registry>,
+    #[setters(skip)]
+    sm: Lrc<SourceMap>,
+    fluent_bundle: Option<Lrc<FluentBundle>>,
+    #[setters(skip)]
+    fallback_bundle: LazyFallbackBundle,
+    /// The version of the compiler, reported as the version of the tool that produced the log.
+    tool_version: Option<String>,
+    /// Whether this is the emitter of an `EarlyDiagCtxt`, see [`SarifEmitter::early`].
+    #[setters(skip)]
+    early: bool,
+    /// The descriptions of the lints registered in the lint store, keyed by their name.
+    #[setters(skip)]
+    lint_descriptions: FxHashMap<String, &'static str>,
+    /// The rules (error codes and lints) referenced by `results`, keyed by their id.
+    #[setters(skip)]
+    rules: FxIndexMap<String, ReportingDescriptor>,
+    #[setters(skip)]
+    results: Vec<SarifResult>,
+}
+
+impl SarifEmitter {
+    pub fn new(
+        dst: Box<dyn Write + Send>,
+        sm: Lrc<SourceMap>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        EARLY_LOG.lock().unwrap().session_emitters += 1;
+        SarifEmitter::new_inner(dst, sm, fallback_bundle, false)
+    }
+
+    /// Creates the emitter of an `EarlyDiagCtxt`. It doesn't write a log of its own, but keeps its
+    /// diagnostics until a session emitter adds them to its log. Only if no session emitter exists
+    /// when it is dropped, as compilation stopped before one was created, does it write them.
+    pub fn early(
+        dst: Box<dyn Write + Send>,
+        sm: Lrc<SourceMap>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        SarifEmitter::new_inner(dst, sm, fallback_bundle, true)
+    }
+
+    fn new_inner(
+        dst: Box<dyn Write + Send>,
+        sm: Lrc<SourceMap>,
+        fallback_bundle: LazyFallbackBundle,
+        early: bool,
+    ) -> SarifEmitter {
+        SarifEmitter {
+            dst: IntoDynSyncSend(dst),
+            registry: None,
+            sm,
+            fluent_bundle: None,
+            fallback_bundle,
+            tool_version: None,
+            early,
+            lint_descriptions: FxHashMap::default(),
+            rules: FxIndexMap::default(),
+            results: Vec::new(),
+        }
+    }
+
+    fn write_log(&mut self) -> io::Result<()> {
+        let log = SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: [Run {
+                tool: Tool {
+                    driver: ToolComponent {
+                        name: "rustc",
+                        information_uri: "https://www.rust-lang.org/",
+                        version: self.tool_version.as_deref(),
+                        rules: self.rules.values().collect(),
+                    },
+                },
+                column_kind: "unicodeCodePoints",
+                results: &self.results,
+            }],
+        };
+        serde_json::to_writer_pretty(&mut *self.dst, &log)?;
+        self.dst.write_all(b"\n")?;
+        self.dst.flush()
+    }
+
+    /// Returns the index of the rule for the error code or lint of `diag`, registering it first
+    /// if this is the first result referencing it.
+    fn rule_index(&mut self, diag: &DiagInner) -> Option<(String, usize)> {
+        let (id, rule) = if let Some(code) = diag.code {
+            let id = code.to_string();
+            let full_description = self
+                .registry
+                .as_ref()
+                .and_then(|registry| registry.try_find_description(code).ok())
+                .map(|description| Message {
+                    // The first line of an error code's explanation summarizes it.
+                    text: description.lines().next().unwrap_or_default().to_owned(),
+                    markdown: Some(description.to_owned()),
+                });
+            let help_uri = Some(format!("https://doc.rust-lang.org/error_codes/{id}.html"));
+            (id.clone(), ReportingDescriptor { id, full_description, help_uri })
+        } else if let Some(IsLint { name, .. }) = &diag.is_lint {
+            let full_description = self
+                .lint_descriptions
+                .get(name)
+                .map(|description| Message::plain(description.to_string()));
+            let help_uri = name.strip_prefix("clippy::").map(|lint| {
+                format!("https://rust-lang.github.io/rust-clippy/master/index.html#/{lint}")
+            });
+            (name.clone(), ReportingDescriptor { id: name.clone(), full_description, help_uri })
+        } else {
+            return None;
+        };
+        let entry = self.rules.entry(id.clone());
+        let index = entry.index();
+        entry.or_insert(rule);
+        Some((id, index))
+    }
+
+    fn region(&self, span: Span) -> Region {
+        let start = self.sm.lookup_char_pos(span.lo());
+        let end = self.sm.lookup_char_pos(span.hi());
+        let byte_start = start.file.original_relative_byte_pos(span.lo()).0;
+        let byte_end = start.file.original_relative_byte_pos(span.hi()).0;
+        Region {
+            start_line: start.line,
+            start_column: start.col.0 + 1,
+            end_line: end.line,
+            end_column: end.col.0 + 1,
+            byte_offset: byte_start,
+            byte_length: byte_end - byte_start,
+        }
+    }
+
+    fn artifact_location(&self, span: Span) -> ArtifactLocation {
+        let file = self.sm.lookup_source_file(span.lo());
+        let name = self.sm.filename_for_diagnostics(&file.name).to_string();
+        ArtifactLocation { uri: path_to_uri(&name) }
+    }
+
+    fn location(&self, span: Span, message: Option<String>) -> Option<Location> {
+        if span.is_dummy() {
+            return None;
+        }
+        Some(Location {
+            physical_location: PhysicalLocation {
+                artifact_location: self.artifact_location(span),
+                region: self.region(span),
+            },
+            message: message.map(Message::plain),
+        })
+    }
+
+    fn span_label_location(&self, label: SpanLabel, args: &FluentArgs<'_>) -> Option<Location> {
+        let message =
+            label.label.as_ref().map(|m| self.translate_message(m, args).unwrap().to_string());
+        self.location(label.span, message)
+    }
+
+    fn fix(&self, sugg: &CodeSuggestion, args: &FluentArgs<'_>) -> Option<Fix> {
+        // Only the first substitution is used, as is done by `rustfix`.
+        let substitution = sugg.substitutions.first()?;
+        let mut changes: FxIndexMap<String, Vec<Replacement>> = FxIndexMap::default();
+        for part in &substitution.parts {
+            if part.span.is_dummy() {
+                return None;
+            }
+            let artifact = self.artifact_location(part.span);
+            changes.entry(artifact.uri).or_default().push(Replacement {
+                deleted_region: self.region(part.span),
+                inserted_content: ArtifactContent { text: part.snippet.clone() },
+            });
+        }
+        Some(Fix {
+            description: Message::plain(
+                self.translate_message(&sugg.msg, args).unwrap().to_string(),
+            ),
+            artifact_changes: changes
+                .into_iter()
+                .map(|(uri, replacements)| ArtifactChange {
+                    artifact_location: ArtifactLocation { uri },
+                    replacements,
+                })
+                .collect(),
+        })
+    }
+
+    fn sub_diagnostic(
+        &self,
+        subdiag: &Subdiag,
+        args: &FluentArgs<'_>,
+        text: &mut String,
+        related_locations: &mut Vec<Location>,
+    ) {
+        let message = self.translate_messages(&subdiag.messages, args).to_string();
+        match subdiag.span.primary_span() {
+            Some(span) if !span.is_dummy() => {
+                let message = format!("{}: {message}", subdiag.level.to_str());
+                related_locations.extend(self.location(span, Some(message)));
+            }
+            _ => {
+                let _ = write!(text, "\n{}: {message}", subdiag.level.to_str());
+            }
+        }
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        let mut early_log = EARLY_LOG.lock().unwrap();
+        if !self.early {
+            early_log.session_emitters -= 1;
+        } else if early_log.session_emitters > 0 || early_log.diagnostics.is_empty() {
+            return;
+        }
+        let early_diagnostics = mem::take(&mut early_log.diagnostics);
+        drop(early_log);
+        self.early = false;
+        for diag in early_diagnostics {
+            self.emit_diagnostic(diag);
+        }
+
+        if let Err(e) = self.write_log()
+            && !std::thread::panicking()
+        {
+            panic!("failed to print SARIF log: {e:?}");
+        }
+    }
+}
+
+impl Translate for SarifEmitter {
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        self.fluent_bundle.as_ref()
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &self.fallback_bundle
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: DiagInner) {
+        if self.early {
+            EARLY_LOG.lock().unwrap().diagnostics.push(diag);
+            return;
+        }
+
+        let level = match diag.level {
+            Level::Bug | Level::Fatal | Level::Error | Level::DelayedBug => "error",
+            Level::ForceWarning(_) | Level::Warning => "warning",
+            Level::Note | Level::OnceNote | Level::Help | Level::OnceHelp => "note",
+            // Failure notes only summarize other diagnostics ("aborting due to...") and carry no
+            // information of their own.
+            Level::FailureNote | Level::Allow | Level::Expect(_) => return,
+        };
+
+        let args = to_fluent_args(diag.args.iter());
+        let mut text = self.translate_messages(&diag.messages, &args).to_string();
+        let mut locations = vec![];
+        let mut related_locations = vec![];
+        for label in diag.span.span_labels() {
+            if label.is_primary {
+                locations.extend(self.span_label_location(label, &args));
+            } else {
+                related_locations.extend(self.span_label_location(label, &args));
+            }
+        }
+        for child in &diag.children {
+            self.sub_diagnostic(child, &args, &mut text, &mut related_locations);
+        }
+
+        let mut fixes = vec![];
+        for sugg in diag.suggestions.iter().flatten() {
+            if sugg.applicability == Applicability::MachineApplicable
+                && let Some(fix) = self.fix(sugg, &args)
+            {
+                fixes.push(fix);
+            } else {
+                let message = self.translate_message(&sugg.msg, &args).unwrap();
+                let _ = write!(text, "\nhelp: {message}");
+            }
+        }
+
+        let (rule_id, rule_index) = self.rule_index(&diag).unzip();
+        self.results.push(SarifResult {
+            rule_id,
+            rule_index,
+            level,
+            message: Message::plain(text),
+            locations,
+            related_locations,
+            fixes,
+        });
+    }
+
+    fn register_lints(&mut self, lints: &[&'static Lint]) {
+        self.lint_descriptions.extend(lints.iter().map(|lint| (lint.name_lower(), lint.desc)));
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn should_show_explain(&self) -> bool {
+        false
+    }
+}
+
+/// Converts a file name into a relative or absolute URI reference, percent-encoding every
+/// character that may not appear in one unescaped.
+fn path_to_uri(path: &str) -> String {
+    let mut uri = String::with_capacity(path.len());
+    for &byte in path.as_bytes() {
+        match byte {
+            b'\\' => uri.push('/'),
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => {
+                let _ = write!(uri, "%{byte:02X}");
+            }
+        }
+    }
+    uri
+}
+
+// The following data types are provided just for serialisation, and follow the names used by
+// the SARIF specification.
+
+#[derive(Serialize)]
+struct SarifLog<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: [Run<'a>; 1],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run<'a> {
+    tool: Tool<'a>,
+    column_kind: &'static str,
+    results: &'a [SarifResult],
+}
+
+#[derive(Serialize)]
+struct Tool<'a> {
+    driver: ToolComponent<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolComponent<'a> {
+    name: &'static str,
+    information_uri: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<&'a str>,
+    rules: Vec<&'a ReportingDescriptor>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportingDescriptor {
+    /// The error code (e.g. "E0308") or lint name.
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    full_description: Option<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    /// "error", "warning" or "note".
+    level: &'static str,
+    /// The primary message, followed by the messages of any children without a span.
+    message: Message,
+    /// The primary spans of the diagnostic.
+    locations: Vec<Location>,
+    /// Secondary spans and the spans of child diagnostics.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<Location>,
+    /// The diagnostic's `MachineApplicable` suggestions.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<Fix>,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    markdown: Option<String>,
+}
+
+impl Message {
+    fn plain(text: String) -> Message {
+        Message { text, markdown: None }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<Message>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    /// 1-based.
+    start_line: usize,
+    /// 1-based, character offset.
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+    byte_offset: u32,
+    byte_length: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Fix {
+    description: Message,
+    artifact_changes: Vec<ArtifactChange>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactChange {
+    artifact_location: ArtifactLocation,
+    replacements: Vec<Replacement>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Replacement {
+    deleted_region: Region,
+    inserted_content: ArtifactContent,
+}
+
+#[derive(Serialize)]
+struct ArtifactContent {
+    text: String,
+}
@@ -0,0 +1,117 @@
+use super::*;
+
+use crate::codes::E0308;
+use crate::test_helpers::{with_source_map, Shared};
+use crate::DiagCtxt;
+use rustc_span::BytePos;
+
+use std::path::Path;
+use std::str;
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+
+/// Runs `f` with a `DiagCtxt` using a `SarifEmitter` over `code`, and returns the parsed log.
+fn emit_sarif(code: &str, f: impl FnOnce(&DiagCtxt)) -> Value {
+    with_source_map(|sm, fallback_bundle| {
+        sm.new_source_file(Path::new("src/my file.rs").to_owned().into(), code.to_owned());
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let se = SarifEmitter::new(Box::new(Shared { data: output.clone() }), sm, fallback_bundle)
+            .registry(Some(Registry::new(&[(E0308, "Expected type did not match.\n\nMore.")])));
+        let dcx = DiagCtxt::new(Box::new(se));
+        f(&dcx);
+        // The log is written when the emitter is dropped.
+        drop(dcx);
+
+        let bytes = output.lock().unwrap();
+        serde_json::from_str(str::from_utf8(&bytes).unwrap()).unwrap()
+    })
+}
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::with_root_ctxt(BytePos(lo), BytePos(hi))
+}
+
+#[test]
+fn empty_log() {
+    let log = emit_sarif("", |_| {});
+    assert_eq!(log["version"], "2.1.0");
+    assert_eq!(log["runs"][0]["tool"]["driver"]["name"], "rustc");
+    assert_eq!(log["runs"][0]["results"], Value::Array(vec![]));
+}
+
+#[test]
+fn error_with_code_and_region() {
+    let log = emit_sarif("fn main() {\n    let x: u8 = 'a';\n}\n", |dcx| {
+        dcx.struct_span_err(span(28, 31), "mismatched types")
+            .with_code(E0308)
+            .with_span_label(span(23, 25), "expected due to this")
+            .with_note("a note")
+            .emit();
+    });
+    let run = &log["runs"][0];
+    let rules = &run["tool"]["driver"]["rules"];
+    assert_eq!(rules[0]["id"], "E0308");
+    assert_eq!(rules[0]["fullDescription"]["text"], "Expected type did not match.");
+    assert_eq!(rules[0]["helpUri"], "https://doc.rust-lang.org/error_codes/E0308.html");
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    let result = &results[0];
+    assert_eq!(result["ruleId"], "E0308");
+    assert_eq!(result["ruleIndex"], 0);
+    assert_eq!(result["level"], "error");
+    assert_eq!(result["message"]["text"], "mismatched types\nnote: a note");
+
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "src/my%20file.rs");
+    assert_eq!(location["region"]["startLine"], 2);
+    assert_eq!(location["region"]["startColumn"], 17);
+    assert_eq!(location["region"]["endColumn"], 20);
+    assert_eq!(location["region"]["byteOffset"], 28);
+    assert_eq!(location["region"]["byteLength"], 3);
+
+    let related = &result["relatedLocations"][0];
+    assert_eq!(related["message"]["text"], "expected due to this");
+    assert_eq!(related["physicalLocation"]["region"]["startColumn"], 12);
+}
+
+#[test]
+fn machine_applicable_fixes() {
+    static UNUSED_MUT: Lint = Lint {
+        name: "UNUSED_MUT",
+        desc: "detect mut variables which don't need to be mutable",
+        ..Lint::default_fields_for_macro()
+    };
+    let log = emit_sarif("let mut x = 1;\n", |dcx| {
+        dcx.register_lints(&[&UNUSED_MUT]);
+        let mut diag = dcx.struct_span_warn(span(4, 9), "variable does not need to be mutable");
+        diag.is_lint("unused_mut".to_owned(), false);
+        diag.span_suggestion(span(4, 8), "remove this `mut`", "", Applicability::MachineApplicable);
+        diag.span_suggestion(span(8, 9), "rename it", "y", Applicability::MaybeIncorrect);
+        diag.emit();
+    });
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "unused_mut");
+    assert_eq!(
+        run["tool"]["driver"]["rules"][0]["fullDescription"]["text"],
+        "detect mut variables which don't need to be mutable"
+    );
+
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], "unused_mut");
+    assert_eq!(result["level"], "warning");
+    // Suggestions that are not machine-applicable are only mentioned in the message.
+    assert_eq!(result["message"]["text"], "variable does not need to be mutable\nhelp: rename it");
+
+    let fixes = result["fixes"].as_array().unwrap();
+    assert_eq!(fixes.len(), 1);
+    assert_eq!(fixes[0]["description"]["text"], "remove this `mut`");
+    let change = &fixes[0]["artifactChanges"][0];
+    assert_eq!(change["artifactLocation"]["uri"], "src/my%20file.rs");
+    let replacement = &change["replacements"][0];
+    assert_eq!(replacement["deletedRegion"]["byteOffset"], 4);
+    assert_eq!(replacement["deletedRegion"]["byteLength"], 4);
+    assert_eq!(replacement["insertedContent"]["text"], "");
+}
@@ -0,0 +1,33 @@
+//! Helpers shared by the tests of the emitters writing machine-readable output.
+
+use crate::LazyFallbackBundle;
+use rustc_data_structures::sync::Lrc;
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+/// A writer appending to a buffer which the test reads once the emitter is done with it.
+pub(crate) struct Shared<T> {
+    pub(crate) data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+/// Runs `f` within fresh session globals, with an empty `SourceMap` and the fallback Fluent bundle
+/// to create an emitter with.
+pub(crate) fn with_source_map<R>(f: impl FnOnce(Lrc<SourceMap>, LazyFallbackBundle) -> R) -> R {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(vec![crate::DEFAULT_LOCALE_RESOURCE], false);
+        f(sm, fallback_bundle)
+    })
+}
@@ -94,11 +94,15 @@ fn translate_message<'a>(
                 Some(Ok(t)) => t,
 
                 // If `translate_with_bundle` returns `Err` with the primary bundle, this is likely
-                // just that the primary bundle doesn't contain the message being translated, so
-                // proceed to the fallback bundle.
+                // just that the primary bundle doesn't contain the message being translated, or
+                // only translates some of its attributes, so proceed to the fallback bundle.
                 Some(Err(
                     primary @ TranslateError::One {
-                        kind: TranslateErrorKind::MessageMissing, ..
+                        kind:
+                            TranslateErrorKind::MessageMissing
+                            | TranslateErrorKind::AttributeMissing { .. }
+                            | TranslateErrorKind::ValueMissing,
+                        ..
                     },
                 )) => translate_with_bundle(self.fallback_fluent_bundle())
                     .map_err(|fallback| primary.and(fallback))?,
@@ -438,6 +438,10 @@ pub struct BuiltinAttribute {
         WarnFollowing, EncodeCrossCrate::No,
         coverage_attribute, experimental!(coverage)
     ),
+    gated!(
+        codegen_unit, Normal, template!(NameValueStr: "name"), ErrorFollowing,
+        EncodeCrossCrate::No, codegen_unit_hints, experimental!(codegen_unit)
+    ),
 
     ungated!(
         doc, Normal, template!(List: "hidden|inline|...", NameValueStr: "string"), DuplicatesOk,
@@ -482,7 +486,7 @@ pub struct BuiltinAttribute {
     ),
     // RFC 2412
     gated!(
-        optimize, Normal, template!(List: "size|speed"), ErrorPreceding,
+        optimize, Normal, template!(List: r#"size|speed|level = "0|1|2|3|s|z""#), ErrorPreceding,
         EncodeCrossCrate::No, optimize_attribute, experimental!(optimize)
     ),
 
@@ -35,6 +35,13 @@ pub struct Feature {
     issue: Option<NonZero<u32>>,
 }
 
+impl Feature {
+    /// The number of the tracking issue of the feature, if it has one.
+    pub fn issue(&self) -> Option<NonZero<u32>> {
+        self.issue
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum Stability {
     Unstable,
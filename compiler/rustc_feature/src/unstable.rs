@@ -395,6 +395,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, closure_track_caller, "1.57.0", Some(87417)),
     /// Allows to use the `#[cmse_nonsecure_entry]` attribute.
     (unstable, cmse_nonsecure_entry, "1.48.0", Some(75835)),
+    /// Allows pinning items to named codegen units with `#[codegen_unit = "name"]`.
+    (unstable, codegen_unit_hints, "CURRENT_RUSTC_VERSION", None),
     /// Allows use of the `#[collapse_debuginfo]` attribute.
     (unstable, collapse_debuginfo, "1.65.0", Some(100758)),
     /// Allows `async {}` expressions in const contexts.
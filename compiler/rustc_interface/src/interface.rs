@@ -9,7 +9,7 @@
 use rustc_data_structures::stable_hasher::StableHasher;
 use rustc_data_structures::sync::Lrc;
 use rustc_errors::registry::Registry;
-use rustc_errors::{DiagCtxt, ErrorGuaranteed};
+use rustc_errors::{DiagCtxt, ErrorGuaranteed, SkippedResource};
 use rustc_lint::LintStore;
 
 use rustc_middle::ty;
@@ -389,7 +389,15 @@ pub fn run_compiler<R: Send>(config: Config, f: impl FnOnce(&Compiler) -> R + Se
                 config.opts.unstable_opts.translate_additional_ftl.as_deref(),
                 config.opts.unstable_opts.translate_directionality_markers,
             ) {
-                Ok(bundle) => bundle,
+                Ok((bundle, skipped)) => {
+                    for SkippedResource { path, error } in skipped {
+                        early_dcx.early_warn(format!(
+                            "skipped translations of `{}`: {error}",
+                            path.display()
+                        ));
+                    }
+                    bundle
+                }
                 Err(e) => early_dcx.early_fatal(format!("failed to load fluent bundle: {e}")),
             };
 
@@ -446,6 +454,7 @@ pub fn run_compiler<R: Send>(config: Config, f: impl FnOnce(&Compiler) -> R + Se
                 register_lints(&sess, &mut lint_store);
                 sess.registered_lints = true;
             }
+            sess.dcx().register_lints(lint_store.get_lints());
             sess.lint_store = Some(Lrc::new(lint_store));
 
             let compiler = Compiler {
@@ -25,13 +25,15 @@
 use rustc_passes::{abi_test, hir_stats, layout_test};
 use rustc_resolve::Resolver;
 use rustc_session::code_stats::VTableSizeInfo;
-use rustc_session::config::{CrateType, Input, OutFileName, OutputFilenames, OutputType};
+use rustc_session::config::{
+    CrateType, DepInfoFormat, Input, OutFileName, OutputFilenames, OutputType,
+};
 use rustc_session::cstore::Untracked;
 use rustc_session::output::filename_for_input;
 use rustc_session::search_paths::PathKind;
 use rustc_session::{Limit, Session};
 use rustc_span::symbol::{sym, Symbol};
-use rustc_span::FileName;
+use rustc_span::{FileName, SourceFileHash, SourceFileHashAlgorithm};
 use rustc_target::spec::PanicStrategy;
 use rustc_trait_selection::traits;
 
@@ -475,6 +477,10 @@ fn write_out_deps(tcx: TyCtxt<'_>, outputs: &OutputFilenames, out_filenames: &[P
         }
 
         let write_deps_to_file = |file: &mut dyn Write| -> io::Result<()> {
+            if sess.opts.unstable_opts.dep_info_format == DepInfoFormat::Json {
+                return write_json_deps(tcx, out_filenames, file);
+            }
+
             for path in out_filenames {
                 writeln!(file, "{}: {}\n", path.display(), files.join(" "))?;
             }
@@ -533,6 +539,109 @@ fn write_out_deps(tcx: TyCtxt<'_>, outputs: &OutputFilenames, out_filenames: &[P
     }
 }
 
+/// Writes the dep-info file in the format of `-Zdep-info-format=json`: a JSON object listing the
+/// outputs, the input files with their content hash, the environment variables that were read,
+/// and the extern crates with their SVH.
+fn write_json_deps(
+    tcx: TyCtxt<'_>,
+    out_filenames: &[PathBuf],
+    file: &mut dyn Write,
+) -> io::Result<()> {
+    let sess = tcx.sess;
+    let hash_kind = sess.opts.unstable_opts.src_hash_algorithm(&sess.target);
+    let hash_to_string = |hash: SourceFileHash| {
+        let kind = match hash.kind {
+            SourceFileHashAlgorithm::Md5 => "md5",
+            SourceFileHashAlgorithm::Sha1 => "sha1",
+            SourceFileHashAlgorithm::Sha256 => "sha256",
+        };
+        let hex: String = hash.hash_bytes().iter().map(|byte| format!("{byte:02x}")).collect();
+        format!("{kind}:{hex}")
+    };
+    let mut files = Vec::new();
+    let mut push_file = |kind: &str, path: &Path, hash: Option<SourceFileHash>| {
+        files.push(serde_json::json!({
+            "path": FileName::from(path.to_path_buf()).prefer_local().to_string(),
+            "kind": kind,
+            "hash": hash.map(hash_to_string),
+        }));
+    };
+    // The files which aren't in the source map are hashed the same way as source files.
+    let hash_file = |path: &Path| {
+        fs::read(path).ok().map(|bytes| SourceFileHash::new_from_bytes(hash_kind, &bytes))
+    };
+
+    for source_file in sess.source_map().files().iter() {
+        if let FileName::Real(name) = &source_file.name
+            && let Some(path) = name.local_path()
+            && !source_file.is_imported()
+        {
+            push_file("source", path, Some(source_file.src_hash));
+        }
+    }
+    // Sorted, so that the output doesn't depend on the order the files were accessed in.
+    #[allow(rustc::potential_query_instability)]
+    let mut tracked_files: Vec<_> = sess.psess.file_depinfo.borrow().iter().copied().collect();
+    tracked_files.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    for path in tracked_files {
+        let path = Path::new(path.as_str());
+        push_file("tracked", path, hash_file(path));
+    }
+    if let Some(path) = &sess.opts.cg.profile_use {
+        push_file("profile", path, hash_file(path));
+    }
+    if let Some(path) = &sess.opts.unstable_opts.profile_sample_use {
+        push_file("profile", path, hash_file(path));
+    }
+    for debugger_visualizer in tcx.debugger_visualizers(LOCAL_CRATE) {
+        let path = debugger_visualizer.path.as_ref().unwrap();
+        let hash = SourceFileHash::new_from_bytes(hash_kind, &debugger_visualizer.src);
+        push_file("debugger-visualizer", path, Some(hash));
+    }
+    if let Some(backend) = &sess.opts.unstable_opts.codegen_backend
+        && backend.contains('.')
+    {
+        let path = Path::new(backend);
+        push_file("codegen-backend", path, hash_file(path));
+    }
+
+    #[allow(rustc::potential_query_instability)]
+    let mut env: Vec<_> = sess.psess.env_depinfo.borrow().iter().copied().collect();
+    env.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+    let env: Vec<_> = env
+        .into_iter()
+        .map(|(name, value)| {
+            serde_json::json!({ "name": name.as_str(), "value": value.map(|v| v.to_string()) })
+        })
+        .collect();
+
+    let externs: Vec<_> = tcx
+        .crates(())
+        .iter()
+        .map(|&cnum| {
+            let source = tcx.used_crate_source(cnum);
+            let paths: Vec<_> = [&source.dylib, &source.rlib, &source.rmeta]
+                .into_iter()
+                .filter_map(|path| Some(path.as_ref()?.0.display().to_string()))
+                .collect();
+            serde_json::json!({
+                "name": tcx.crate_name(cnum).as_str(),
+                "svh": tcx.crate_hash(cnum).to_string(),
+                "paths": paths,
+            })
+        })
+        .collect();
+
+    let outputs: Vec<_> = out_filenames.iter().map(|path| path.display().to_string()).collect();
+    let deps = serde_json::json!({
+        "outputs": outputs,
+        "files": files,
+        "env": env,
+        "externs": externs,
+    });
+    writeln!(file, "{}", serde_json::to_string_pretty(&deps).unwrap())
+}
+
 fn resolver_for_lowering_raw<'tcx>(
     tcx: TyCtxt<'tcx>,
     (): (),
@@ -90,11 +90,14 @@ fn visit_param(&mut self, param: &'a ast::Param) {
     }
 
     fn visit_item(&mut self, it: &'a ast::Item) {
+        let is_mod = matches!(it.kind, ast::ItemKind::Mod(..));
+        let push = self.context.builder.enter_item(it.ident.name, is_mod);
         self.with_lint_attrs(it.id, &it.attrs, |cx| {
             lint_callback!(cx, check_item, it);
             ast_visit::walk_item(cx, it);
             lint_callback!(cx, check_item_post, it);
-        })
+        });
+        self.context.builder.exit_item(push);
     }
 
     fn visit_foreign_item(&mut self, it: &'a ast::ForeignItem) {
@@ -21,6 +21,7 @@ pub enum OverruledAttributeSub {
     DefaultSource { id: String },
     NodeSource { span: Span, reason: Option<Symbol> },
     CommandLineSource,
+    LintConfigSource,
 }
 
 impl Subdiagnostic for OverruledAttributeSub {
@@ -44,6 +45,9 @@ fn add_to_diag_with<G: EmissionGuarantee, F: SubdiagMessageOp<G>>(
             OverruledAttributeSub::CommandLineSource => {
                 diag.note(fluent::lint_command_line_source);
             }
+            OverruledAttributeSub::LintConfigSource => {
+                diag.note(fluent::lint_lint_config_source);
+            }
         }
     }
 }
@@ -89,10 +93,11 @@ pub struct BuiltinEllipsisInclusiveRangePatterns {
 }
 
 #[derive(Subdiagnostic)]
-#[note(lint_requested_level)]
-pub struct RequestedLevel<'a> {
-    pub level: Level,
-    pub lint_name: &'a str,
+pub enum RequestedLevel<'a> {
+    #[note(lint_requested_level)]
+    CommandLine { level: Level, lint_name: &'a str },
+    #[note(lint_requested_level_in_lint_config)]
+    LintConfig { level: &'static str, lint_name: &'a str },
 }
 
 #[derive(Diagnostic)]
@@ -19,6 +19,7 @@
 use rustc_errors::{Diag, DiagMessage, LintDiagnostic, MultiSpan};
 use rustc_feature::{Features, GateIssue};
 use rustc_hir as hir;
+use rustc_hir::def::DefKind;
 use rustc_hir::intravisit::{self, Visitor};
 use rustc_hir::HirId;
 use rustc_index::IndexVec;
@@ -173,6 +174,9 @@ fn shallow_lint_levels_on(tcx: TyCtxt<'_>, owner: hir::OwnerId) -> ShallowLintLe
     if owner == hir::CRATE_OWNER_ID {
         levels.add_command_line();
     }
+    if let Some(module_path) = lint_config_module_path(tcx, owner) {
+        levels.add_lint_config_module(&module_path);
+    }
 
     match attrs.map.range(..) {
         // There is only something to do if there are attributes at all.
@@ -205,9 +209,23 @@ fn shallow_lint_levels_on(tcx: TyCtxt<'_>, owner: hir::OwnerId) -> ShallowLintLe
     specs
 }
 
+/// Returns the path of `owner` relative to the crate root if it's a module and the
+/// `--lint-config` file has module tables, so that they can be matched against it.
+fn lint_config_module_path(tcx: TyCtxt<'_>, owner: hir::OwnerId) -> Option<Vec<Symbol>> {
+    if tcx.sess.opts.lint_config.as_ref().map_or(true, |config| config.modules.is_empty())
+        || tcx.def_kind(owner.def_id) != DefKind::Mod
+    {
+        return None;
+    }
+    tcx.def_path(owner.to_def_id()).data.iter().map(|data| data.data.get_opt_name()).collect()
+}
+
 pub struct TopDown {
     sets: LintLevelSets,
     cur: LintStackIndex,
+    /// The names of the items being visited, to match the modules against the `--lint-config`
+    /// file.
+    item_path: Vec<Symbol>,
 }
 
 pub trait LintLevelsProvider {
@@ -473,7 +491,11 @@ pub(crate) fn new(
         let mut builder = LintLevelsBuilder {
             sess,
             features,
-            provider: TopDown { sets: LintLevelSets::new(), cur: COMMAND_LINE },
+            provider: TopDown {
+                sets: LintLevelSets::new(),
+                cur: COMMAND_LINE,
+                item_path: Vec::new(),
+            },
             lint_added_lints,
             store,
             registered_tools,
@@ -490,6 +512,40 @@ fn process_command_line(&mut self) {
             .list
             .push(LintSet { specs: FxIndexMap::default(), parent: COMMAND_LINE });
         self.add_command_line();
+        self.add_lint_config_module(&[]);
+    }
+
+    /// Enters the item named `name`, applying the levels of the `--lint-config` file if it is a
+    /// module. This must be called before `push`ing the attributes of the item, so that they can
+    /// override these levels.
+    ///
+    /// Don't forget to call `exit_item`!
+    pub(crate) fn enter_item(&mut self, name: Symbol, is_mod: bool) -> BuilderPush {
+        let prev = self.provider.cur;
+        self.provider.item_path.push(name);
+        if is_mod {
+            self.provider.cur = self
+                .provider
+                .sets
+                .list
+                .push(LintSet { specs: FxIndexMap::default(), parent: prev });
+
+            let item_path = std::mem::take(&mut self.provider.item_path);
+            self.add_lint_config_module(&item_path);
+            self.provider.item_path = item_path;
+
+            if self.provider.current_specs().is_empty() {
+                self.provider.sets.list.pop();
+                self.provider.cur = prev;
+            }
+        }
+        BuilderPush { prev }
+    }
+
+    /// Called after `enter_item` when the item is exited.
+    pub(crate) fn exit_item(&mut self, push: BuilderPush) {
+        self.provider.item_path.pop();
+        self.pop(push);
     }
 
     /// Pushes a list of AST lint attributes onto this context.
@@ -558,72 +614,128 @@ fn insert(&mut self, id: LintId, lvl: LevelAndSource) {
     }
 
     fn add_command_line(&mut self) {
-        for &(ref lint_name, level) in &self.sess.opts.lint_opts {
-            // Checks the validity of lint names derived from the command line.
-            let (tool_name, lint_name_only) = parse_lint_and_tool_name(lint_name);
-            if lint_name_only == crate::WARNINGS.name_lower()
-                && matches!(level, Level::ForceWarn(_))
-            {
-                self.sess
-                    .dcx()
-                    .emit_err(UnsupportedGroup { lint_group: crate::WARNINGS.name_lower() });
+        let sess = self.sess;
+        if let Some(lint_config) = &sess.opts.lint_config {
+            // The names of the module tables are only checked here, at the crate root.
+            for (lint_name, level) in lint_config.modules.iter().flat_map(|module| &module.levels) {
+                let requested_level =
+                    RequestedLevel::LintConfig { level: level.as_str(), lint_name };
+                self.check_command_line_lint_name(lint_name, *level, requested_level);
             }
-            match self.store.check_lint_name(lint_name_only, tool_name, self.registered_tools) {
-                CheckLintNameResult::Renamed(ref replace) => {
-                    let name = lint_name.as_str();
-                    let suggestion = RenamedLintSuggestion::WithoutSpan { replace };
-                    let requested_level = RequestedLevel { level, lint_name };
-                    let lint = RenamedLintFromCommandLine { name, suggestion, requested_level };
-                    self.emit_lint(RENAMED_AND_REMOVED_LINTS, lint);
-                }
-                CheckLintNameResult::Removed(ref reason) => {
-                    let name = lint_name.as_str();
-                    let requested_level = RequestedLevel { level, lint_name };
-                    let lint = RemovedLintFromCommandLine { name, reason, requested_level };
-                    self.emit_lint(RENAMED_AND_REMOVED_LINTS, lint);
-                }
-                CheckLintNameResult::NoLint(suggestion) => {
-                    let name = lint_name.clone();
-                    let suggestion = suggestion.map(|(replace, from_rustc)| {
-                        UnknownLintSuggestion::WithoutSpan { replace, from_rustc }
-                    });
-                    let requested_level = RequestedLevel { level, lint_name };
-                    let lint = UnknownLintFromCommandLine { name, suggestion, requested_level };
-                    self.emit_lint(UNKNOWN_LINTS, lint);
-                }
-                CheckLintNameResult::Tool(Err((Some(_), ref replace))) => {
-                    let name = lint_name.clone();
-                    let requested_level = RequestedLevel { level, lint_name };
-                    let lint = DeprecatedLintNameFromCommandLine { name, replace, requested_level };
-                    self.emit_lint(RENAMED_AND_REMOVED_LINTS, lint);
-                }
-                CheckLintNameResult::NoTool => {
-                    self.sess.dcx().emit_err(CheckNameUnknownTool {
-                        tool_name: tool_name.unwrap(),
-                        sub: RequestedLevel { level, lint_name },
-                    });
-                }
-                _ => {}
-            };
+            for (lint_name, level) in &lint_config.levels {
+                let requested_level =
+                    RequestedLevel::LintConfig { level: level.as_str(), lint_name };
+                self.check_command_line_lint_name(lint_name, *level, requested_level);
+                let src = LintLevelSource::LintConfig {
+                    name: Symbol::intern(lint_name),
+                    level: *level,
+                    pattern: None,
+                };
+                self.insert_command_line_level(lint_name, *level, src);
+            }
+        }
+
+        for &(ref lint_name, level) in &sess.opts.lint_opts {
+            let requested_level = RequestedLevel::CommandLine { level, lint_name };
+            self.check_command_line_lint_name(lint_name, level, requested_level);
+            let src = LintLevelSource::CommandLine(Symbol::intern(lint_name), level);
+            self.insert_command_line_level(lint_name, level, src);
+        }
+    }
+
+    /// Checks the validity of a lint name given on the command line or in the `--lint-config`
+    /// file.
+    fn check_command_line_lint_name(
+        &self,
+        lint_name: &str,
+        level: Level,
+        requested_level: RequestedLevel<'_>,
+    ) {
+        let (tool_name, lint_name_only) = parse_lint_and_tool_name(lint_name);
+        if lint_name_only == crate::WARNINGS.name_lower() && matches!(level, Level::ForceWarn(_)) {
+            self.sess.dcx().emit_err(UnsupportedGroup { lint_group: crate::WARNINGS.name_lower() });
+        }
+        match self.store.check_lint_name(lint_name_only, tool_name, self.registered_tools) {
+            CheckLintNameResult::Renamed(ref replace) => {
+                let suggestion = RenamedLintSuggestion::WithoutSpan { replace };
+                let lint =
+                    RenamedLintFromCommandLine { name: lint_name, suggestion, requested_level };
+                self.emit_lint(RENAMED_AND_REMOVED_LINTS, lint);
+            }
+            CheckLintNameResult::Removed(ref reason) => {
+                let lint = RemovedLintFromCommandLine { name: lint_name, reason, requested_level };
+                self.emit_lint(RENAMED_AND_REMOVED_LINTS, lint);
+            }
+            CheckLintNameResult::NoLint(suggestion) => {
+                let name = lint_name.to_owned();
+                let suggestion = suggestion.map(|(replace, from_rustc)| {
+                    UnknownLintSuggestion::WithoutSpan { replace, from_rustc }
+                });
+                let lint = UnknownLintFromCommandLine { name, suggestion, requested_level };
+                self.emit_lint(UNKNOWN_LINTS, lint);
+            }
+            CheckLintNameResult::Tool(Err((Some(_), ref replace))) => {
+                let name = lint_name.to_owned();
+                let lint = DeprecatedLintNameFromCommandLine { name, replace, requested_level };
+                self.emit_lint(RENAMED_AND_REMOVED_LINTS, lint);
+            }
+            CheckLintNameResult::NoTool => {
+                self.sess.dcx().emit_err(CheckNameUnknownTool {
+                    tool_name: tool_name.unwrap(),
+                    sub: requested_level,
+                });
+            }
+            _ => {}
+        };
+    }
 
-            let orig_level = level;
-            let lint_flag_val = Symbol::intern(lint_name);
+    /// Sets the level of the lints named `lint_name`, from the command line or the `[lints]`
+    /// table of the `--lint-config` file.
+    fn insert_command_line_level(&mut self, lint_name: &str, level: Level, src: LintLevelSource) {
+        let Ok(ids) = self.store.find_lints(lint_name) else {
+            // errors already handled above
+            return;
+        };
+        for id in ids {
+            // ForceWarn and Forbid cannot be overridden
+            if let Some((Level::ForceWarn(_) | Level::Forbid, _)) = self.current_specs().get(&id) {
+                continue;
+            }
 
-            let Ok(ids) = self.store.find_lints(lint_name) else {
-                // errors already handled above
+            if self.check_gated_lint(id, DUMMY_SP, true) {
+                self.insert(id, (level, src));
+            }
+        }
+    }
+
+    /// Sets the levels of the `[modules."PATTERN"]` tables of the `--lint-config` file matching
+    /// the module at `module_path`, relative to the crate root. This must be done before adding
+    /// the attributes of the module, so that they can override these levels.
+    fn add_lint_config_module(&mut self, module_path: &[Symbol]) {
+        let sess = self.sess;
+        let Some(lint_config) = &sess.opts.lint_config else { return };
+        for module in &lint_config.modules {
+            if !module.matches(module_path) {
                 continue;
-            };
-            for id in ids {
-                // ForceWarn and Forbid cannot be overridden
-                if let Some((Level::ForceWarn(_) | Level::Forbid, _)) =
-                    self.current_specs().get(&id)
-                {
-                    continue;
-                }
+            }
+            let pattern = Symbol::intern(&module.pattern);
+            for &(ref lint_name, level) in &module.levels {
+                // Invalid names were reported at the crate root.
+                let Ok(ids) = self.store.find_lints(lint_name) else { continue };
+                let src = LintLevelSource::LintConfig {
+                    name: Symbol::intern(lint_name),
+                    level,
+                    pattern: Some(pattern),
+                };
+                for id in ids {
+                    // ForceWarn and Forbid cannot be overridden
+                    if let (Level::ForceWarn(_) | Level::Forbid, _) = self.lint_level(id.lint) {
+                        continue;
+                    }
 
-                if self.check_gated_lint(id, DUMMY_SP, true) {
-                    let src = LintLevelSource::CommandLine(lint_flag_val, orig_level);
-                    self.insert(id, (level, src));
+                    if self.check_gated_lint(id, DUMMY_SP, true) {
+                        self.insert(id, (level, src));
+                    }
                 }
             }
         }
@@ -657,6 +769,7 @@ fn insert_spec(&mut self, id: LintId, (mut level, src): LevelAndSource) {
                 LintLevelSource::Default => false,
                 LintLevelSource::Node { name, .. } => self.store.is_lint_group(name),
                 LintLevelSource::CommandLine(symbol, _) => self.store.is_lint_group(symbol),
+                LintLevelSource::LintConfig { name, .. } => self.store.is_lint_group(name),
             };
             debug!(
                 "fcw_warning={:?}, specs.get(&id) = {:?}, old_src={:?}, id_name={:?}",
@@ -673,6 +786,7 @@ fn insert_spec(&mut self, id: LintId, (mut level, src): LevelAndSource) {
                     OverruledAttributeSub::NodeSource { span, reason }
                 }
                 LintLevelSource::CommandLine(_, _) => OverruledAttributeSub::CommandLineSource,
+                LintLevelSource::LintConfig { .. } => OverruledAttributeSub::LintConfigSource,
             };
             if !fcw_warning {
                 self.sess.dcx().emit_err(OverruledAttribute {
@@ -889,6 +1003,7 @@ fn add(&mut self, attrs: &[ast::Attribute], is_crate_node: bool, source_hir_id:
                                 .name,
                             span: sp,
                             reason,
+                            level,
                         };
                         for &id in *ids {
                             if self.check_gated_lint(id, attr.span, false) {
@@ -906,6 +1021,7 @@ fn add(&mut self, attrs: &[ast::Attribute], is_crate_node: bool, source_hir_id:
                                     name: Symbol::intern(complete_name),
                                     span: sp,
                                     reason,
+                                    level,
                                 };
                                 for &id in ids {
                                     if self.check_gated_lint(id, attr.span, false) {
@@ -935,6 +1051,7 @@ fn add(&mut self, attrs: &[ast::Attribute], is_crate_node: bool, source_hir_id:
                                     name: Symbol::intern(new_lint_name),
                                     span: sp,
                                     reason,
+                                    level,
                                 };
                                 for id in ids {
                                     self.insert_spec(*id, (level, src));
@@ -1002,8 +1119,12 @@ fn add(&mut self, attrs: &[ast::Attribute], is_crate_node: bool, source_hir_id:
                         panic!("renamed lint does not exist: {new_name}");
                     };
 
-                    let src =
-                        LintLevelSource::Node { name: Symbol::intern(&new_name), span: sp, reason };
+                    let src = LintLevelSource::Node {
+                        name: Symbol::intern(&new_name),
+                        span: sp,
+                        reason,
+                        level,
+                    };
                     for &id in ids {
                         if self.check_gated_lint(id, attr.span, false) {
                             self.insert_spec(id, (level, src));
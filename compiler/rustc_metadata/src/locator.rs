@@ -216,18 +216,19 @@
 use crate::errors;
 use crate::rmeta::{rustc_version, MetadataBlob, METADATA_HEADER};
 
-use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
 use rustc_data_structures::memmap::Mmap;
-use rustc_data_structures::owned_slice::slice_owned;
+use rustc_data_structures::owned_slice::{slice_owned, OwnedSlice};
 use rustc_data_structures::svh::Svh;
 use rustc_errors::{DiagArgValue, IntoDiagArg};
 use rustc_fs_util::try_canonicalize;
 use rustc_session::config;
-use rustc_session::cstore::CrateSource;
+use rustc_session::cstore::{CrateDepKind, CrateSource};
 use rustc_session::filesearch::FileSearch;
 use rustc_session::search_paths::PathKind;
 use rustc_session::utils::CanonicalizedPath;
 use rustc_session::Session;
+use rustc_span::edition::Edition;
 use rustc_span::symbol::Symbol;
 use rustc_span::Span;
 use rustc_target::spec::{Target, TargetTriple};
@@ -235,8 +236,11 @@
 use snap::read::FrameDecoder;
 use std::borrow::Cow;
 use std::io::{Read, Result as IoResult, Write};
+use std::iter;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
 use std::{cmp, fmt};
 
 #[derive(Clone)]
@@ -273,7 +277,7 @@ pub(crate) fn new(name: Symbol, source: CrateSource) -> CratePaths {
     }
 }
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub(crate) enum CrateFlavor {
     Rlib,
     Rmeta,
@@ -781,7 +785,121 @@ fn get_metadata_section<'p>(
     if !filename.exists() {
         return Err(MetadataError::NotPresent(filename));
     }
-    let raw_bytes = match flavor {
+    let raw_bytes = match METADATA_BLOB_CACHE.get() {
+        Some(cache) => cached_metadata_bytes(cache, target, flavor, filename, loader)?,
+        None => load_metadata_bytes(target, flavor, filename, loader)?,
+    };
+    let blob = MetadataBlob(raw_bytes);
+    match blob.check_compatibility(cfg_version) {
+        Ok(()) => Ok(blob),
+        Err(None) => Err(MetadataError::LoadFailure(format!(
+            "invalid metadata version found: {}",
+            filename.display()
+        ))),
+        Err(Some(found_version)) => {
+            return Err(MetadataError::VersionMismatch {
+                expected_version: rustc_version(cfg_version),
+                found_version,
+            });
+        }
+    }
+}
+
+/// The metadata of the crates loaded by earlier sessions, when several sessions are run in the
+/// same process, as `rustc --server` does. See [`enable_metadata_blob_cache`].
+static METADATA_BLOB_CACHE: OnceLock<Mutex<MetadataBlobCache>> = OnceLock::new();
+
+/// The maximum number of crate files whose metadata is kept by [`METADATA_BLOB_CACHE`].
+const METADATA_BLOB_CACHE_CAPACITY: usize = 256;
+
+/// The cached metadata, keyed by the path and flavor of the crate file it was read from, and the
+/// modification time and length of the file, to tell whether it has been rebuilt since. The least
+/// recently used entries come first.
+type MetadataBlobCache = FxIndexMap<(PathBuf, CrateFlavor, SystemTime, u64), Arc<CachedMetadata>>;
+
+enum CachedMetadata {
+    /// The mapped `.rmeta` file, which is the metadata as is. Crate files are replaced rather
+    /// than overwritten when they are rebuilt, so the mapping keeps the earlier build.
+    Mapped(Mmap),
+    /// The metadata extracted from an rlib or a dylib, decompressed for the latter.
+    Extracted(Vec<u8>),
+}
+
+impl Deref for CachedMetadata {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            CachedMetadata::Mapped(mmap) => mmap,
+            CachedMetadata::Extracted(bytes) => bytes,
+        }
+    }
+}
+
+/// Keeps the metadata of the crates loaded by a session for the following sessions run in this
+/// process, so that they don't need to read and decompress it again. The metadata of a crate is
+/// read again once its file has changed, and only the most recently used crate files are kept.
+///
+/// Only the raw metadata is kept: decoding it still happens in each session, as the decoded
+/// metadata refers to the session's crate numbers and interned symbols.
+pub fn enable_metadata_blob_cache() {
+    METADATA_BLOB_CACHE.get_or_init(Default::default);
+}
+
+fn cached_metadata_bytes<'p>(
+    cache: &Mutex<MetadataBlobCache>,
+    target: &Target,
+    flavor: CrateFlavor,
+    filename: &'p Path,
+    loader: &dyn MetadataLoader,
+) -> Result<OwnedSlice, MetadataError<'p>> {
+    let Some((modified, len)) = std::fs::metadata(filename)
+        .and_then(|metadata| Ok((metadata.modified()?, metadata.len())))
+        .ok()
+    else {
+        return load_metadata_bytes(target, flavor, filename, loader);
+    };
+    let key = (filename.to_path_buf(), flavor, modified, len);
+    let cached = {
+        let mut cache = cache.lock().unwrap();
+        // Move the entry to the back, so that it is evicted last.
+        let cached = cache.shift_remove(&key);
+        if let Some(cached) = &cached {
+            cache.insert(key.clone(), Arc::clone(cached));
+        }
+        cached
+    };
+    let cached = match cached {
+        Some(cached) => cached,
+        None => {
+            let cached = Arc::new(match flavor {
+                CrateFlavor::Rmeta => CachedMetadata::Mapped(map_rmeta_file(filename)?),
+                CrateFlavor::Rlib | CrateFlavor::Dylib => CachedMetadata::Extracted(
+                    load_metadata_bytes(target, flavor, filename, loader)?.to_vec(),
+                ),
+            });
+            let mut cache = cache.lock().unwrap();
+            // The earlier builds of the file won't be used anymore.
+            cache.retain(|(path, cached_flavor, ..), _| {
+                (path.as_path(), *cached_flavor) != (filename, flavor)
+            });
+            cache.insert(key, Arc::clone(&cached));
+            if cache.len() > METADATA_BLOB_CACHE_CAPACITY {
+                cache.shift_remove_index(0);
+            }
+            cached
+        }
+    };
+    Ok(slice_owned(cached, |cached| &cached[..]))
+}
+
+fn load_metadata_bytes<'p>(
+    target: &Target,
+    flavor: CrateFlavor,
+    filename: &'p Path,
+    loader: &dyn MetadataLoader,
+) -> Result<OwnedSlice, MetadataError<'p>> {
+    Ok(match flavor {
         CrateFlavor::Rlib => {
             loader.get_rlib_metadata(target, filename).map_err(MetadataError::LoadFailure)?
         }
@@ -834,39 +952,25 @@ fn get_metadata_section<'p>(
                 slice_owned(inflated, Deref::deref)
             }
         }
-        CrateFlavor::Rmeta => {
-            // mmap the file, because only a small fraction of it is read.
-            let file = std::fs::File::open(filename).map_err(|_| {
-                MetadataError::LoadFailure(format!(
-                    "failed to open rmeta metadata: '{}'",
-                    filename.display()
-                ))
-            })?;
-            let mmap = unsafe { Mmap::map(file) };
-            let mmap = mmap.map_err(|_| {
-                MetadataError::LoadFailure(format!(
-                    "failed to mmap rmeta metadata: '{}'",
-                    filename.display()
-                ))
-            })?;
+        CrateFlavor::Rmeta => slice_owned(map_rmeta_file(filename)?, Deref::deref),
+    })
+}
 
-            slice_owned(mmap, Deref::deref)
-        }
-    };
-    let blob = MetadataBlob(raw_bytes);
-    match blob.check_compatibility(cfg_version) {
-        Ok(()) => Ok(blob),
-        Err(None) => Err(MetadataError::LoadFailure(format!(
-            "invalid metadata version found: {}",
+fn map_rmeta_file(filename: &Path) -> Result<Mmap, MetadataError<'_>> {
+    // mmap the file, because only a small fraction of it is read.
+    let file = std::fs::File::open(filename).map_err(|_| {
+        MetadataError::LoadFailure(format!(
+            "failed to open rmeta metadata: '{}'",
             filename.display()
-        ))),
-        Err(Some(found_version)) => {
-            return Err(MetadataError::VersionMismatch {
-                expected_version: rustc_version(cfg_version),
-                found_version,
-            });
-        }
-    }
+        ))
+    })?;
+    let mmap = unsafe { Mmap::map(file) };
+    mmap.map_err(|_| {
+        MetadataError::LoadFailure(format!(
+            "failed to mmap rmeta metadata: '{}'",
+            filename.display()
+        ))
+    })
 }
 
 /// A diagnostic function for dumping crate metadata to an output stream.
@@ -885,6 +989,64 @@ pub fn list_file_metadata(
     }
 }
 
+/// The information about a crate file printed by `--print=crate-info`.
+pub struct CrateFileInfo {
+    pub name: Symbol,
+    pub edition: Edition,
+    pub hash: Svh,
+    pub triple: TargetTriple,
+    pub dependencies: Vec<CrateFileDependency>,
+}
+
+pub struct CrateFileDependency {
+    pub name: Symbol,
+    pub hash: Svh,
+    pub kind: CrateDepKind,
+    pub is_private: bool,
+}
+
+/// Reads the crate name, edition, SVH and dependencies of the crate file at `path`.
+pub fn crate_file_info(
+    target: &Target,
+    path: &Path,
+    metadata_loader: &dyn MetadataLoader,
+    cfg_version: &'static str,
+) -> Result<CrateFileInfo, String> {
+    let flavor = get_flavor_from_path(path);
+    match get_metadata_section(target, flavor, path, metadata_loader, cfg_version) {
+        Ok(metadata) => Ok(metadata.crate_file_info()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Compares the metadata of two builds of the same crate file, and returns where they differ: the
+/// names of the metadata tables whose elements differ, or a note that only the values the tables
+/// refer to differ.
+pub fn metadata_differences(
+    target: &Target,
+    a: &Path,
+    b: &Path,
+    metadata_loader: &dyn MetadataLoader,
+    cfg_version: &'static str,
+) -> Result<Vec<String>, String> {
+    let load = |path| {
+        get_metadata_section(target, get_flavor_from_path(path), path, metadata_loader, cfg_version)
+            .map_err(|err| err.to_string())
+    };
+    let (a, b) = (load(a)?, load(b)?);
+    if a[..] == b[..] {
+        return Ok(Vec::new());
+    }
+    let mut differences: Vec<_> = iter::zip(a.table_byte_ranges(), b.table_byte_ranges())
+        .filter(|((_, a_range), (_, b_range))| a[a_range.clone()] != b[b_range.clone()])
+        .map(|((name, _), _)| format!("metadata table `{name}`"))
+        .collect();
+    if differences.is_empty() {
+        differences.push("metadata values outside of the tables".to_owned());
+    }
+    Ok(differences)
+}
+
 fn get_flavor_from_path(path: &Path) -> CrateFlavor {
     let filename = path.file_name().unwrap().to_str().unwrap();
 
@@ -1,6 +1,7 @@
 // Decoding metadata from a single crate's metadata
 
 use crate::creader::CStore;
+use crate::locator::{CrateFileDependency, CrateFileInfo};
 use crate::rmeta::table::IsDefault;
 use crate::rmeta::*;
 
@@ -30,6 +31,7 @@
 
 use proc_macro::bridge::client::ProcMacro;
 use std::iter::TrustedLen;
+use std::ops::Range;
 use std::path::Path;
 use std::{io, iter, mem};
 
@@ -722,6 +724,31 @@ pub(crate) fn get_root(&self) -> CrateRoot {
         LazyValue::<CrateRoot>::from_position(pos).decode(self)
     }
 
+    pub(crate) fn crate_file_info(&self) -> CrateFileInfo {
+        let root = self.get_root();
+        CrateFileInfo {
+            name: root.name(),
+            edition: root.edition,
+            hash: root.hash(),
+            triple: root.header.triple.clone(),
+            dependencies: root
+                .crate_deps
+                .decode(self)
+                .map(|dep| CrateFileDependency {
+                    name: dep.name,
+                    hash: dep.hash,
+                    kind: dep.kind,
+                    is_private: dep.is_private,
+                })
+                .collect(),
+        }
+    }
+
+    /// The name of each table, with the range of the blob holding its elements.
+    pub(crate) fn table_byte_ranges(&self) -> Vec<(&'static str, Range<usize>)> {
+        self.get_root().tables.byte_ranges()
+    }
+
     pub(crate) fn list_crate_metadata(
         &self,
         out: &mut dyn io::Write,
@@ -1650,7 +1650,10 @@ fn encode_mir(&mut self) {
 
             debug!("EntryBuilder::encode_mir({:?})", def_id);
             if encode_opt {
-                record!(self.tables.optimized_mir[def_id.to_def_id()] <- tcx.optimized_mir(def_id));
+                let optimized_mir = tcx
+                    .replaced_optimized_mir(def_id.to_def_id())
+                    .unwrap_or_else(|| tcx.optimized_mir(def_id));
+                record!(self.tables.optimized_mir[def_id.to_def_id()] <- optimized_mir);
                 self.tables
                     .cross_crate_inlinable
                     .set(def_id.to_def_id().index, self.tcx.cross_crate_inlinable(def_id));
@@ -38,6 +38,7 @@
 
 use std::marker::PhantomData;
 use std::num::NonZero;
+use std::ops::Range;
 
 use decoder::DecodeContext;
 pub(crate) use decoder::{CrateMetadata, CrateNumMap, MetadataBlob};
@@ -156,6 +157,12 @@ fn from_position_and_encoded_size(
     ) -> LazyTable<I, T> {
         LazyTable { position, width, len, _marker: PhantomData }
     }
+
+    /// The range of the metadata blob holding the elements of the table.
+    fn byte_range(&self) -> Range<usize> {
+        let start = self.position.get();
+        start..start + self.width * self.len
+    }
 }
 
 impl<T> Copy for LazyValue<T> {}
@@ -370,6 +377,16 @@ fn encode(&self, buf: &mut FileEncoder) -> LazyTables {
                 }
             }
         }
+
+        impl LazyTables {
+            /// The name of each table, with the range of the metadata blob holding its elements.
+            pub(crate) fn byte_ranges(&self) -> Vec<(&'static str, Range<usize>)> {
+                vec![
+                    $((stringify!($name1), self.$name1.byte_range()),)+
+                    $((stringify!($name2), self.$name2.byte_range()),)+
+                ]
+            }
+        }
     }
 }
 
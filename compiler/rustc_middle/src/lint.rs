@@ -2,7 +2,7 @@
 
 use rustc_data_structures::fx::FxIndexMap;
 use rustc_data_structures::sorted_map::SortedMap;
-use rustc_errors::{Diag, DiagMessage, MultiSpan};
+use rustc_errors::{Diag, DiagLintLevelSource, DiagLintLevelSourceKind, DiagMessage, MultiSpan};
 use rustc_hir::{HirId, ItemLocalId};
 use rustc_session::lint::{
     builtin::{self, FORBIDDEN_LINT_GROUPS},
@@ -26,12 +26,20 @@ pub enum LintLevelSource {
         span: Span,
         /// RFC 2383 reason
         reason: Option<Symbol>,
+        /// The level set by the attribute.
+        /// (The actual level may be lower due to `--cap-lints`.)
+        level: Level,
     },
 
     /// Lint level was set by a command-line flag.
     /// The provided `Level` is the level specified on the command line.
     /// (The actual level may be lower due to `--cap-lints`.)
     CommandLine(Symbol, Level),
+
+    /// Lint level was set by the `--lint-config` file, either for the whole crate or for the
+    /// modules matching `pattern`.
+    /// The provided `level` is the level specified in the file.
+    LintConfig { name: Symbol, level: Level, pattern: Option<Symbol> },
 }
 
 impl LintLevelSource {
@@ -40,6 +48,7 @@ pub fn name(&self) -> Symbol {
             LintLevelSource::Default => symbol::kw::Default,
             LintLevelSource::Node { name, .. } => name,
             LintLevelSource::CommandLine(name, _) => name,
+            LintLevelSource::LintConfig { name, .. } => name,
         }
     }
 
@@ -48,6 +57,7 @@ pub fn span(&self) -> Span {
             LintLevelSource::Default => DUMMY_SP,
             LintLevelSource::Node { span, .. } => span,
             LintLevelSource::CommandLine(_, _) => DUMMY_SP,
+            LintLevelSource::LintConfig { .. } => DUMMY_SP,
         }
     }
 }
@@ -97,7 +107,9 @@ pub fn reveal_actual_level(
     }
 
     // Ensure that we never exceed the `--cap-lints` argument unless the source is a --force-warn
-    level = if let LintLevelSource::CommandLine(_, Level::ForceWarn(_)) = src {
+    level = if let LintLevelSource::CommandLine(_, Level::ForceWarn(_))
+    | LintLevelSource::LintConfig { level: Level::ForceWarn(_), .. } = src
+    {
         level
     } else {
         cmp::min(level, sess.opts.lint_cap.unwrap_or(Level::Forbid))
@@ -233,6 +245,20 @@ pub fn explain_lint_level_source(
                 ));
             }
         }
+        LintLevelSource::LintConfig { name: lint_config_name, level: orig_level, pattern } => {
+            let entry = format!("{lint_config_name} = \"{}\"", orig_level.as_str());
+            if let Some(pattern) = pattern {
+                err.note_once(format!(
+                    "requested for the modules matching `{pattern}` in the `--lint-config` file \
+                     with `{entry}`"
+                ));
+            } else {
+                err.note_once(format!("requested in the `--lint-config` file with `{entry}`"));
+            }
+            if lint_config_name.as_str() != name {
+                err.note_once(format!("`{name}` is part of the `{lint_config_name}` lint group"));
+            }
+        }
         LintLevelSource::Node { name: lint_attr_name, span, reason, .. } => {
             if let Some(rationale) = reason {
                 err.note(rationale.to_string());
@@ -248,6 +274,50 @@ pub fn explain_lint_level_source(
     }
 }
 
+/// Describes how the level of `lint` was set, for machine-readable diagnostics.
+fn diag_lint_level_source(
+    sess: &Session,
+    lint: &'static Lint,
+    level: Level,
+    src: LintLevelSource,
+) -> DiagLintLevelSource {
+    let kind = match src {
+        LintLevelSource::Default => DiagLintLevelSourceKind::Default,
+        LintLevelSource::Node { span, reason, .. } => DiagLintLevelSourceKind::Attribute {
+            span,
+            reason: reason.map(|reason| reason.to_string()),
+        },
+        LintLevelSource::CommandLine(lint_flag_val, orig_level) => {
+            let flag = orig_level.to_cmd_flag();
+            let hyphen_case_flag_val = lint_flag_val.as_str().replace('_', "-");
+            DiagLintLevelSourceKind::CommandLine { flag: format!("{flag} {hyphen_case_flag_val}") }
+        }
+        LintLevelSource::LintConfig { pattern, .. } => DiagLintLevelSourceKind::LintConfig {
+            pattern: pattern.map(|pattern| pattern.to_string()),
+        },
+    };
+    let name = match src {
+        LintLevelSource::Default => lint.name_lower(),
+        _ => src.name().to_string(),
+    };
+    // The level before `reveal_actual_level` capped it. Force-warn levels are not capped.
+    let uncapped_level = match src {
+        LintLevelSource::Default => lint.default_level(sess.edition()),
+        LintLevelSource::Node { level, .. }
+        | LintLevelSource::CommandLine(_, level)
+        | LintLevelSource::LintConfig { level, .. } => level,
+    };
+    let cap_lints = sess.opts.lint_cap.filter(|&cap| {
+        cap == level && uncapped_level > cap && !matches!(uncapped_level, Level::ForceWarn(_))
+    });
+    DiagLintLevelSource {
+        kind,
+        name,
+        level: level.as_str().to_owned(),
+        cap_lints: cap_lints.map(|cap| cap.as_str().to_owned()),
+    }
+}
+
 /// The innermost function for emitting lints.
 ///
 /// If you are looking to implement a lint, look for higher level functions,
@@ -354,6 +424,7 @@ fn lint_level_impl(
         err.primary_message(msg);
 
         err.is_lint(lint.name_lower(), has_future_breakage);
+        err.lint_level_source(diag_lint_level_source(sess, lint, level, src));
 
         // Lint diagnostics that are covered by the expect level will not be emitted outside
         // the compiler. It is therefore not necessary to add any information for the user.
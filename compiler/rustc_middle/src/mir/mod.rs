@@ -21,6 +21,7 @@
     self as hir, BindingAnnotation, ByRef, CoroutineDesugaring, CoroutineKind, HirId,
     ImplicitSelfKind,
 };
+use rustc_session::config::OptLevel;
 use rustc_session::Session;
 use rustc_span::source_map::Spanned;
 use rustc_target::abi::{FieldIdx, VariantIdx};
@@ -138,6 +139,40 @@ fn to_profiler_name(type_name: &'static str) -> &'static str {
     })
 }
 
+/// The optimization levels of a body: the ones of the session, unless the function of the body
+/// sets another one with `#[optimize(level = "...")]`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BodyOptLevels {
+    /// The level of `-C opt-level`.
+    pub opt_level: OptLevel,
+    /// The level of `-Z mir-opt-level`. It's `0` for the functions with
+    /// `#[optimize(level = "0")]`, to only run the passes which are always needed.
+    pub mir_opt_level: usize,
+}
+
+impl BodyOptLevels {
+    /// The optimization levels of the session.
+    pub fn of_session(sess: &Session) -> BodyOptLevels {
+        BodyOptLevels { opt_level: sess.opts.optimize, mir_opt_level: sess.mir_opt_level() }
+    }
+
+    /// The optimization levels of the body of `def_id`.
+    pub fn of_body(tcx: TyCtxt<'_>, def_id: DefId) -> BodyOptLevels {
+        let optimize = if tcx.def_kind(def_id).has_codegen_attrs() {
+            tcx.codegen_fn_attrs(def_id).optimize.opt_level()
+        } else {
+            None
+        };
+        match optimize {
+            None => BodyOptLevels::of_session(tcx.sess),
+            Some(OptLevel::No) => BodyOptLevels { opt_level: OptLevel::No, mir_opt_level: 0 },
+            Some(opt_level) => {
+                BodyOptLevels { opt_level, mir_opt_level: tcx.sess.mir_opt_level_for(opt_level) }
+            }
+        }
+    }
+}
+
 /// A streamlined trait that you can implement to create a pass; the
 /// pass will be named after the type, and it will consist of a main
 /// loop that goes over each available MIR and applies `run_pass`.
@@ -155,8 +190,9 @@ fn profiler_name(&self) -> &'static str {
         to_profiler_name(self.name())
     }
 
-    /// Returns `true` if this pass is enabled with the current combination of compiler flags.
-    fn is_enabled(&self, _sess: &Session) -> bool {
+    /// Returns `true` if this pass is enabled with the current combination of compiler flags, for
+    /// a body optimized at `levels`.
+    fn is_enabled(&self, _sess: &Session, _levels: BodyOptLevels) -> bool {
         true
     }
 
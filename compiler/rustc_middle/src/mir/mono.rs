@@ -3,7 +3,7 @@
 use rustc_attr::InlineAttr;
 use rustc_data_structures::base_n;
 use rustc_data_structures::fingerprint::Fingerprint;
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::fx::FxIndexMap;
 use rustc_data_structures::stable_hasher::{Hash128, HashStable, StableHasher};
 use rustc_hir::def_id::{CrateNum, DefId, LOCAL_CRATE};
@@ -13,6 +13,7 @@
 use rustc_session::config::OptLevel;
 use rustc_span::symbol::Symbol;
 use rustc_span::Span;
+use std::collections::VecDeque;
 use std::fmt;
 use std::hash::Hash;
 
@@ -235,6 +236,111 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+/// How a mono item uses another one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum UseKind {
+    /// A direct call, including the calls to panic functions inserted for assertions.
+    Call,
+    /// The drop glue of a type, called by a drop or referenced by a vtable.
+    DropGlue,
+    /// A method referenced by a vtable, which can be called through a trait object.
+    VtableMethod,
+    /// A function reified to a function pointer, or referenced by a constant or a `sym` operand.
+    FnPointer,
+    /// A static, or the shim accessing a thread local static.
+    Static,
+}
+
+impl UseKind {
+    pub fn descr(self) -> &'static str {
+        match self {
+            UseKind::Call => "call",
+            UseKind::DropGlue => "drop",
+            UseKind::VtableMethod => "vtable",
+            UseKind::FnPointer => "fn pointer",
+            UseKind::Static => "static",
+        }
+    }
+}
+
+/// A use of a mono item by another one, i.e. an edge of the mono item graph.
+#[derive(Copy, Clone, Debug)]
+pub struct MonoItemUse<'tcx> {
+    pub item: MonoItem<'tcx>,
+    pub kind: UseKind,
+    /// Where the item is used, or `DUMMY_SP` for the uses through constants.
+    pub span: Span,
+}
+
+/// The graph of the uses between the mono items collected for the crate.
+pub struct UsageMap<'tcx> {
+    // Maps every mono item to the mono items used by it.
+    used_map: FxHashMap<MonoItem<'tcx>, Vec<MonoItemUse<'tcx>>>,
+
+    // Maps every mono item to the mono items that use it.
+    user_map: FxHashMap<MonoItem<'tcx>, Vec<MonoItem<'tcx>>>,
+
+    // The roots the collection started from, in the order they were collected.
+    roots: Vec<MonoItem<'tcx>>,
+}
+
+impl<'tcx> UsageMap<'tcx> {
+    pub fn new(roots: Vec<MonoItem<'tcx>>) -> UsageMap<'tcx> {
+        UsageMap { used_map: FxHashMap::default(), user_map: FxHashMap::default(), roots }
+    }
+
+    pub fn record_used(&mut self, user_item: MonoItem<'tcx>, used_items: &[MonoItemUse<'tcx>]) {
+        for used_item in used_items.iter() {
+            self.user_map.entry(used_item.item).or_default().push(user_item);
+        }
+
+        assert!(self.used_map.insert(user_item, used_items.to_vec()).is_none());
+    }
+
+    pub fn get_user_items(&self, item: MonoItem<'tcx>) -> &[MonoItem<'tcx>] {
+        self.user_map.get(&item).map(|items| items.as_slice()).unwrap_or(&[])
+    }
+
+    /// Returns the items used by `item`, with how and where they are used.
+    pub fn get_uses(&self, item: MonoItem<'tcx>) -> &[MonoItemUse<'tcx>] {
+        self.used_map.get(&item).map(|uses| uses.as_slice()).unwrap_or(&[])
+    }
+
+    /// Returns, for every item reachable from the roots which isn't a root, the item using it and
+    /// the use through which a breadth-first traversal from the roots reaches it first. Following
+    /// the users back to a root gives the shortest chain of uses from a root to the item.
+    pub fn first_uses_from_roots(
+        &self,
+    ) -> FxHashMap<MonoItem<'tcx>, (MonoItem<'tcx>, MonoItemUse<'tcx>)> {
+        let mut first_uses = FxHashMap::default();
+        let mut visited: FxHashSet<_> = self.roots.iter().copied().collect();
+        let mut queue: VecDeque<_> = self.roots.iter().copied().collect();
+        while let Some(user) = queue.pop_front() {
+            for used in self.get_uses(user) {
+                if visited.insert(used.item) {
+                    first_uses.insert(used.item, (user, *used));
+                    queue.push_back(used.item);
+                }
+            }
+        }
+        first_uses
+    }
+
+    /// Internally iterate over all inlined items used by `item`.
+    pub fn for_each_inlined_used_item<F>(&self, tcx: TyCtxt<'tcx>, item: MonoItem<'tcx>, mut f: F)
+    where
+        F: FnMut(MonoItem<'tcx>),
+    {
+        let used_items = self.used_map.get(&item).unwrap();
+        for used_item in used_items.iter() {
+            let is_inlined = used_item.item.instantiation_mode(tcx) == InstantiationMode::LocalCopy;
+            if is_inlined {
+                f(used_item.item);
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct CodegenUnit<'tcx> {
     /// A name for this CGU. Incremental compilation requires that
@@ -248,6 +354,9 @@ pub struct CodegenUnit<'tcx> {
     /// True if this is CGU is used to hold code coverage information for dead code,
     /// false otherwise.
     is_code_coverage_dead_code_cgu: bool,
+    /// The optimization level of the functions placed in this CGU by their
+    /// `#[optimize(level = "...")]` attribute, when it isn't the one of the session.
+    opt_level: Option<OptLevel>,
 }
 
 /// Auxiliary info about a `MonoItem`.
@@ -298,6 +407,7 @@ pub fn new(name: Symbol) -> CodegenUnit<'tcx> {
             size_estimate: 0,
             primary: false,
             is_code_coverage_dead_code_cgu: false,
+            opt_level: None,
         }
     }
 
@@ -334,6 +444,15 @@ pub fn make_code_coverage_dead_code_cgu(&mut self) {
         self.is_code_coverage_dead_code_cgu = true;
     }
 
+    /// The optimization level of this CGU, when it differs from the one of the session.
+    pub fn opt_level(&self) -> Option<OptLevel> {
+        self.opt_level
+    }
+
+    pub fn set_opt_level(&mut self, opt_level: Option<OptLevel>) {
+        self.opt_level = opt_level;
+    }
+
     pub fn mangle_name(human_readable_name: &str) -> String {
         // We generate a 80 bit hash from the name. This should be enough to
         // avoid collisions and is still reasonably short for filenames.
@@ -435,10 +554,12 @@ fn hash_stable(&self, hcx: &mut StableHashingContext<'a>, hasher: &mut StableHas
             size_estimate: _,
             primary: _,
             is_code_coverage_dead_code_cgu,
+            opt_level,
         } = *self;
 
         name.hash_stable(hcx, hasher);
         is_code_coverage_dead_code_cgu.hash_stable(hcx, hasher);
+        opt_level.hash_stable(hcx, hasher);
 
         let mut items: Vec<(Fingerprint, _)> = items
             .iter()
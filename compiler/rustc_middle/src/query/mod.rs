@@ -24,7 +24,7 @@
     EvalToValTreeResult,
 };
 use crate::mir::interpret::{LitToConstError, LitToConstInput};
-use crate::mir::mono::CodegenUnit;
+use crate::mir::mono::{CodegenUnit, MonoItem, UsageMap};
 use crate::query::erase::{erase, restore, Erase};
 use crate::query::plumbing::{
     query_ensure, query_ensure_error_guaranteed, query_get_at, CyclePlaceholder, DynamicQuery,
@@ -58,7 +58,7 @@
 use rustc_ast::expand::{allocator::AllocatorKind, StrippedCfgItem};
 use rustc_attr as attr;
 use rustc_data_structures::fingerprint::Fingerprint;
-use rustc_data_structures::fx::{FxIndexMap, FxIndexSet};
+use rustc_data_structures::fx::{FxHashSet, FxIndexMap, FxIndexSet};
 use rustc_data_structures::steal::Steal;
 use rustc_data_structures::svh::Svh;
 use rustc_data_structures::sync::Lrc;
@@ -1887,6 +1887,15 @@
         separate_provide_extern
     }
 
+    /// Collects the mono items of the crate with the strategy of code generation, together with
+    /// the graph of their uses.
+    query collect_mono_items(_: ()) -> &'tcx (FxHashSet<MonoItem<'tcx>>, UsageMap<'tcx>) {
+        arena_cache
+        eval_always
+        no_hash
+        desc { "collecting the mono items of the crate" }
+    }
+
     query collect_and_partition_mono_items(_: ()) -> (&'tcx DefIdSet, &'tcx [CodegenUnit<'tcx>]) {
         eval_always
         desc { "collect_and_partition_mono_items" }
@@ -24,6 +24,7 @@
 use crate::traits::solve::{
     ExternalConstraints, ExternalConstraintsData, PredefinedOpaques, PredefinedOpaquesData,
 };
+use crate::ty::print::{with_forced_impl_filename_line, with_no_trimmed_paths};
 use crate::ty::{
     self, AdtDef, AdtDefData, AdtKind, Binder, Clause, Clauses, Const, ConstData,
     GenericParamDefKind, ImplPolarity, List, ListWithCachedTypeInfo, ParamConst, ParamTy, Pattern,
@@ -59,6 +60,7 @@
 use rustc_macros::HashStable;
 use rustc_query_system::dep_graph::DepNodeIndex;
 use rustc_query_system::ich::StableHashingContext;
+use rustc_query_system::query::QueryCache;
 use rustc_serialize::opaque::{FileEncodeResult, FileEncoder};
 use rustc_session::config::CrateType;
 use rustc_session::cstore::{CrateStoreDyn, Untracked};
@@ -751,6 +753,10 @@ pub struct GlobalCtxt<'tcx> {
     /// Stores memory for globals (statics/consts).
     pub(crate) alloc_map: Lock<interpret::AllocMap<'tcx>>,
 
+    /// The bodies replacing the optimized MIR of functions, see
+    /// [`TyCtxt::replace_optimized_mir`].
+    optimized_mir_replacements: Lock<FxHashMap<DefId, &'tcx Body<'tcx>>>,
+
     current_gcx: CurrentGcx,
 }
 
@@ -969,10 +975,50 @@ pub fn create_global_ctxt(
             canonical_param_env_cache: Default::default(),
             data_layout,
             alloc_map: Lock::new(interpret::AllocMap::new()),
+            optimized_mir_replacements: Default::default(),
             current_gcx,
         }
     }
 
+    /// Replaces the optimized MIR of the function `def_id` by `body` for the rest of the
+    /// compilation, e.g. to instrument it: `body` is returned by [`TyCtxt::instance_mir`], used
+    /// by codegen and the MIR inliner, and encoded in the crate metadata.
+    ///
+    /// The replacement is rejected once the original MIR may have been used by a query which
+    /// isn't recomputed afterwards: when codegen started, or when the MIR inliner, the metadata
+    /// encoder or the deduction of the parameter attributes looked at the original body.
+    pub fn replace_optimized_mir(
+        self,
+        def_id: DefId,
+        body: Body<'tcx>,
+    ) -> Result<(), &'static str> {
+        let caches = &self.query_system.caches;
+        if caches.collect_and_partition_mono_items.lookup(&()).is_some() {
+            return Err("codegen already started");
+        }
+        // The MIR inliner and the metadata encoder both ask whether the function is inlinable
+        // across crates before using its body.
+        if caches.cross_crate_inlinable.lookup(&def_id).is_some() {
+            return Err("its body may already have been inlined or encoded in the crate metadata");
+        }
+        if caches.deduced_param_attrs.lookup(&def_id).is_some() {
+            return Err("the attributes of its parameters were already deduced from its body");
+        }
+        // The callees are used by the MIR inliner to detect cycles, they must come from the body
+        // which will be inlined.
+        if caches.mir_inliner_callees.lookup(&ty::InstanceDef::Item(def_id)).is_some() {
+            return Err("its callees were already collected for the MIR inliner");
+        Ok(())
+        }
+        let body = self.arena.alloc(body);
+        self.optimized_mir_replacements.lock().insert(def_id, body);
+    }
+
+    /// Returns the body given to [`TyCtxt::replace_optimized_mir`] for `def_id`, if any.
+    pub fn replaced_optimized_mir(self, def_id: DefId) -> Option<&'tcx Body<'tcx>> {
+        self.optimized_mir_replacements.lock().get(&def_id).copied()
+    }
+
     pub fn consider_optimizing<T: Fn() -> String>(self, msg: T) -> bool {
         self.sess.consider_optimizing(|| self.crate_name(LOCAL_CRATE), msg)
     }
@@ -2373,6 +2419,7 @@ pub fn emit_node_span_lint(
         let (level, src) = self.lint_level_at_node(lint, hir_id);
         lint_level(self.sess, lint, level, src, Some(span.into()), msg, |diag| {
             decorator.decorate_lint(diag);
+            self.record_lint_def_path(diag, hir_id);
         })
     }
 
@@ -2390,7 +2437,23 @@ pub fn node_span_lint(
         decorate: impl for<'a, 'b> FnOnce(&'b mut Diag<'a, ()>),
     ) {
         let (level, src) = self.lint_level_at_node(lint, hir_id);
-        lint_level(self.sess, lint, level, src, Some(span.into()), msg, decorate);
+        lint_level(self.sess, lint, level, src, Some(span.into()), msg, |diag| {
+            decorate(diag);
+            self.record_lint_def_path(diag, hir_id);
+        });
+    }
+
+    /// Records the path of the item owning `hir_id` on a lint, as part of the key identifying
+    /// it in the `-Z diagnostic-baseline` file.
+    fn record_lint_def_path(self, diag: &mut Diag<'_, ()>, hir_id: HirId) {
+        if self.sess.opts.unstable_opts.diagnostic_baseline.is_some() {
+            // Impls are named after their location rather than their self type, as computing it
+            // may cycle if the lint is emitted while computing the type (#41697).
+            let def_path = with_no_trimmed_paths!(with_forced_impl_filename_line!(
+                self.def_path_str(hir_id.owner.to_def_id())
+            ));
+            diag.lint_def_path(def_path);
+        }
     }
 
     /// Find the crate root and the appropriate span where `use` and outer attributes can be
@@ -2459,7 +2522,10 @@ pub fn node_lint(
         decorate: impl for<'a, 'b> FnOnce(&'b mut Diag<'a, ()>),
     ) {
         let (level, src) = self.lint_level_at_node(lint, id);
-        lint_level(self.sess, lint, level, src, None, msg, decorate);
+        lint_level(self.sess, lint, level, src, None, msg, |diag| {
+            decorate(diag);
+            self.record_lint_def_path(diag, id);
+        });
     }
 
     pub fn in_scope_traits(self, id: HirId) -> Option<&'tcx [TraitCandidate]> {
@@ -1783,7 +1783,9 @@ pub fn instance_mir(self, instance: ty::InstanceDef<'tcx>) -> &'tcx Body<'tcx> {
                     | DefKind::InlineConst => self.mir_for_ctfe(def),
                     // If the caller wants `mir_for_ctfe` of a function they should not be using
                     // `instance_mir`, so we'll assume const fn also wants the optimized version.
-                    _ => self.optimized_mir(def),
+                    _ => {
+                        self.replaced_optimized_mir(def).unwrap_or_else(|| self.optimized_mir(def))
+                    }
                 }
             }
             ty::InstanceDef::VTableShim(..)
@@ -4,7 +4,8 @@
 use rustc_middle::{
     mir::{
         visit::{PlaceContext, Visitor},
-        Body, ConstOperand, Local, Location, Operand, Rvalue, StatementKind, VarDebugInfoContents,
+        Body, BodyOptLevels, ConstOperand, Local, Location, Operand, Rvalue, StatementKind,
+        VarDebugInfoContents,
     },
     ty::TyCtxt,
 };
@@ -15,8 +16,8 @@
 pub struct ConstDebugInfo;
 
 impl<'tcx> MirPass<'tcx> for ConstDebugInfo {
-    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
-        sess.mir_opt_level() > 0
+    fn is_enabled(&self, _sess: &rustc_session::Session, levels: BodyOptLevels) -> bool {
+        levels.mir_opt_level > 0
     }
 
     fn run_pass(&self, _tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
@@ -20,8 +20,8 @@
 pub struct CopyProp;
 
 impl<'tcx> MirPass<'tcx> for CopyProp {
-    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
-        sess.mir_opt_level() >= 1
+    fn is_enabled(&self, _sess: &rustc_session::Session, levels: BodyOptLevels) -> bool {
+        levels.mir_opt_level >= 1
     }
 
     #[instrument(level = "trace", skip(self, tcx, body))]
@@ -26,8 +26,8 @@
 pub struct DataflowConstProp;
 
 impl<'tcx> MirPass<'tcx> for DataflowConstProp {
-    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
-        sess.mir_opt_level() >= 3
+    fn is_enabled(&self, _sess: &rustc_session::Session, levels: BodyOptLevels) -> bool {
+        levels.mir_opt_level >= 3
     }
 
     #[instrument(skip_all level = "debug")]
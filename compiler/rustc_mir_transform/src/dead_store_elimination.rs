@@ -138,8 +138,8 @@ fn name(&self) -> &'static str {
         }
     }
 
-    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
-        sess.mir_opt_level() >= 2
+    fn is_enabled(&self, _sess: &rustc_session::Session, levels: BodyOptLevels) -> bool {
+        levels.mir_opt_level >= 2
     }
 
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
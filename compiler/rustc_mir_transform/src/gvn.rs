@@ -108,8 +108,8 @@
 pub struct GVN;
 
 impl<'tcx> MirPass<'tcx> for GVN {
-    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
-        sess.mir_opt_level() >= 2
+    fn is_enabled(&self, _sess: &rustc_session::Session, levels: BodyOptLevels) -> bool {
+        levels.mir_opt_level >= 2
     }
 
     #[instrument(level = "trace", skip(self, tcx, body))]
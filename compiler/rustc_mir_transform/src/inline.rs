@@ -38,16 +38,15 @@ struct CallSite<'tcx> {
 }
 
 impl<'tcx> MirPass<'tcx> for Inline {
-    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+    fn is_enabled(&self, sess: &rustc_session::Session, levels: BodyOptLevels) -> bool {
         if let Some(enabled) = sess.opts.unstable_opts.inline_mir {
             return enabled;
         }
 
-        match sess.mir_opt_level() {
+        match levels.mir_opt_level {
             0 | 1 => false,
             2 => {
-                (sess.opts.optimize == OptLevel::Default
-                    || sess.opts.optimize == OptLevel::Aggressive)
+                (levels.opt_level == OptLevel::Default || levels.opt_level == OptLevel::Aggressive)
                     && sess.opts.incremental == None
             }
             _ => true,
@@ -430,6 +429,11 @@ fn check_codegen_attributes(
             return Err("never inline hint");
         }
 
+        // The code of functions that aren't optimized is kept as is, to be debuggable.
+        if callee_attrs.optimize.do_not_optimize() {
+            return Err("not optimized");
+        }
+
         // Reachability pass defines which functions are eligible for inlining. Generally inlining
         // other functions is incorrect because they could reference symbols that aren't exported.
         let is_generic = callsite
@@ -1,5 +1,11 @@
-use rustc_middle::mir::{self, Body, MirPhase, RuntimePhase};
-use rustc_middle::ty::TyCtxt;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+use rustc_data_structures::fx::{FxHashMap, FxHasher};
+use rustc_middle::mir::{self, Body, BodyOptLevels, MirPhase, RuntimePhase};
+use rustc_middle::ty::print::{with_forced_impl_filename_line, with_no_trimmed_paths};
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_session::code_stats::{MirBodySize, MirPassInfo};
 use rustc_session::Session;
 
 use crate::{lint::lint_body, validate, MirPass};
@@ -34,7 +40,7 @@ fn name(&self) -> &'static str {
         self.0.name()
     }
 
-    fn is_enabled(&self, sess: &Session) -> bool {
+    fn is_enabled(&self, sess: &Session, _levels: BodyOptLevels) -> bool {
         self.0.is_enabled(sess)
     }
 
@@ -57,8 +63,8 @@ fn name(&self) -> &'static str {
         self.1.name()
     }
 
-    fn is_enabled(&self, sess: &Session) -> bool {
-        sess.mir_opt_level() >= self.0 as usize
+    fn is_enabled(&self, _sess: &Session, levels: BodyOptLevels) -> bool {
+        levels.mir_opt_level >= self.0 as usize
     }
 
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
@@ -87,7 +93,7 @@ pub fn run_passes<'tcx>(
     run_passes_inner(tcx, body, passes, phase_change, true);
 }
 
-pub fn should_run_pass<'tcx, P>(tcx: TyCtxt<'tcx>, pass: &P) -> bool
+pub fn should_run_pass<'tcx, P>(tcx: TyCtxt<'tcx>, pass: &P, levels: BodyOptLevels) -> bool
 where
     P: MirPass<'tcx> + ?Sized,
 {
@@ -103,7 +109,7 @@ pub fn should_run_pass<'tcx, P>(tcx: TyCtxt<'tcx>, pass: &P) -> bool
             );
             *polarity
         });
-    overridden.unwrap_or_else(|| pass.is_enabled(tcx.sess))
+    overridden.unwrap_or_else(|| pass.is_enabled(tcx.sess, levels))
 }
 
 fn run_passes_inner<'tcx>(
@@ -121,11 +127,13 @@ fn run_passes_inner<'tcx>(
     if !body.should_skip() {
         let validate = validate_each & tcx.sess.opts.unstable_opts.validate_mir;
         let lint = tcx.sess.opts.unstable_opts.lint_mir;
+        let record_stats = tcx.sess.opts.unstable_opts.mir_pass_stats.enabled();
+        let levels = BodyOptLevels::of_body(tcx, body.source.def_id());
 
         for pass in passes {
             let name = pass.name();
 
-            if !should_run_pass(tcx, *pass) {
+            if !should_run_pass(tcx, *pass, levels) {
                 continue;
             };
 
@@ -134,6 +142,7 @@ fn run_passes_inner<'tcx>(
             if dump_enabled {
                 dump_mir_for_pass(tcx, body, name, false);
             }
+            let before = record_stats.then(|| (BodyStats::of(body), Instant::now()));
 
             if let Some(prof_arg) = &prof_arg {
                 tcx.sess
@@ -144,6 +153,9 @@ fn run_passes_inner<'tcx>(
                 pass.run_pass(tcx, body);
             }
 
+            if let Some((before, start)) = before {
+                record_pass_stats(tcx, body, name, before, start.elapsed());
+            }
             if dump_enabled {
                 dump_mir_for_pass(tcx, body, name, true);
             }
@@ -189,6 +201,75 @@ fn run_passes_inner<'tcx>(
     }
 }
 
+/// The contents of a body before a pass, whose changes are recorded for `-Z mir-pass-stats`.
+struct BodyStats {
+    size: MirBodySize,
+    /// The number of statements of each kind, by the hash of their kind.
+    statements: FxHashMap<u64, usize>,
+}
+
+impl BodyStats {
+    fn of(body: &Body<'_>) -> BodyStats {
+        let mut statements = FxHashMap::default();
+        for statement in body.basic_blocks.iter().flat_map(|data| &data.statements) {
+            let mut hasher = FxHasher::default();
+            statement.kind.hash(&mut hasher);
+            *statements.entry(hasher.finish()).or_default() += 1;
+        }
+        let size = MirBodySize {
+            basic_blocks: body.basic_blocks.len(),
+            statements: statements.values().sum(),
+            locals: body.local_decls.len(),
+        };
+        BodyStats { size, statements }
+    }
+
+    /// The number of statements of `self` which aren't in `other`.
+    fn statements_missing_from(&self, other: &BodyStats) -> usize {
+        self.statements
+            .iter()
+            .map(|(kind, count)| {
+                count.saturating_sub(other.statements.get(kind).copied().unwrap_or(0))
+            })
+            .sum()
+    }
+}
+
+fn record_pass_stats<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    pass_name: &'static str,
+    before: BodyStats,
+    duration: Duration,
+) {
+    let source = body.source;
+    // Impls are named by their location, as naming them by their types could trigger `type_of`
+    // while it's being computed, see #41697.
+    let path = with_no_trimmed_paths!(with_forced_impl_filename_line!({
+        let mut path = tcx.def_path_str(source.def_id());
+        if let Some(promoted) = source.promoted {
+            path.push_str(&format!("::{promoted:?}"));
+        }
+        match source.instance {
+            ty::InstanceDef::Item(_) => {}
+            ty::InstanceDef::DropGlue(_, Some(ty)) => path.push_str(&format!(" - shim({ty})")),
+            _ => path.push_str(" - shim"),
+        }
+        path
+    }));
+
+    let after = BodyStats::of(body);
+    tcx.sess.code_stats.record_mir_pass(MirPassInfo {
+        pass_name,
+        body: path,
+        duration,
+        before: before.size,
+        after: after.size,
+        statements_added: after.statements_missing_from(&before),
+        statements_removed: before.statements_missing_from(&after),
+    });
+}
+
 pub fn validate_body<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>, when: String) {
     validate::Validator { when, mir_phase: body.phase }.run_pass(tcx, body);
 }
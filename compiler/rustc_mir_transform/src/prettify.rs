@@ -17,7 +17,7 @@
 pub struct ReorderBasicBlocks;
 
 impl<'tcx> MirPass<'tcx> for ReorderBasicBlocks {
-    fn is_enabled(&self, _session: &Session) -> bool {
+    fn is_enabled(&self, _session: &Session, _levels: BodyOptLevels) -> bool {
         false
     }
 
@@ -45,7 +45,7 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
 pub struct ReorderLocals;
 
 impl<'tcx> MirPass<'tcx> for ReorderLocals {
-    fn is_enabled(&self, _session: &Session) -> bool {
+    fn is_enabled(&self, _session: &Session, _levels: BodyOptLevels) -> bool {
         false
     }
 
@@ -6,7 +6,7 @@
 pub struct RemovePlaceMention;
 
 impl<'tcx> MirPass<'tcx> for RemovePlaceMention {
-    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+    fn is_enabled(&self, sess: &rustc_session::Session, _levels: BodyOptLevels) -> bool {
         !sess.opts.unstable_opts.mir_keep_place_mention
     }
 
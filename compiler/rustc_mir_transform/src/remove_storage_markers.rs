@@ -6,8 +6,8 @@
 pub struct RemoveStorageMarkers;
 
 impl<'tcx> MirPass<'tcx> for RemoveStorageMarkers {
-    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
-        sess.mir_opt_level() > 0 && !sess.emit_lifetime_markers()
+    fn is_enabled(&self, sess: &rustc_session::Session, levels: BodyOptLevels) -> bool {
+        levels.mir_opt_level > 0 && !sess.emit_lifetime_markers()
     }
 
     fn run_pass(&self, _tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
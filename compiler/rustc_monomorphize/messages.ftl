@@ -1,3 +1,8 @@
+monomorphize_codegen_unit_hints_ignored =
+    ignoring the `#[codegen_unit]` attributes, as the codegen units they name don't fit within `-C codegen-units={$max_codegen_units}`
+    .note = the items without `#[codegen_unit]` attribute need a codegen unit of their own
+    .help = raise the maximum number of codegen units, or name fewer of them
+
 monomorphize_consider_type_length_limit =
     consider adding a `#![type_length_limit="{$type_length}"]` attribute to your crate
 
@@ -16,6 +21,11 @@ monomorphize_no_optimized_mir =
     missing optimized MIR for an item in the crate `{$crate_name}`
     .note = missing optimized MIR for this item (was the crate `{$crate_name}` compiled with `--emit=metadata`?)
 
+monomorphize_optimize_levels_ignored =
+    ignoring the optimization levels of `#[optimize(level = "...")]` for LLVM, as the codegen units optimized at these levels don't fit within `-C codegen-units={$max_codegen_units}`
+    .note = the functions optimized at another level than the crate need a codegen unit of their own
+    .help = raise the maximum number of codegen units
+
 monomorphize_recursion_limit =
     reached the recursion limit while instantiating `{$shrunk}`
     .note = `{$def_path_str}` defined here
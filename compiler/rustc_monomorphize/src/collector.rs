@@ -215,7 +215,7 @@
 use rustc_hir::lang_items::LangItem;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::mir::interpret::{AllocId, ErrorHandled, GlobalAlloc, Scalar};
-use rustc_middle::mir::mono::{InstantiationMode, MonoItem};
+use rustc_middle::mir::mono::{MonoItem, MonoItemUse, UsageMap, UseKind};
 use rustc_middle::mir::traversal;
 use rustc_middle::mir::visit::Visitor as MirVisitor;
 use rustc_middle::mir::{self, Location, MentionedItem};
@@ -247,15 +247,7 @@ pub enum MonoItemCollectionStrategy {
     Lazy,
 }
 
-pub struct UsageMap<'tcx> {
-    // Maps every mono item to the mono items used by it.
-    used_map: FxHashMap<MonoItem<'tcx>, Vec<MonoItem<'tcx>>>,
-
-    // Maps every mono item to the mono items that use it.
-    user_map: FxHashMap<MonoItem<'tcx>, Vec<MonoItem<'tcx>>>,
-}
-
-type MonoItems<'tcx> = Vec<Spanned<MonoItem<'tcx>>>;
+type MonoItems<'tcx> = Vec<MonoItemUse<'tcx>>;
 
 /// The state that is shared across the concurrent threads that are doing collection.
 struct SharedState<'tcx> {
@@ -286,45 +278,6 @@ enum CollectionMode {
     MentionedItems,
 }
 
-impl<'tcx> UsageMap<'tcx> {
-    fn new() -> UsageMap<'tcx> {
-        UsageMap { used_map: FxHashMap::default(), user_map: FxHashMap::default() }
-    }
-
-    fn record_used<'a>(
-        &mut self,
-        user_item: MonoItem<'tcx>,
-        used_items: &'a [Spanned<MonoItem<'tcx>>],
-    ) where
-        'tcx: 'a,
-    {
-        let used_items: Vec<_> = used_items.iter().map(|item| item.node).collect();
-        for &used_item in used_items.iter() {
-            self.user_map.entry(used_item).or_default().push(user_item);
-        }
-
-        assert!(self.used_map.insert(user_item, used_items).is_none());
-    }
-
-    pub fn get_user_items(&self, item: MonoItem<'tcx>) -> &[MonoItem<'tcx>] {
-        self.user_map.get(&item).map(|items| items.as_slice()).unwrap_or(&[])
-    }
-
-    /// Internally iterate over all inlined items used by `item`.
-    pub fn for_each_inlined_used_item<F>(&self, tcx: TyCtxt<'tcx>, item: MonoItem<'tcx>, mut f: F)
-    where
-        F: FnMut(MonoItem<'tcx>),
-    {
-        let used_items = self.used_map.get(&item).unwrap();
-        for used_item in used_items.iter() {
-            let is_inlined = used_item.instantiation_mode(tcx) == InstantiationMode::LocalCopy;
-            if is_inlined {
-                f(*used_item);
-            }
-        }
-    }
-}
-
 /// Collect all monomorphized items reachable from `starting_point`, and emit a note diagnostic if a
 /// post-monomorphization error is encountered during a collection step.
 ///
@@ -415,13 +368,14 @@ fn collect_items_rec<'tcx>(
                 }
 
                 if tcx.needs_thread_local_shim(def_id) {
-                    used_items.push(respan(
-                        starting_item.span,
-                        MonoItem::Fn(Instance {
+                    used_items.push(MonoItemUse {
+                        item: MonoItem::Fn(Instance {
                             def: InstanceDef::ThreadLocalShim(def_id),
                             args: GenericArgs::empty(),
                         }),
-                    ));
+                        kind: UseKind::Static,
+                        span: starting_item.span,
+                    });
                 }
             }
 
@@ -478,7 +432,11 @@ fn collect_items_rec<'tcx>(
                             let instance = Instance::mono(tcx, *def_id);
                             if should_codegen_locally(tcx, instance) {
                                 trace!("collecting static {:?}", def_id);
-                                used_items.push(dummy_spanned(MonoItem::Static(*def_id)));
+                                used_items.push(MonoItemUse {
+                                    item: MonoItem::Static(*def_id),
+                                    kind: UseKind::Static,
+                                    span: DUMMY_SP,
+                                });
                             }
                         }
                         hir::InlineAsmOperand::In { .. }
@@ -525,7 +483,7 @@ fn collect_items_rec<'tcx>(
         for used_item in used_items {
             collect_items_rec(
                 tcx,
-                used_item,
+                respan(used_item.span, used_item.item),
                 state,
                 recursion_depths,
                 recursion_limit,
@@ -539,7 +497,7 @@ fn collect_items_rec<'tcx>(
     for mentioned_item in mentioned_items {
         collect_items_rec(
             tcx,
-            mentioned_item,
+            respan(mentioned_item.span, mentioned_item.item),
             state,
             recursion_depths,
             recursion_limit,
@@ -774,7 +732,12 @@ fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
                     let instance =
                         Instance::resolve_closure(self.tcx, def_id, args, ty::ClosureKind::FnOnce);
                     if should_codegen_locally(self.tcx, instance) {
-                        self.used_items.push(create_fn_mono_item(self.tcx, instance, span));
+                        self.used_items.push(create_fn_mono_item(
+                            self.tcx,
+                            instance,
+                            UseKind::FnPointer,
+                            span,
+                        ));
                     }
                 } else {
                     bug!()
@@ -785,7 +748,11 @@ fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
                 let instance = Instance::mono(self.tcx, def_id);
                 if should_codegen_locally(self.tcx, instance) {
                     trace!("collecting thread-local static {:?}", def_id);
-                    self.used_items.push(respan(span, MonoItem::Static(def_id)));
+                    self.used_items.push(MonoItemUse {
+                        item: MonoItem::Static(def_id),
+                        kind: UseKind::Static,
+                        span,
+                    });
                 }
             }
             _ => { /* not interesting */ }
@@ -811,7 +778,7 @@ fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Loc
         let push_mono_lang_item = |this: &mut Self, lang_item: LangItem| {
             let instance = Instance::mono(tcx, tcx.require_lang_item(lang_item, Some(source)));
             if should_codegen_locally(tcx, instance) {
-                this.used_items.push(create_fn_mono_item(tcx, instance, source));
+                this.used_items.push(create_fn_mono_item(tcx, instance, UseKind::Call, source));
             }
         };
 
@@ -845,7 +812,11 @@ fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Loc
                             let instance = Instance::mono(self.tcx, def_id);
                             if should_codegen_locally(self.tcx, instance) {
                                 trace!("collecting asm sym static {:?}", def_id);
-                                self.used_items.push(respan(source, MonoItem::Static(def_id)));
+                                self.used_items.push(MonoItemUse {
+                                    item: MonoItem::Static(def_id),
+                                    kind: UseKind::Static,
+                                    span: source,
+                                });
                             }
                         }
                         _ => {}
@@ -900,7 +871,7 @@ fn visit_drop_use<'tcx>(
     output: &mut MonoItems<'tcx>,
 ) {
     let instance = Instance::resolve_drop_in_place(tcx, ty);
-    visit_instance_use(tcx, instance, is_direct_call, source, output);
+    visit_instance_use(tcx, instance, is_direct_call, UseKind::DropGlue, source, output);
 }
 
 /// For every call of this function in the visitor, make sure there is a matching call in the
@@ -921,7 +892,8 @@ fn visit_fn_use<'tcx>(
                 _ => bug!("failed to resolve instance for {ty}"),
             }
         };
-        visit_instance_use(tcx, instance, is_direct_call, source, output);
+        let kind = if is_direct_call { UseKind::Call } else { UseKind::FnPointer };
+        visit_instance_use(tcx, instance, is_direct_call, kind, source, output);
     }
 }
 
@@ -929,6 +901,7 @@ fn visit_instance_use<'tcx>(
     tcx: TyCtxt<'tcx>,
     instance: ty::Instance<'tcx>,
     is_direct_call: bool,
+    kind: UseKind,
     source: Span,
     output: &mut MonoItems<'tcx>,
 ) {
@@ -946,13 +919,13 @@ fn visit_instance_use<'tcx>(
             let def_id = tcx.lang_items().get(LangItem::PanicNounwind).unwrap();
             let panic_instance = Instance::mono(tcx, def_id);
             if should_codegen_locally(tcx, panic_instance) {
-                output.push(create_fn_mono_item(tcx, panic_instance, source));
+                output.push(create_fn_mono_item(tcx, panic_instance, UseKind::Call, source));
             }
         } else if tcx.has_attr(def_id, sym::rustc_intrinsic) {
             // Codegen the fallback body of intrinsics with fallback bodies
             let instance = ty::Instance::new(def_id, instance.args);
             if should_codegen_locally(tcx, instance) {
-                output.push(create_fn_mono_item(tcx, instance, source));
+                output.push(create_fn_mono_item(tcx, instance, kind, source));
             }
         }
     }
@@ -969,7 +942,7 @@ fn visit_instance_use<'tcx>(
         ty::InstanceDef::DropGlue(_, None) => {
             // Don't need to emit noop drop glue if we are calling directly.
             if !is_direct_call {
-                output.push(create_fn_mono_item(tcx, instance, source));
+                output.push(create_fn_mono_item(tcx, instance, kind, source));
             }
         }
         ty::InstanceDef::DropGlue(_, Some(_))
@@ -982,7 +955,7 @@ fn visit_instance_use<'tcx>(
         | ty::InstanceDef::FnPtrShim(..)
         | ty::InstanceDef::CloneShim(..)
         | ty::InstanceDef::FnPtrAddrShim(..) => {
-            output.push(create_fn_mono_item(tcx, instance, source));
+            output.push(create_fn_mono_item(tcx, instance, kind, source));
         }
     }
 }
@@ -1140,8 +1113,9 @@ fn find_vtable_types_for_unsizing<'tcx>(
 fn create_fn_mono_item<'tcx>(
     tcx: TyCtxt<'tcx>,
     instance: Instance<'tcx>,
+    kind: UseKind,
     source: Span,
-) -> Spanned<MonoItem<'tcx>> {
+) -> MonoItemUse<'tcx> {
     let def_id = instance.def_id();
     if tcx.sess.opts.unstable_opts.profile_closures
         && def_id.is_local()
@@ -1150,7 +1124,7 @@ fn create_fn_mono_item<'tcx>(
         crate::util::dump_closure_profile(tcx, instance);
     }
 
-    respan(source, MonoItem::Fn(instance.polymorphize(tcx)))
+    MonoItemUse { item: MonoItem::Fn(instance.polymorphize(tcx)), kind, span: source }
 }
 
 /// Creates a `MonoItem` for each method that is referenced by the vtable for
@@ -1189,7 +1163,7 @@ fn create_mono_items_for_vtable_methods<'tcx>(
                     Some(*instance).filter(|instance| should_codegen_locally(tcx, *instance))
                 }
             })
-            .map(|item| create_fn_mono_item(tcx, item, source));
+            .map(|item| create_fn_mono_item(tcx, item, UseKind::VtableMethod, source));
         output.extend(methods);
     }
 
@@ -1205,7 +1179,11 @@ fn collect_alloc<'tcx>(tcx: TyCtxt<'tcx>, alloc_id: AllocId, output: &mut MonoIt
             let instance = Instance::mono(tcx, def_id);
             if should_codegen_locally(tcx, instance) {
                 trace!("collecting static {:?}", def_id);
-                output.push(dummy_spanned(MonoItem::Static(def_id)));
+                output.push(MonoItemUse {
+                    item: MonoItem::Static(def_id),
+                    kind: UseKind::Static,
+                    span: DUMMY_SP,
+                });
             }
         }
         GlobalAlloc::Memory(alloc) => {
@@ -1223,7 +1201,7 @@ fn collect_alloc<'tcx>(tcx: TyCtxt<'tcx>, alloc_id: AllocId, output: &mut MonoIt
         GlobalAlloc::Function(fn_instance) => {
             if should_codegen_locally(tcx, fn_instance) {
                 trace!("collecting {:?} with {:#?}", alloc_id, fn_instance);
-                output.push(create_fn_mono_item(tcx, fn_instance, DUMMY_SP));
+                output.push(create_fn_mono_item(tcx, fn_instance, UseKind::FnPointer, DUMMY_SP));
             }
         }
         GlobalAlloc::VTable(ty, trait_ref) => {
@@ -1321,7 +1299,14 @@ fn visit_mentioned_item<'tcx>(
                 // for "mentioned" item collection.
                 // We can set `is_direct_call`; that just means we'll skip a bunch of shims that anyway
                 // can't have their own failing constants.
-                visit_instance_use(tcx, instance, /*is_direct_call*/ true, span, output);
+                visit_instance_use(
+                    tcx,
+                    instance,
+                    /*is_direct_call*/ true,
+                    UseKind::Call,
+                    span,
+                    output,
+                );
             }
         }
         MentionedItem::Drop(ty) => {
@@ -1344,7 +1329,7 @@ fn visit_mentioned_item<'tcx>(
                 let instance =
                     Instance::resolve_closure(tcx, def_id, args, ty::ClosureKind::FnOnce);
                 if should_codegen_locally(tcx, instance) {
-                    output.push(create_fn_mono_item(tcx, instance, span));
+                    output.push(create_fn_mono_item(tcx, instance, UseKind::FnPointer, span));
                 }
             } else {
                 bug!()
@@ -1407,22 +1392,24 @@ fn collect_roots(tcx: TyCtxt<'_>, mode: MonoItemCollectionStrategy) -> Vec<MonoI
     // We can only codegen items that are instantiable - items all of
     // whose predicates hold. Luckily, items that aren't instantiable
     // can't actually be used, so we can just skip codegenning them.
-    roots
-        .into_iter()
-        .filter_map(|Spanned { node: mono_item, .. }| {
-            mono_item.is_instantiable(tcx).then_some(mono_item)
-        })
-        .collect()
+    roots.into_iter().filter(|mono_item| mono_item.is_instantiable(tcx)).collect()
 }
 
 struct RootCollector<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
     strategy: MonoItemCollectionStrategy,
-    output: &'a mut MonoItems<'tcx>,
+    output: &'a mut Vec<MonoItem<'tcx>>,
     entry_fn: Option<(DefId, EntryFnType)>,
 }
 
 impl<'v> RootCollector<'_, 'v> {
+    /// Adds the items `collect` finds to the roots.
+    fn push_used_items(&mut self, collect: impl FnOnce(&mut MonoItems<'v>)) {
+        let mut used_items = MonoItems::new();
+        collect(&mut used_items);
+        self.output.extend(used_items.into_iter().map(|used_item| used_item.item));
+    }
+
     fn process_item(&mut self, id: hir::ItemId) {
         match self.tcx.def_kind(id.owner_id) {
             DefKind::Enum | DefKind::Struct | DefKind::Union => {
@@ -1432,7 +1419,8 @@ fn process_item(&mut self, id: hir::ItemId) {
                     debug!("RootCollector: ADT drop-glue for `{id:?}`",);
 
                     let ty = self.tcx.type_of(id.owner_id.to_def_id()).no_bound_vars().unwrap();
-                    visit_drop_use(self.tcx, ty, true, DUMMY_SP, self.output);
+                    let tcx = self.tcx;
+                    self.push_used_items(|output| visit_drop_use(tcx, ty, true, DUMMY_SP, output));
                 }
             }
             DefKind::GlobalAsm => {
@@ -1440,12 +1428,12 @@ fn process_item(&mut self, id: hir::ItemId) {
                     "RootCollector: ItemKind::GlobalAsm({})",
                     self.tcx.def_path_str(id.owner_id)
                 );
-                self.output.push(dummy_spanned(MonoItem::GlobalAsm(id)));
+                self.output.push(MonoItem::GlobalAsm(id));
             }
             DefKind::Static { .. } => {
                 let def_id = id.owner_id.to_def_id();
                 debug!("RootCollector: ItemKind::Static({})", self.tcx.def_path_str(def_id));
-                self.output.push(dummy_spanned(MonoItem::Static(def_id)));
+                self.output.push(MonoItem::Static(def_id));
             }
             DefKind::Const => {
                 // const items only generate mono items if they are
@@ -1453,7 +1441,8 @@ fn process_item(&mut self, id: hir::ItemId) {
 
                 // but even just declaring them must collect the items they refer to
                 if let Ok(val) = self.tcx.const_eval_poly(id.owner_id.to_def_id()) {
-                    collect_const_value(self.tcx, val, self.output);
+                    let tcx = self.tcx;
+                    self.push_used_items(|output| collect_const_value(tcx, val, output));
                 }
             }
             DefKind::Impl { .. } => {
@@ -1498,7 +1487,7 @@ fn push_if_root(&mut self, def_id: LocalDefId) {
             debug!("found root");
 
             let instance = Instance::mono(self.tcx, def_id.to_def_id());
-            self.output.push(create_fn_mono_item(self.tcx, instance, DUMMY_SP));
+            self.output.push(create_fn_mono_item(self.tcx, instance, UseKind::Call, DUMMY_SP).item);
         }
     }
 
@@ -1534,7 +1523,8 @@ fn push_extra_entry_roots(&mut self) {
             self.tcx.mk_args(&[main_ret_ty.into()]),
         );
 
-        self.output.push(create_fn_mono_item(self.tcx, start_instance, DUMMY_SP));
+        let start_item = create_fn_mono_item(self.tcx, start_instance, UseKind::Call, DUMMY_SP);
+        self.output.push(start_item.item);
     }
 }
 
@@ -1542,7 +1532,7 @@ fn push_extra_entry_roots(&mut self) {
 fn create_mono_items_for_default_impls<'tcx>(
     tcx: TyCtxt<'tcx>,
     item: hir::ItemId,
-    output: &mut MonoItems<'tcx>,
+    output: &mut Vec<MonoItem<'tcx>>,
 ) {
     let Some(impl_) = tcx.impl_trait_header(item.owner_id) else {
         return;
@@ -1604,8 +1594,8 @@ fn create_mono_items_for_default_impls<'tcx>(
         let args = trait_ref.args.extend_to(tcx, method.def_id, only_region_params);
         let instance = ty::Instance::expect_resolve(tcx, param_env, method.def_id, args);
 
-        let mono_item = create_fn_mono_item(tcx, instance, DUMMY_SP);
-        if mono_item.node.is_instantiable(tcx) && should_codegen_locally(tcx, instance) {
+        let mono_item = create_fn_mono_item(tcx, instance, UseKind::Call, DUMMY_SP).item;
+        if mono_item.is_instantiable(tcx) && should_codegen_locally(tcx, instance) {
             output.push(mono_item);
         }
     }
@@ -1631,7 +1621,7 @@ pub fn collect_crate_mono_items(
     let mut state = SharedState {
         visited: MTLock::new(FxHashSet::default()),
         mentioned: MTLock::new(FxHashSet::default()),
-        usage_map: MTLock::new(UsageMap::new()),
+        usage_map: MTLock::new(UsageMap::new(roots.clone())),
     };
     let recursion_limit = tcx.recursion_limit();
 
@@ -104,3 +104,19 @@ pub struct EncounteredErrorWhileInstantiating {
 pub struct UnknownCguCollectionMode<'a> {
     pub mode: &'a str,
 }
+
+#[derive(Diagnostic)]
+#[diag(monomorphize_codegen_unit_hints_ignored)]
+#[note]
+#[help]
+pub struct CodegenUnitHintsIgnored {
+    pub max_codegen_units: usize,
+}
+
+#[derive(Diagnostic)]
+#[diag(monomorphize_optimize_levels_ignored)]
+#[note]
+#[help]
+pub struct OptimizeLevelsIgnored {
+    pub max_codegen_units: usize,
+}
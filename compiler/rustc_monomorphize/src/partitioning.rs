@@ -96,6 +96,7 @@
 use std::collections::hash_map::Entry;
 use std::fs::{self, File};
 use std::io::{BufWriter, Write};
+use std::mem;
 use std::path::{Path, PathBuf};
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
@@ -107,18 +108,20 @@
 use rustc_middle::middle::exported_symbols::{SymbolExportInfo, SymbolExportLevel};
 use rustc_middle::mir::mono::{
     CodegenUnit, CodegenUnitNameBuilder, InstantiationMode, Linkage, MonoItem, MonoItemData,
-    Visibility,
+    UsageMap, Visibility,
 };
 use rustc_middle::query::Providers;
 use rustc_middle::ty::print::{characteristic_def_id_of_type, with_no_trimmed_paths};
 use rustc_middle::ty::{self, visit::TypeVisitableExt, InstanceDef, TyCtxt};
-use rustc_session::config::{DumpMonoStatsFormat, SwitchWithOptPath};
+use rustc_session::config::{DumpMonoStatsFormat, OptLevel, SwitchWithOptPath};
 use rustc_session::CodegenUnits;
-use rustc_span::symbol::Symbol;
+use rustc_span::symbol::{sym, Symbol};
 
-use crate::collector::UsageMap;
 use crate::collector::{self, MonoItemCollectionStrategy};
-use crate::errors::{CouldntDumpMonoStats, SymbolAlreadyDefined, UnknownCguCollectionMode};
+use crate::errors::{
+    CodegenUnitHintsIgnored, CouldntDumpMonoStats, OptimizeLevelsIgnored, SymbolAlreadyDefined,
+    UnknownCguCollectionMode,
+};
 
 struct PartitioningCx<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
@@ -130,6 +133,9 @@ struct PlacedMonoItems<'tcx> {
     codegen_units: Vec<CodegenUnit<'tcx>>,
 
     internalization_candidates: FxHashSet<MonoItem<'tcx>>,
+
+    /// The codegen units named by `#[codegen_unit]` attributes.
+    pinned_codegen_units: FxHashSet<Symbol>,
 }
 
 // The output CGUs are sorted by name.
@@ -147,7 +153,7 @@ fn partition<'tcx, I>(
 
     // Place all mono items into a codegen unit. `place_mono_items` is
     // responsible for initializing the CGU size estimates.
-    let PlacedMonoItems { mut codegen_units, internalization_candidates } = {
+    let PlacedMonoItems { mut codegen_units, internalization_candidates, pinned_codegen_units } = {
         let _prof_timer = tcx.prof.generic_activity("cgu_partitioning_place_items");
         let placed = place_mono_items(cx, mono_items);
 
@@ -161,7 +167,7 @@ fn partition<'tcx, I>(
     // estimates.
     {
         let _prof_timer = tcx.prof.generic_activity("cgu_partitioning_merge_cgus");
-        merge_codegen_units(cx, &mut codegen_units);
+        merge_codegen_units(cx, &mut codegen_units, &pinned_codegen_units);
         debug_dump(tcx, "MERGE", &codegen_units);
     }
 
@@ -198,6 +204,7 @@ fn place_mono_items<'tcx, I>(cx: &PartitioningCx<'_, 'tcx>, mono_items: I) -> Pl
     let mut codegen_units = FxHashMap::default();
     let is_incremental_build = cx.tcx.sess.opts.incremental.is_some();
     let mut internalization_candidates = FxHashSet::default();
+    let mut pinned_codegen_units = FxHashSet::default();
 
     // Determine if monomorphizations instantiated in this crate will be made
     // available to downstream crates. This depends on whether we are in
@@ -226,18 +233,30 @@ fn place_mono_items<'tcx, I>(cx: &PartitioningCx<'_, 'tcx>, mono_items: I) -> Pl
         let characteristic_def_id = characteristic_def_id_of_mono_item(cx.tcx, mono_item);
         let is_volatile = is_incremental_build && mono_item.is_generic_fn(cx.tcx);
 
-        let cgu_name = match characteristic_def_id {
-            Some(def_id) => compute_codegen_unit_name(
-                cx.tcx,
-                cgu_name_builder,
-                def_id,
-                is_volatile,
-                cgu_name_cache,
-            ),
-            None => fallback_cgu_name(cgu_name_builder),
+        let opt_level = codegen_unit_opt_level(cx.tcx, mono_item);
+        let cgu_name = if let Some(opt_level) = opt_level {
+            let components = ["opt-level", opt_level_name(opt_level)];
+            cgu_name_builder.build_cgu_name_no_mangle(LOCAL_CRATE, &components, Some("optimize"))
+        } else if let Some(name) = codegen_unit_hint(cx.tcx, mono_item.def_id()) {
+            let cgu_name =
+                cgu_name_builder.build_cgu_name_no_mangle(LOCAL_CRATE, &[name], Some("pinned"));
+            pinned_codegen_units.insert(cgu_name);
+            cgu_name
+        } else {
+            match characteristic_def_id {
+                Some(def_id) => compute_codegen_unit_name(
+                    cx.tcx,
+                    cgu_name_builder,
+                    def_id,
+                    is_volatile,
+                    cgu_name_cache,
+                ),
+                None => fallback_cgu_name(cgu_name_builder),
+            }
         };
 
         let cgu = codegen_units.entry(cgu_name).or_insert_with(|| CodegenUnit::new(cgu_name));
+        cgu.set_opt_level(opt_level);
 
         let mut can_be_internalized = true;
         let (linkage, visibility) = mono_item_linkage_and_visibility(
@@ -289,7 +308,7 @@ fn place_mono_items<'tcx, I>(cx: &PartitioningCx<'_, 'tcx>, mono_items: I) -> Pl
         cgu.compute_size_estimate();
     }
 
-    return PlacedMonoItems { codegen_units, internalization_candidates };
+    return PlacedMonoItems { codegen_units, internalization_candidates, pinned_codegen_units };
 
     fn get_reachable_inlined_items<'tcx>(
         tcx: TyCtxt<'tcx>,
@@ -311,12 +330,53 @@ fn get_reachable_inlined_items<'tcx>(
 fn merge_codegen_units<'tcx>(
     cx: &PartitioningCx<'_, 'tcx>,
     codegen_units: &mut Vec<CodegenUnit<'tcx>>,
+    pinned_codegen_units: &FxHashSet<Symbol>,
 ) {
     assert!(cx.tcx.sess.codegen_units().as_usize() >= 1);
 
     // A sorted order here ensures merging is deterministic.
     assert!(codegen_units.is_sorted_by(|a, b| a.name().as_str() <= b.name().as_str()));
 
+    // The CGUs named by `#[codegen_unit]` are neither merged nor renamed, so that they keep the
+    // items pinned to them, and only them. Neither are the ones optimized at another level than
+    // the rest of the crate. They still count towards the maximum number of CGUs.
+    let (pinned, unpinned) = mem::take(codegen_units).into_iter().partition::<Vec<_>, _>(|cgu| {
+        pinned_codegen_units.contains(&cgu.name()) || cgu.opt_level().is_some()
+    });
+
+    // The other items need at least one CGU of their own, so the pinned CGUs can only be kept if
+    // that still fits in the maximum number of CGUs. Otherwise, the hints are ignored.
+    let max_codegen_units = cx.tcx.sess.codegen_units().as_usize();
+    if pinned.len() + usize::from(!unpinned.is_empty()) > max_codegen_units {
+        if pinned.iter().any(|cgu| cgu.opt_level().is_none()) {
+            cx.tcx.dcx().emit_warn(CodegenUnitHintsIgnored { max_codegen_units });
+        }
+        if pinned.iter().any(|cgu| cgu.opt_level().is_some()) {
+            cx.tcx.dcx().emit_warn(OptimizeLevelsIgnored { max_codegen_units });
+        }
+        *codegen_units = pinned;
+        codegen_units.extend(unpinned);
+        for cgu in codegen_units.iter_mut() {
+            cgu.set_opt_level(None);
+        }
+        merge_unpinned_codegen_units(cx, codegen_units, 0);
+    } else {
+        *codegen_units = unpinned;
+        if !codegen_units.is_empty() {
+            merge_unpinned_codegen_units(cx, codegen_units, pinned.len());
+        }
+        codegen_units.extend(pinned);
+    }
+    codegen_units.sort_by(|a, b| a.name().as_str().cmp(b.name().as_str()));
+}
+
+// Merges the CGUs down to the maximum number of CGUs, minus the `pinned_count` ones which are
+// kept as is, and renames them.
+fn merge_unpinned_codegen_units<'tcx>(
+    cx: &PartitioningCx<'_, 'tcx>,
+    codegen_units: &mut Vec<CodegenUnit<'tcx>>,
+    pinned_count: usize,
+) {
     // This map keeps track of what got merged into what.
     let mut cgu_contents: FxHashMap<Symbol, Vec<Symbol>> =
         codegen_units.iter().map(|cgu| (cgu.name(), vec![cgu.name()])).collect();
@@ -335,7 +395,7 @@ fn merge_codegen_units<'tcx>(
     // getting any bigger, if we can avoid it. When we have more than N CGUs
     // then at least one of the biggest N will have to grow. codegen_units[N-1]
     // is the smallest of those, and so has the most room to grow.
-    let max_codegen_units = cx.tcx.sess.codegen_units().as_usize();
+    let max_codegen_units = cx.tcx.sess.codegen_units().as_usize() - pinned_count;
     while codegen_units.len() > max_codegen_units {
         // Sort small CGUs to the back.
         codegen_units.sort_by_key(|cgu| cmp::Reverse(cgu.size_estimate()));
@@ -718,6 +778,49 @@ fn compute_codegen_unit_name(
     })
 }
 
+/// Returns the name given by the innermost `#[codegen_unit]` attribute on the local item `def_id`
+/// or the items containing it, to place its code in the codegen unit of that name.
+fn codegen_unit_hint(tcx: TyCtxt<'_>, def_id: DefId) -> Option<Symbol> {
+    let mut current = def_id.as_local()?;
+    loop {
+        if let Some(attr) = tcx.get_attr(current, sym::codegen_unit) {
+            return attr.value_str();
+        }
+        current = tcx.opt_local_parent(current)?;
+    }
+}
+
+/// Returns the optimization level set by the `#[optimize(level = "...")]` attribute of the function
+/// of `mono_item`, when LLVM must optimize it at another level than the rest of the crate, in a
+/// codegen unit of its own. The functions which aren't optimized only need the `optnone`
+/// attribute, and in optimized crates, neither do the ones optimized for size need more than the
+/// `optsize` and `minsize` attributes.
+fn codegen_unit_opt_level<'tcx>(tcx: TyCtxt<'tcx>, mono_item: MonoItem<'tcx>) -> Option<OptLevel> {
+    let MonoItem::Fn(ty::Instance { def: InstanceDef::Item(def_id), .. }) = mono_item else {
+        return None;
+    };
+    if !tcx.def_kind(def_id).has_codegen_attrs() {
+        return None;
+    }
+    let session_level = tcx.sess.opts.optimize;
+    match tcx.codegen_fn_attrs(def_id).optimize.opt_level()? {
+        OptLevel::No => None,
+        OptLevel::Size | OptLevel::SizeMin if session_level != OptLevel::No => None,
+        level => (level != session_level).then_some(level),
+    }
+}
+
+fn opt_level_name(opt_level: OptLevel) -> &'static str {
+    match opt_level {
+        OptLevel::No => "0",
+        OptLevel::Less => "1",
+        OptLevel::Default => "2",
+        OptLevel::Aggressive => "3",
+        OptLevel::Size => "s",
+        OptLevel::SizeMin => "z",
+    }
+}
+
 // Anything we can't find a proper codegen unit for goes into this.
 fn fallback_cgu_name(name_builder: &mut CodegenUnitNameBuilder<'_>) -> Symbol {
     name_builder.build_cgu_name(LOCAL_CRATE, &["fallback"], Some("cgu"))
@@ -1086,7 +1189,7 @@ fn assert_symbols_are_distinct<'a, 'tcx, I>(tcx: TyCtxt<'tcx>, mono_items: I)
     }
 }
 
-fn collect_and_partition_mono_items(tcx: TyCtxt<'_>, (): ()) -> (&DefIdSet, &[CodegenUnit<'_>]) {
+fn collect_mono_items(tcx: TyCtxt<'_>, (): ()) -> (FxHashSet<MonoItem<'_>>, UsageMap<'_>) {
     let collection_strategy = match tcx.sess.opts.unstable_opts.print_mono_items {
         Some(ref s) => {
             let mode = s.to_lowercase();
@@ -1110,7 +1213,11 @@ fn collect_and_partition_mono_items(tcx: TyCtxt<'_>, (): ()) -> (&DefIdSet, &[Co
         }
     };
 
-    let (items, usage_map) = collector::collect_crate_mono_items(tcx, collection_strategy);
+    collector::collect_crate_mono_items(tcx, collection_strategy)
+}
+
+fn collect_and_partition_mono_items(tcx: TyCtxt<'_>, (): ()) -> (&DefIdSet, &[CodegenUnit<'_>]) {
+    let (items, usage_map) = tcx.collect_mono_items(());
 
     // If there was an error during collection (e.g. from one of the constants we evaluated),
     // then we stop here. This way codegen does not have to worry about failing constants.
@@ -1120,7 +1227,7 @@ fn collect_and_partition_mono_items(tcx: TyCtxt<'_>, (): ()) -> (&DefIdSet, &[Co
     let (codegen_units, _) = tcx.sess.time("partition_and_assert_distinct_symbols", || {
         sync::join(
             || {
-                let mut codegen_units = partition(tcx, items.iter().copied(), &usage_map);
+                let mut codegen_units = partition(tcx, items.iter().copied(), usage_map);
                 codegen_units[0].make_primary();
                 &*tcx.arena.alloc_from_iter(codegen_units)
             },
@@ -1208,9 +1315,56 @@ fn collect_and_partition_mono_items(tcx: TyCtxt<'_>, (): ()) -> (&DefIdSet, &[Co
         }
     }
 
+    if let Some(ref filter) = tcx.sess.opts.unstable_opts.print_mono_item_paths {
+        print_mono_item_paths(tcx, items, usage_map, filter);
+    }
+
     (tcx.arena.alloc(mono_items), codegen_units)
 }
 
+/// Prints the shortest chain of uses from a root to each mono item whose name contains `filter`,
+/// with how and where each item of the chain uses the next one.
+fn print_mono_item_paths<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    items: &FxHashSet<MonoItem<'tcx>>,
+    usage_map: &UsageMap<'tcx>,
+    filter: &str,
+) {
+    let mut matching: Vec<_> = items
+        .iter()
+        .map(|item| (with_no_trimmed_paths!(item.to_string()), *item))
+        .filter(|(name, _)| name.contains(filter))
+        .collect();
+    if matching.is_empty() {
+        return;
+    }
+    matching.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let first_uses = usage_map.first_uses_from_roots();
+    let source_map = tcx.sess.source_map();
+    for (name, item) in matching {
+        let mut path = vec![];
+        let mut current = item;
+        while let Some(&(user, used)) = first_uses.get(&current) {
+            path.push(used);
+            current = user;
+        }
+
+        println!("MONO_ITEM_PATH {name}");
+        println!("    {} (root)", with_no_trimmed_paths!(current.to_string()));
+        for used in path.iter().rev() {
+            let item = with_no_trimmed_paths!(used.item.to_string());
+            let kind = used.kind.descr();
+            if used.span.is_dummy() {
+                println!("    {item} ({kind})");
+            } else {
+                let location = source_map.span_to_embeddable_string(used.span);
+                println!("    {item} ({kind} @ {location})");
+            }
+        }
+    }
+}
+
 /// Outputs stats about instantiation counts and estimated size, per `MonoItem`'s
 /// def, to a file in the given output directory.
 fn dump_mono_items_stats<'tcx>(
@@ -1290,6 +1444,7 @@ struct MonoItem {
 }
 
 pub fn provide(providers: &mut Providers) {
+    providers.collect_mono_items = collect_mono_items;
     providers.collect_and_partition_mono_items = collect_and_partition_mono_items;
 
     providers.is_codegened_item = |tcx, def_id| {
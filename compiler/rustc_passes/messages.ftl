@@ -97,6 +97,14 @@ passes_cold =
     .warn = {-passes_previously_accepted}
     .label = {passes_should_be_applied_to_fn.label}
 
+passes_codegen_unit =
+    `codegen_unit` attribute should be applied to a function, static, module or implementation
+    .label = not a function, static, module or implementation
+
+passes_codegen_unit_name =
+    invalid codegen unit name `{$name}`
+    .note = codegen unit names can only contain ASCII letters, digits, `_` and `-`
+
 passes_collapse_debuginfo =
     `collapse_debuginfo` attribute should be applied to macro definitions
     .label = not a macro definition
@@ -181,6 +181,7 @@ fn check_attributes(
                     self.check_cmse_nonsecure_entry(hir_id, attr, span, target)
                 }
                 sym::collapse_debuginfo => self.check_collapse_debuginfo(attr, span, target),
+                sym::codegen_unit => self.check_codegen_unit(attr, span, target),
                 sym::must_not_suspend => self.check_must_not_suspend(attr, span, target),
                 sym::must_use => self.check_must_use(hir_id, attr, target),
                 sym::rustc_pass_by_value => self.check_pass_by_value(attr, span, target),
@@ -505,6 +506,32 @@ fn check_collapse_debuginfo(&self, attr: &Attribute, span: Span, target: Target)
         }
     }
 
+    /// Checks if `#[codegen_unit]` is applied to an item containing code, and names a codegen unit
+    /// which can be used in file names.
+    fn check_codegen_unit(&self, attr: &Attribute, span: Span, target: Target) -> bool {
+        match target {
+            Target::Fn
+            | Target::Method(MethodKind::Inherent | MethodKind::Trait { body: true })
+            | Target::Static
+            | Target::Mod
+            | Target::Impl => {}
+            _ => {
+                self.dcx().emit_err(errors::CodegenUnit { attr_span: attr.span, defn_span: span });
+                return false;
+            }
+        }
+        let Some(name) = attr.value_str() else {
+            // The attribute template is checked elsewhere.
+            return false;
+        };
+        let is_valid = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-';
+        if name.as_str().is_empty() || !name.as_str().chars().all(is_valid) {
+            self.dcx().emit_err(errors::CodegenUnitName { span: attr.span, name });
+            return false;
+        }
+        true
+    }
+
     /// Checks if a `#[track_caller]` is applied to a non-naked function. Returns `true` if valid.
     fn check_track_caller(
         &self,
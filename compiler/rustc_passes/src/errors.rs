@@ -777,6 +777,24 @@ pub struct RustcLintOptDenyFieldAccess {
     pub span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(passes_codegen_unit)]
+pub struct CodegenUnit {
+    #[primary_span]
+    pub attr_span: Span,
+    #[label]
+    pub defn_span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(passes_codegen_unit_name)]
+#[note]
+pub struct CodegenUnitName {
+    #[primary_span]
+    pub span: Span,
+    pub name: Symbol,
+}
+
 #[derive(Diagnostic)]
 #[diag(passes_collapse_debuginfo)]
 pub struct CollapseDebuginfo {
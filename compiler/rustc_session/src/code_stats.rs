@@ -4,6 +4,7 @@
 use rustc_span::Symbol;
 use rustc_target::abi::{Align, Size};
 use std::cmp;
+use std::time::Duration;
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct VariantInfo {
@@ -89,10 +90,37 @@ pub struct VTableSizeInfo {
     pub upcasting_cost_percent: f64,
 }
 
+/// The size of a MIR body, recorded for `-Z mir-pass-stats`.
+#[derive(Copy, Clone)]
+pub struct MirBodySize {
+    pub basic_blocks: usize,
+    pub statements: usize,
+    pub locals: usize,
+}
+
+/// A run of a MIR pass on a body, recorded for `-Z mir-pass-stats`.
+pub struct MirPassInfo {
+    pub pass_name: &'static str,
+    /// The path of the body, including the promoted and shim it is, if any.
+    pub body: String,
+    pub duration: Duration,
+
+    /// The size of the body before the pass.
+    pub before: MirBodySize,
+    /// The size of the body after the pass.
+    pub after: MirBodySize,
+    /// The number of statements after the pass which weren't in the body before it. A statement
+    /// changed by the pass counts as both added and removed, a moved one as neither.
+    pub statements_added: usize,
+    /// The number of statements before the pass which aren't in the body after it.
+    pub statements_removed: usize,
+}
+
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
     vtable_sizes: Lock<FxHashMap<DefId, VTableSizeInfo>>,
+    mir_passes: Lock<Vec<MirPassInfo>>,
 }
 
 impl CodeStats {
@@ -134,6 +162,15 @@ pub fn record_vtable_size(&self, trait_did: DefId, trait_name: &str, info: VTabl
         );
     }
 
+    pub fn record_mir_pass(&self, info: MirPassInfo) {
+        self.mir_passes.lock().push(info);
+    }
+
+    /// Takes the recorded runs of MIR passes, in the order they ran on each body.
+    pub fn take_mir_passes(&self) -> Vec<MirPassInfo> {
+        std::mem::take(&mut *self.mir_passes.lock())
+    }
+
     pub fn print_type_sizes(&self) {
         let type_sizes = self.type_sizes.borrow();
         // We will soon sort, so the initial order does not matter.
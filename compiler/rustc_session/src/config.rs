@@ -34,9 +34,11 @@
 use std::sync::LazyLock;
 
 mod cfg;
+mod lint_config;
 pub mod sigpipe;
 
 pub use cfg::{Cfg, CheckCfg, ExpectedValues};
+pub use lint_config::{LintConfig, ModuleLintLevels};
 
 /// The different settings that the `-C strip` flag can have.
 #[derive(Clone, Copy, PartialEq, Hash, Debug)]
@@ -559,6 +561,9 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// A single SARIF 2.1.0 log, written once compilation finishes, for consumption by code
+    /// scanning tools.
+    Sarif,
 }
 
 impl Default for ErrorOutputType {
@@ -757,6 +762,12 @@ pub enum PrintKind {
     LinkArgs,
     SplitDebuginfo,
     DeploymentTarget,
+    Lints,
+    LintsJson,
+    UnstableFeatures,
+    UnstableFeaturesJson,
+    CrateInfo,
+    CrateInfoJson,
 }
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
@@ -1079,6 +1090,7 @@ fn default() -> Options {
             debuginfo_compression: DebugInfoCompression::None,
             lint_opts: Vec::new(),
             lint_cap: None,
+            lint_config: None,
             describe_lints: false,
             output_types: OutputTypes(BTreeMap::new()),
             search_paths: vec![],
@@ -1110,6 +1122,7 @@ fn default() -> Options {
             json_future_incompat: false,
             pretty: None,
             working_dir: RealFileName::LocalPath(std::env::current_dir().unwrap()),
+            perturbed_build: false,
             color: ColorConfig::Auto,
             logical_env: FxIndexMap::default(),
             verbose: false,
@@ -1360,7 +1373,7 @@ pub(crate) fn flagmulti_s(a: S, b: S, c: S) -> R {
         stable(longer(a, b), move |opts| opts.optflagmulti(a, b, c))
     }
 
-    fn opt(a: S, b: S, c: S, d: S) -> R {
+    pub(crate) fn opt(a: S, b: S, c: S, d: S) -> R {
         unstable(longer(a, b), move |opts| opts.optopt(a, b, c, d))
     }
     pub(crate) fn multi(a: S, b: S, c: S, d: S) -> R {
@@ -1425,7 +1438,8 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
             "[crate-name|file-names|sysroot|target-libdir|cfg|calling-conventions|\
              target-list|target-cpus|target-features|relocation-models|code-models|\
              tls-models|target-spec-json|all-target-specs-json|native-static-libs|\
-             stack-protector-strategies|link-args|deployment-target]",
+             stack-protector-strategies|link-args|deployment-target|lints|lints-json|\
+             unstable-features|unstable-features-json|crate-info|crate-info-json]",
         ),
         opt::flagmulti_s("g", "", "Equivalent to -C debuginfo=2"),
         opt::flagmulti_s("O", "", "Equivalent to -C opt-level=2"),
@@ -1509,6 +1523,13 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "FROM=TO",
         ),
         opt::multi("", "env-set", "Inject an environment variable", "VAR=VALUE"),
+        opt::opt("", "lint-config", "Read lint levels from a TOML file", "PATH"),
+        opt::opt(
+            "",
+            "server",
+            "Run as a compiler server accepting compilation requests on a local socket",
+            "SOCKET",
+        ),
     ]);
     opts
 }
@@ -1545,6 +1566,25 @@ pub fn get_cmd_lint_options(
     (lint_opts, describe_lints, lint_cap)
 }
 
+/// Reads the `--lint-config` file, if any.
+pub fn parse_lint_config(
+    early_dcx: &EarlyDiagCtxt,
+    matches: &getopts::Matches,
+) -> Option<LintConfig> {
+    let path = PathBuf::from(matches.opt_str("lint-config")?);
+    let src = fs::read_to_string(&path).unwrap_or_else(|e| {
+        early_dcx.early_fatal(format!(
+            "failed to read the lint configuration file `{}`: {e}",
+            path.display()
+        ))
+    });
+    let display = path.display().to_string();
+    let config = LintConfig::parse(path, &src).unwrap_or_else(|e| {
+        early_dcx.early_fatal(format!("invalid lint configuration file `{display}`: {e}"))
+    });
+    Some(config)
+}
+
 /// Parses the `--color` flag.
 pub fn parse_color(early_dcx: &EarlyDiagCtxt, matches: &getopts::Matches) -> ColorConfig {
     match matches.opt_str("color").as_deref() {
@@ -1658,6 +1698,7 @@ pub fn parse_error_format(
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
+            Some("sarif") => ErrorOutputType::Sarif,
 
             Some(arg) => {
                 early_dcx.abort_if_error_and_set_error_format(ErrorOutputType::HumanReadable(
@@ -1729,6 +1770,9 @@ fn check_error_format_stability(
         {
             early_dcx.early_fatal("`--error-format=human-annotate-rs` is unstable");
         }
+        if let ErrorOutputType::Sarif = error_format {
+            early_dcx.early_fatal("`--error-format=sarif` is unstable");
+        }
     }
 }
 
@@ -1833,10 +1877,14 @@ fn collect_print_requests(
         ("calling-conventions", PrintKind::CallingConventions),
         ("cfg", PrintKind::Cfg),
         ("code-models", PrintKind::CodeModels),
+        ("crate-info", PrintKind::CrateInfo),
+        ("crate-info-json", PrintKind::CrateInfoJson),
         ("crate-name", PrintKind::CrateName),
         ("deployment-target", PrintKind::DeploymentTarget),
         ("file-names", PrintKind::FileNames),
         ("link-args", PrintKind::LinkArgs),
+        ("lints", PrintKind::Lints),
+        ("lints-json", PrintKind::LintsJson),
         ("native-static-libs", PrintKind::NativeStaticLibs),
         ("relocation-models", PrintKind::RelocationModels),
         ("split-debuginfo", PrintKind::SplitDebuginfo),
@@ -1848,6 +1896,8 @@ fn collect_print_requests(
         ("target-list", PrintKind::TargetList),
         ("target-spec-json", PrintKind::TargetSpec),
         ("tls-models", PrintKind::TlsModels),
+        ("unstable-features", PrintKind::UnstableFeatures),
+        ("unstable-features-json", PrintKind::UnstableFeaturesJson),
         // tidy-alphabetical-end
     ];
 
@@ -1861,25 +1911,21 @@ fn collect_print_requests(
         let (req, out) = split_out_file_name(&req);
 
         let kind = match PRINT_KINDS.iter().find(|&&(name, _)| name == req) {
-            Some((_, PrintKind::TargetSpec)) => {
-                if unstable_opts.unstable_options {
-                    PrintKind::TargetSpec
-                } else {
-                    early_dcx.early_fatal(
-                        "the `-Z unstable-options` flag must also be passed to \
-                         enable the target-spec-json print option",
-                    );
-                }
-            }
-            Some((_, PrintKind::AllTargetSpecs)) => {
-                if unstable_opts.unstable_options {
-                    PrintKind::AllTargetSpecs
-                } else {
-                    early_dcx.early_fatal(
-                        "the `-Z unstable-options` flag must also be passed to \
-                         enable the all-target-specs-json print option",
-                    );
-                }
+            Some((
+                _,
+                PrintKind::TargetSpec
+                | PrintKind::AllTargetSpecs
+                | PrintKind::Lints
+                | PrintKind::LintsJson
+                | PrintKind::UnstableFeatures
+                | PrintKind::UnstableFeaturesJson
+                | PrintKind::CrateInfo
+                | PrintKind::CrateInfoJson,
+            )) if !unstable_opts.unstable_options => {
+                early_dcx.early_fatal(format!(
+                    "the `-Z unstable-options` flag must also be passed to \
+                     enable the {req} print option",
+                ));
             }
             Some(&(_, print_kind)) => print_kind,
             None => {
@@ -2324,6 +2370,7 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
 
     let mut unstable_opts = UnstableOptions::build(early_dcx, matches);
     let (lint_opts, describe_lints, lint_cap) = get_cmd_lint_options(early_dcx, matches);
+    let lint_config = parse_lint_config(early_dcx, matches);
 
     check_error_format_stability(early_dcx, &unstable_opts, error_format);
 
@@ -2566,6 +2613,7 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
         debuginfo_compression,
         lint_opts,
         lint_cap,
+        lint_config,
         describe_lints,
         output_types,
         search_paths,
@@ -2597,6 +2645,7 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
         json_future_incompat,
         pretty,
         working_dir,
+        perturbed_build: false,
         color,
         logical_env,
         verbose,
@@ -2866,10 +2915,10 @@ pub enum WasiExecModel {
 pub(crate) mod dep_tracking {
     use super::{
         BranchProtection, CFGuard, CFProtection, CollapseMacroDebuginfo, CoverageOptions,
-        CrateType, DebugInfo, DebugInfoCompression, ErrorOutputType, FunctionReturn,
-        InliningThreshold, InstrumentCoverage, InstrumentXRay, LinkerPluginLto, LocationDetail,
-        LtoCli, NextSolverConfig, OomStrategy, OptLevel, OutFileName, OutputType, OutputTypes,
-        Polonius, RemapPathScopeComponents, ResolveDocLinks, SourceFileHashAlgorithm,
+        CrateType, DebugInfo, DebugInfoCompression, DepInfoFormat, ErrorOutputType, FunctionReturn,
+        InliningThreshold, InstrumentCoverage, InstrumentXRay, LinkerPluginLto, LintConfig,
+        LocationDetail, LtoCli, NextSolverConfig, OomStrategy, OptLevel, OutFileName, OutputType,
+        OutputTypes, Polonius, RemapPathScopeComponents, ResolveDocLinks, SourceFileHashAlgorithm,
         SplitDwarfKind, SwitchWithOptPath, SymbolManglingVersion, WasiExecModel,
     };
     use crate::lint;
@@ -2934,6 +2983,7 @@ fn hash(
         String,
         PathBuf,
         lint::Level,
+        LintConfig,
         WasiExecModel,
         u32,
         RelocModel,
@@ -2950,6 +3000,7 @@ fn hash(
         LtoCli,
         DebugInfo,
         DebugInfoCompression,
+        DepInfoFormat,
         CollapseMacroDebuginfo,
         UnstableFeatures,
         NativeLib,
@@ -3151,6 +3202,16 @@ pub fn extension(self) -> &'static str {
     }
 }
 
+/// Which format to use for the dep-info file of `--emit=dep-info`.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum DepInfoFormat {
+    /// A Makefile fragment.
+    Makefile,
+    /// A JSON object also holding the content hash of the inputs, and the SVH of the extern
+    /// crates.
+    Json,
+}
+
 /// `-Zpolonius` values, enabling the borrow checker polonius analysis, and which version: legacy,
 /// or future prototype.
 #[derive(Clone, Copy, PartialEq, Hash, Debug, Default)]
@@ -0,0 +1,245 @@
+//! The `--lint-config` file, which sets lint levels for the whole crate and for the modules
+//! matching path patterns, instead of `-A`/`-W`/`-D`/`-F` flags and lint attributes.
+//!
+//! The file is written in a subset of TOML:
+//!
+//! ```toml
+//! # Levels for the whole crate, applied before the command-line flags.
+//! [lints]
+//! unsafe_code = "deny"
+//! "clippy::pedantic" = "warn"
+//!
+//! # Levels for the `crate::ffi` module and everything in it, applied before its attributes.
+//! [modules."crate::ffi::*"]
+//! unsafe_code = "allow"
+//! ```
+//!
+//! A module pattern is a path starting with `crate`, in which `*` matches any module name. As lint
+//! levels set on a module apply to everything in it, a trailing `::*` makes no difference and can
+//! be used for readability. When several tables match a module, their levels are applied in the
+//! order the tables appear in the file.
+
+use crate::lint::Level;
+use rustc_span::symbol::{kw, Symbol};
+use std::iter;
+use std::path::PathBuf;
+
+/// The parsed `--lint-config` file.
+#[derive(Clone, Debug, Hash)]
+pub struct LintConfig {
+    pub path: PathBuf,
+    /// The levels of the `[lints]` table, which apply to the whole crate.
+    pub levels: Vec<(String, Level)>,
+    /// The `[modules."PATTERN"]` tables, in the order they appear in the file.
+    pub modules: Vec<ModuleLintLevels>,
+}
+
+/// The levels of a `[modules."PATTERN"]` table of the `--lint-config` file.
+#[derive(Clone, Debug, Hash)]
+pub struct ModuleLintLevels {
+    /// The pattern as written in the file.
+    pub pattern: String,
+    /// The segments of the pattern after `crate`, without a trailing `*`.
+    segments: Vec<String>,
+    pub levels: Vec<(String, Level)>,
+}
+
+impl ModuleLintLevels {
+    /// Whether the pattern matches the module at `module_path`, which is relative to the crate
+    /// root.
+    pub fn matches(&self, module_path: &[Symbol]) -> bool {
+        self.segments.len() == module_path.len()
+            && iter::zip(&self.segments, module_path).all(|(segment, &name)| {
+                name != kw::Empty && (segment == "*" || segment == name.as_str())
+            })
+    }
+}
+
+impl LintConfig {
+    /// Parses the contents of a `--lint-config` file. Errors are reported with the line they
+    /// occur on.
+    pub fn parse(path: PathBuf, src: &str) -> Result<LintConfig, String> {
+        let mut config = LintConfig { path, levels: Vec::new(), modules: Vec::new() };
+        let mut has_lints_table = false;
+        // The levels of the table the entries are currently added to.
+        let mut levels = None;
+
+        for (i, line) in src.lines().enumerate() {
+            let error = |msg: String| format!("line {}: {msg}", i + 1);
+            let mut line = Line(line.trim_start());
+            if line.is_end() {
+                continue;
+            }
+
+            if line.eat('[') {
+                line.skip_whitespace();
+                let table = line.key().map_err(error)?;
+                let pattern = if table == "modules" {
+                    line.skip_whitespace();
+                    if !line.eat('.') {
+                        return Err(error("expected `.` followed by a module pattern".to_owned()));
+                    }
+                    line.skip_whitespace();
+                    Some(line.key().map_err(error)?)
+                } else if table == "lints" {
+                    None
+                } else {
+                    return Err(error(format!(
+                        "unknown table `{table}`, expected `lints` or `modules`"
+                    )));
+                };
+                line.skip_whitespace();
+                if !line.eat(']') || !line.is_end() {
+                    return Err(error("expected `]` at the end of the table header".to_owned()));
+                }
+
+                levels = Some(match pattern {
+                    None if has_lints_table => {
+                        return Err(error("duplicate table `lints`".to_owned()));
+                    }
+                    None => {
+                        has_lints_table = true;
+                        &mut config.levels
+                    }
+                    Some(pattern) => {
+                        if config.modules.iter().any(|module| module.pattern == pattern) {
+                            return Err(error(format!("duplicate table for `{pattern}`")));
+                        }
+                        let segments = parse_module_pattern(&pattern).map_err(error)?;
+                        config.modules.push(ModuleLintLevels {
+                            pattern,
+                            segments,
+                            levels: Vec::new(),
+                        });
+                        &mut config.modules.last_mut().unwrap().levels
+                    }
+                });
+                continue;
+            }
+
+            let lint_name = line.key().map_err(error)?.replace('-', "_");
+            line.skip_whitespace();
+            if !line.eat('=') {
+                return Err(error(format!("expected `=` after `{lint_name}`")));
+            }
+            line.skip_whitespace();
+            let level = line.string().map_err(error)?;
+            if !line.is_end() {
+                return Err(error("unexpected characters after the lint level".to_owned()));
+            }
+            let level = match &*level {
+                "force-warn" => Level::ForceWarn(None),
+                level => Level::from_str(level).ok_or_else(|| {
+                    error(format!(
+                        "unknown lint level `{level}`, expected `allow`, `warn`, `force-warn`, \
+                         `deny` or `forbid`"
+                    ))
+                })?,
+            };
+
+            let Some(levels) = &mut levels else {
+                return Err(error(
+                    "lint levels must be in a `[lints]` or `[modules.\"PATTERN\"]` table"
+                        .to_owned(),
+                ));
+            };
+            if levels.iter().any(|(name, _)| *name == lint_name) {
+                return Err(error(format!("duplicate level for `{lint_name}`")));
+            }
+            levels.push((lint_name, level));
+        }
+
+        Ok(config)
+    }
+}
+
+/// Checks a module pattern, and returns its segments after `crate` without a trailing `*`.
+fn parse_module_pattern(pattern: &str) -> Result<Vec<String>, String> {
+    let mut segments = pattern.split("::").map(str::trim);
+    if segments.next() != Some("crate") {
+        return Err(format!("the module pattern `{pattern}` must start with `crate`"));
+    }
+    let mut segments: Vec<_> = segments.map(str::to_owned).collect();
+    for segment in &segments {
+        let is_ident = segment.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && segment.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !is_ident && segment != "*" {
+            return Err(format!("invalid module name `{segment}` in the pattern `{pattern}`"));
+        }
+    }
+    if segments.last().is_some_and(|segment| segment == "*") {
+        segments.pop();
+    }
+    Ok(segments)
+}
+
+/// The remainder of a line of the file being parsed.
+struct Line<'a>(&'a str);
+
+impl Line<'_> {
+    fn skip_whitespace(&mut self) {
+        self.0 = self.0.trim_start();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if let Some(rest) = self.0.strip_prefix(c) {
+            self.0 = rest;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Whether only whitespace or a comment remains.
+    fn is_end(&self) -> bool {
+        let rest = self.0.trim_start();
+        rest.is_empty() || rest.starts_with('#')
+    }
+
+    /// Parses a bare or quoted key.
+    fn key(&mut self) -> Result<String, String> {
+        if self.0.starts_with(['"', '\'']) {
+            return self.string();
+        }
+        let len = self
+            .0
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(self.0.len());
+        if len == 0 {
+            return Err("expected a key".to_owned());
+        }
+        let (key, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(key.to_owned())
+    }
+
+    /// Parses a basic (`"..."`) or literal (`'...'`) string.
+    fn string(&mut self) -> Result<String, String> {
+        if self.eat('\'') {
+            let Some((string, rest)) = self.0.split_once('\'') else {
+                return Err("unterminated string".to_owned());
+            };
+            self.0 = rest;
+            return Ok(string.to_owned());
+        }
+        if !self.eat('"') {
+            return Err("expected a string".to_owned());
+        }
+        let mut string = String::new();
+        let mut chars = self.0.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.0 = &self.0[i + 1..];
+                    return Ok(string);
+                }
+                '\\' => match chars.next() {
+                    Some((_, c @ ('"' | '\\'))) => string.push(c),
+                    _ => return Err("only `\\\"` and `\\\\` escapes are supported".to_owned()),
+                },
+                c => string.push(c),
+            }
+        }
+        Err("unterminated string".to_owned())
+    }
+}
@@ -143,6 +143,7 @@ pub struct Options {
         debuginfo_compression: DebugInfoCompression [TRACKED],
         lint_opts: Vec<(String, lint::Level)> [TRACKED_NO_CRATE_HASH],
         lint_cap: Option<lint::Level> [TRACKED_NO_CRATE_HASH],
+        lint_config: Option<LintConfig> [TRACKED_NO_CRATE_HASH],
         describe_lints: bool [UNTRACKED],
         output_types: OutputTypes [TRACKED],
         search_paths: Vec<SearchPath> [UNTRACKED],
@@ -222,6 +223,9 @@ pub struct Options {
 
         /// The (potentially remapped) working directory
         working_dir: RealFileName [TRACKED],
+        /// Set for the second compilation of `-Zverify-reproducible`, which perturbs what
+        /// shouldn't affect the outputs, such as the order codegen units are codegened in.
+        perturbed_build: bool [UNTRACKED],
         color: ColorConfig [UNTRACKED],
 
         verbose: bool [TRACKED_NO_CRATE_HASH],
@@ -395,6 +399,7 @@ mod desc {
     pub const parse_linker_flavor: &str = ::rustc_target::spec::LinkerFlavorCli::one_of();
     pub const parse_optimization_fuel: &str = "crate=integer";
     pub const parse_dump_mono_stats: &str = "`markdown` (default) or `json`";
+    pub const parse_dep_info_format: &str = "`makefile` (default) or `json`";
     pub const parse_instrument_coverage: &str = parse_bool;
     pub const parse_coverage_options: &str = "`branch` or `no-branch`";
     pub const parse_instrument_xray: &str = "either a boolean (`yes`, `no`, `on`, `off`, etc), or a comma separated list of settings: `always` or `never` (mutually exclusive), `ignore-loops`, `instruction-threshold=N`, `skip-entry`, `skip-exit`";
@@ -915,6 +920,15 @@ pub(crate) fn parse_dump_mono_stats(slot: &mut DumpMonoStatsFormat, v: Option<&s
         }
     }
 
+    pub(crate) fn parse_dep_info_format(slot: &mut DepInfoFormat, v: Option<&str>) -> bool {
+        match v {
+            Some("makefile") => *slot = DepInfoFormat::Makefile,
+            Some("json") => *slot = DepInfoFormat::Json,
+            _ => return false,
+        }
+        true
+    }
+
     pub(crate) fn parse_instrument_coverage(
         slot: &mut InstrumentCoverage,
         v: Option<&str>,
@@ -1618,13 +1632,27 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         "deduplicate identical diagnostics (default: yes)"),
     default_hidden_visibility: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "overrides the `default_hidden_visibility` setting of the target"),
+    dep_info_format: DepInfoFormat = (DepInfoFormat::Makefile, parse_dep_info_format, [TRACKED],
+        "the format of the dep-info file written by `--emit=dep-info`: `makefile` (default), or \
+        `json` which also records the content hash of the inputs and the SVH of the extern crates"),
     dep_info_omit_d_target: bool = (false, parse_bool, [TRACKED],
         "in dep-info output, omit targets for tracking dependencies of the dep-info files \
         themselves (default: no)"),
+    diagnostic_baseline: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
+        "suppress the lint warnings recorded in this file"),
+    diagnostic_baseline_record: bool = (false, parse_bool, [UNTRACKED],
+        "record the lint warnings emitted into the `-Z diagnostic-baseline` file, instead of \
+        suppressing the warnings recorded in it (default: no)"),
     direct_access_external_data: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "Direct or use GOT indirect to reference external data symbols"),
     dual_proc_macros: bool = (false, parse_bool, [TRACKED],
         "load proc macros for both target and host, but only link to the target (default: no)"),
+    dump_code_size: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "output a report of the size of the code in the object files, per crate, definition \
+        and codegen unit"),
+    dump_code_size_format: DumpMonoStatsFormat = (DumpMonoStatsFormat::Markdown, parse_dump_mono_stats, [UNTRACKED],
+        "the format to use for -Z dump-code-size (`markdown` (default) or `json`)"),
     dump_dep_graph: bool = (false, parse_bool, [UNTRACKED],
         "dump the dependency graph to $RUST_DEP_GRAPH (default: /tmp/dep_graph.gv) \
         (default: no)"),
@@ -1649,6 +1677,9 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         "output statistics about monomorphization collection"),
     dump_mono_stats_format: DumpMonoStatsFormat = (DumpMonoStatsFormat::Markdown, parse_dump_mono_stats, [UNTRACKED],
         "the format to use for -Z dump-mono-stats (`markdown` (default) or `json`)"),
+    dump_stable_mir: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write the StableMIR of the local items, with the types, layouts and ADT definitions \
+        they refer to, to this file in a versioned JSON format"),
     dwarf_version: Option<u32> = (None, parse_opt_number, [TRACKED],
         "version of DWARF debug information to emit (default: 2 or 4, depending on platform)"),
     dylib_lto: bool = (false, parse_bool, [UNTRACKED],
@@ -1658,6 +1689,8 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         them only if an error has not been emitted"),
     ehcont_guard: bool = (false, parse_bool, [TRACKED],
         "generate Windows EHCont Guard tables"),
+    emit_fix_patch: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write the machine-applicable suggestions as a unified diff to this file"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emit a section containing stack size metadata (default: no)"),
     emit_thin_lto: bool = (true, parse_bool, [TRACKED],
@@ -1784,6 +1817,10 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
     #[rustc_lint_opt_deny_field_access("use `Session::mir_opt_level` instead of this field")]
     mir_opt_level: Option<usize> = (None, parse_opt_number, [TRACKED],
         "MIR optimization level (0-4; default: 1 in non optimized builds and 2 in optimized builds)"),
+    mir_pass_stats: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [TRACKED],
+        "write the changes made by each MIR pass to each body, and the time it took, as JSON to \
+        the given directory (default: `-Z dump-mir-dir`)"),
     move_size_limit: Option<usize> = (None, parse_opt_number, [TRACKED],
         "the size at which the `large_assignments` lint starts to be emitted"),
     mutable_noalias: bool = (true, parse_bool, [TRACKED],
@@ -1853,6 +1890,9 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         "make rustc print the total optimization fuel used by a crate"),
     print_llvm_passes: bool = (false, parse_bool, [UNTRACKED],
         "print the LLVM optimization passes being run (default: no)"),
+    print_mono_item_paths: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "print the shortest chain of uses from a root to each mono item whose name contains \
+        the given string, to explain why it was instantiated"),
     print_mono_items: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "print the result of the monomorphization collection pass. \
          Value `lazy` means to use normal collection; `eager` means to collect all items.
@@ -2003,6 +2043,9 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         "language identifier for diagnostic output"),
     translate_remapped_path_to_local_path: bool = (true, parse_bool, [TRACKED],
         "translate remapped paths into local paths when possible (default: yes)"),
+    translate_validate: bool = (false, parse_bool, [UNTRACKED],
+        "warn about translated messages with attributes or arguments that the English messages \
+        don't have (for testing translation)"),
     trap_unreachable: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "generate trap instructions for unreachable intrinsics (default: use target setting, usually yes)"),
     treat_err_as_bug: Option<NonZero<usize>> = (None, parse_treat_err_as_bug, [TRACKED],
@@ -2053,6 +2096,9 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
     #[rustc_lint_opt_deny_field_access("use `Session::verify_llvm_ir` instead of this field")]
     verify_llvm_ir: bool = (false, parse_bool, [TRACKED],
         "verify LLVM IR (default: no)"),
+    verify_reproducible: bool = (false, parse_bool, [UNTRACKED],
+        "compile the crate a second time with perturbed inputs, and report the output files \
+        that differ (default: no)"),
     virtual_function_elimination: bool = (false, parse_bool, [TRACKED],
         "enables dead virtual function elimination optimization. \
         Requires `-Clto[=[fat,yes]]`"),
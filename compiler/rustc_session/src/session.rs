@@ -18,12 +18,15 @@
     AtomicU64, DynSend, DynSync, Lock, Lrc, MappedReadGuard, ReadGuard, RwLock,
 };
 use rustc_errors::annotate_snippet_emitter_writer::AnnotateSnippetEmitter;
+use rustc_errors::baseline::DiagnosticBaseline;
 use rustc_errors::emitter::{stderr_destination, DynEmitter, HumanEmitter, HumanReadableErrorType};
+use rustc_errors::fix_patch::FixPatch;
 use rustc_errors::json::JsonEmitter;
 use rustc_errors::registry::Registry;
+use rustc_errors::sarif::SarifEmitter;
 use rustc_errors::{
-    codes::*, fallback_fluent_bundle, Diag, DiagCtxt, DiagMessage, Diagnostic, ErrorGuaranteed,
-    FatalAbort, FluentBundle, LazyFallbackBundle, TerminalUrl,
+    codes::*, fallback_fluent_bundle, validate_fluent_bundle, Diag, DiagCtxt, DiagMessage,
+    Diagnostic, ErrorGuaranteed, FatalAbort, FluentBundle, LazyFallbackBundle, TerminalUrl,
 };
 use rustc_macros::HashStable_Generic;
 pub use rustc_span::def_id::StableCrateId;
@@ -288,6 +291,18 @@ pub fn finish_diagnostics(&self, registry: &Registry) -> Option<ErrorGuaranteed>
         let mut guar = None;
         guar = guar.or(self.check_miri_unleashed_features());
         guar = guar.or(self.dcx().emit_stashed_diagnostics());
+        if let Err(err) = self.dcx().write_diagnostic_baseline() {
+            let path = self.opts.unstable_opts.diagnostic_baseline.as_deref().unwrap();
+            guar = guar.or(Some(
+                self.dcx().emit_err(errors::FileWriteFail { path, err: err.to_string() }),
+            ));
+        }
+        if let Err(err) = self.dcx().write_fix_patch() {
+            let path = self.opts.unstable_opts.emit_fix_patch.as_deref().unwrap();
+            guar = guar.or(Some(
+                self.dcx().emit_err(errors::FileWriteFail { path, err: err.to_string() }),
+            ));
+        }
         self.dcx().print_error_count(registry);
         if self.opts.json_future_incompat {
             self.dcx().emit_future_breakage_report();
@@ -634,10 +649,16 @@ pub fn binary_dep_depinfo(&self) -> bool {
     }
 
     pub fn mir_opt_level(&self) -> usize {
+        self.mir_opt_level_for(self.opts.optimize)
+    }
+
+    /// The MIR optimization level of the code optimized at `opt_level`, which differs from the
+    /// one of the session for the functions with `#[optimize(level = "...")]`.
+    pub fn mir_opt_level_for(&self, opt_level: OptLevel) -> usize {
         self.opts
             .unstable_opts
             .mir_opt_level
-            .unwrap_or_else(|| if self.opts.optimize != OptLevel::No { 2 } else { 1 })
+            .unwrap_or_else(|| if opt_level != OptLevel::No { 2 } else { 1 })
     }
 
     /// Calculates the flavor of LTO to use for this compilation.
@@ -910,6 +931,7 @@ fn default_emitter(
     source_map: Lrc<SourceMap>,
     bundle: Option<Lrc<FluentBundle>>,
     fallback_bundle: LazyFallbackBundle,
+    cfg_version: &'static str,
 ) -> Box<DynEmitter> {
     let macro_backtrace = sopts.unstable_opts.macro_backtrace;
     let track_diagnostics = sopts.unstable_opts.track_diagnostics;
@@ -974,6 +996,16 @@ fn default_emitter(
             .track_diagnostics(track_diagnostics)
             .terminal_url(terminal_url),
         ),
+        config::ErrorOutputType::Sarif => Box::new(
+            SarifEmitter::new(
+                Box::new(io::BufWriter::new(io::stderr())),
+                source_map,
+                fallback_bundle,
+            )
+            .registry(Some(registry))
+            .fluent_bundle(bundle)
+            .tool_version(Some(cfg_version.to_owned())),
+        ),
     }
 }
 
@@ -1014,18 +1046,46 @@ pub fn build_session(
         early_dcx.early_warn(warning)
     }
 
+    if sopts.unstable_opts.translate_validate
+        && let Some(bundle) = &bundle
+    {
+        for mismatch in validate_fluent_bundle(bundle, &fluent_resources) {
+            early_dcx.early_warn(mismatch.to_string());
+        }
+    }
+
     let fallback_bundle = fallback_fluent_bundle(
         fluent_resources,
         sopts.unstable_opts.translate_directionality_markers,
     );
     let source_map = rustc_span::source_map::get_source_map().unwrap();
-    let emitter = default_emitter(&sopts, registry, source_map.clone(), bundle, fallback_bundle);
+    let emitter =
+        default_emitter(&sopts, registry, source_map.clone(), bundle, fallback_bundle, cfg_version);
 
     let mut dcx =
         DiagCtxt::new(emitter).with_flags(sopts.unstable_opts.dcx_flags(can_emit_warnings));
     if let Some(ice_file) = ice_file {
         dcx = dcx.with_ice_file(ice_file);
     }
+    if let Some(path) = &sopts.unstable_opts.diagnostic_baseline {
+        if sopts.unstable_opts.diagnostic_baseline_record {
+            dcx = dcx.with_diagnostic_baseline(DiagnosticBaseline::record(path.clone()));
+        } else {
+            match DiagnosticBaseline::load(path.clone()) {
+                Ok(baseline) => dcx = dcx.with_diagnostic_baseline(baseline),
+                Err(e) => early_dcx.early_fatal(format!(
+                    "failed to load diagnostic baseline `{}`: {e}",
+                    path.display()
+                )),
+            }
+        }
+    } else if sopts.unstable_opts.diagnostic_baseline_record {
+        early_dcx.early_fatal("`-Z diagnostic-baseline-record` requires `-Z diagnostic-baseline`");
+    }
+
+    if let Some(path) = &sopts.unstable_opts.emit_fix_patch {
+        dcx = dcx.with_fix_patch(FixPatch::new(path.clone()));
+    }
 
     // Now that the proper handler has been constructed, drop early_dcx to
     // prevent accidental use.
@@ -1424,6 +1484,11 @@ fn mk_emitter(output: ErrorOutputType) -> Box<DynEmitter> {
             pretty,
             json_rendered,
         )),
+        config::ErrorOutputType::Sarif => Box::new(SarifEmitter::early(
+            Box::new(io::BufWriter::new(io::stderr())),
+            Lrc::new(SourceMap::new(FilePathMapping::empty())),
+            fallback_bundle,
+        )),
     };
     emitter
 }
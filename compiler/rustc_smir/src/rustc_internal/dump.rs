@@ -0,0 +1,12 @@
+use std::io;
+use std::path::Path;
+
+use super::run;
+use rustc_middle::ty::TyCtxt;
+use stable_mir::dump::CrateDump;
+
+/// Writes the StableMIR of the local crate to `path`, in the format of [`stable_mir::dump`].
+pub fn write_smir_dump(tcx: TyCtxt<'_>, path: &Path) -> io::Result<()> {
+    let dump = run(tcx, CrateDump::collect).map_err(|e| io::Error::other(e.to_string()))?;
+    dump.write(path)
+}
@@ -21,6 +21,7 @@
 use std::hash::Hash;
 use std::ops::Index;
 
+pub mod dump;
 mod internal;
 pub mod pretty;
 
@@ -121,6 +122,10 @@ pub fn trait_def(&mut self, did: DefId) -> stable_mir::ty::TraitDef {
         stable_mir::ty::TraitDef(self.create_def_id(did))
     }
 
+    pub fn assoc_def(&mut self, did: DefId) -> stable_mir::ty::AssocDef {
+        stable_mir::ty::AssocDef(self.create_def_id(did))
+    }
+
     pub fn generic_def(&mut self, did: DefId) -> stable_mir::ty::GenericDef {
         stable_mir::ty::GenericDef(self.create_def_id(did))
     }
@@ -216,6 +221,7 @@ pub fn run<F, T>(tcx: TyCtxt<'_>, f: F) -> Result<T, Error>
         instances: IndexMap::default(),
         constants: IndexMap::default(),
         layouts: IndexMap::default(),
+        mono_items: None,
     }));
     stable_mir::compiler_interface::run(&tables, || init(&tables, f))
 }
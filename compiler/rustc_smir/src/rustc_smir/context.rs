@@ -15,16 +15,17 @@
     GenericPredicates, Instance, List, ParamEnv, ScalarInt, TyCtxt, TypeVisitableExt, ValTree,
 };
 use rustc_span::def_id::LOCAL_CRATE;
+use rustc_trait_selection::infer::{InferCtxtExt, TyCtxtInferExt};
 use stable_mir::abi::{FnAbi, Layout, LayoutShape};
 use stable_mir::compiler_interface::Context;
 use stable_mir::mir::alloc::GlobalAlloc;
-use stable_mir::mir::mono::{InstanceDef, StaticDef};
+use stable_mir::mir::mono::{InstanceDef, MonoItem, MonoItemUse, StaticDef};
 use stable_mir::mir::{BinOp, Body, Place};
 use stable_mir::target::{MachineInfo, MachineSize};
 use stable_mir::ty::{
-    AdtDef, AdtKind, Allocation, ClosureDef, ClosureKind, Const, FieldDef, FnDef, ForeignDef,
-    ForeignItemKind, GenericArgs, LineInfo, PolyFnSig, RigidTy, Span, Ty, TyKind, UintTy,
-    VariantDef,
+    AdtDef, AdtKind, Allocation, AssocItem, ClosureDef, ClosureKind, Const, FieldDef, FnDef,
+    ForeignDef, ForeignItemKind, GenericArgs, LineInfo, PolyFnSig, RigidTy, Span, TraitDef, Ty,
+    TyKind, UintTy, VariantDef,
 };
 use stable_mir::{Crate, CrateDef, CrateItem, CrateNum, DefId, Error, Filename, ItemKind, Symbol};
 use std::cell::RefCell;
@@ -32,6 +33,7 @@
 
 use crate::rustc_internal::RustcInternal;
 use crate::rustc_smir::builder::BodyBuilder;
+use crate::rustc_smir::replace::replace_body;
 use crate::rustc_smir::{alloc, new_item_kind, smir_crate, Stable, Tables};
 
 impl<'tcx> Context for TablesWrapper<'tcx> {
@@ -68,6 +70,12 @@ fn has_body(&self, def: DefId) -> bool {
         tables.tcx.is_mir_available(def_id)
     }
 
+    fn replace_mir_body(&self, item: DefId, body: &Body) -> Result<(), Error> {
+        let mut tables = self.0.borrow_mut();
+        let def_id = tables[item];
+        replace_body(&mut tables, def_id, body)
+    }
+
     fn foreign_modules(&self, crate_num: CrateNum) -> Vec<stable_mir::ty::ForeignModuleDef> {
         let mut tables = self.0.borrow_mut();
         let tcx = tables.tcx;
@@ -148,6 +156,17 @@ fn trait_impl(&self, impl_def: &stable_mir::ty::ImplDef) -> stable_mir::ty::Impl
         impl_trait.stable(&mut *tables)
     }
 
+    fn trait_associated_items(&self, trait_def: TraitDef) -> Vec<AssocItem> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let def_id = tables[trait_def.0];
+        tcx.associated_items(def_id)
+            .in_definition_order()
+            .filter(|item| !item.is_impl_trait_in_trait())
+            .map(|item| item.stable(&mut *tables))
+            .collect()
+    }
+
     fn generics_of(&self, def_id: stable_mir::DefId) -> stable_mir::ty::Generics {
         let mut tables = self.0.borrow_mut();
         let def_id = tables[def_id];
@@ -447,6 +466,59 @@ fn ty_kind(&self, ty: stable_mir::ty::Ty) -> TyKind {
         tables.types[ty].kind().stable(&mut *tables)
     }
 
+    fn ty_implements_trait(
+        &self,
+        ty: stable_mir::ty::Ty,
+        trait_def: TraitDef,
+        args: &GenericArgs,
+    ) -> Result<bool, Error> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let trait_def_id = tables[trait_def.0];
+        let ty = tcx.erase_regions(ty.internal(&mut *tables, tcx));
+        let args = tcx.erase_regions(args.internal(&mut *tables, tcx));
+        let generics = tcx.generics_of(trait_def_id);
+        let expected = generics.count() - 1;
+        if args.len() != expected {
+            return Err(Error::new(format!(
+                "`{}` expects {expected} generic arguments after `Self`, but {} were given",
+                tcx.def_path_str(trait_def_id),
+                args.len()
+            )));
+        }
+        // The first parameter of a trait is `Self`.
+        for (param, arg) in iter::zip(&generics.params[1..], args.iter()) {
+            if !matches!(
+                (&param.kind, arg.unpack()),
+                (ty::GenericParamDefKind::Lifetime, ty::GenericArgKind::Lifetime(_))
+                    | (ty::GenericParamDefKind::Type { .. }, ty::GenericArgKind::Type(_))
+                    | (ty::GenericParamDefKind::Const { .. }, ty::GenericArgKind::Const(_))
+            ) {
+                return Err(Error::new(format!(
+                    "the {} parameter `{}` of `{}` was given the generic argument `{arg}`",
+                    param.kind.descr(),
+                    param.name,
+                    tcx.def_path_str(trait_def_id),
+                )));
+            }
+        }
+        let infcx = tcx.infer_ctxt().build();
+        let params = iter::once(ty::GenericArg::from(ty)).chain(args.iter());
+        Ok(infcx
+            .type_implements_trait(trait_def_id, params, ParamEnv::reveal_all())
+            .must_apply_modulo_regions())
+    }
+
+    fn normalize_ty(&self, ty: stable_mir::ty::Ty) -> Result<stable_mir::ty::Ty, Error> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let ty = ty.internal(&mut *tables, tcx);
+        match tcx.try_normalize_erasing_regions(ParamEnv::reveal_all(), ty) {
+            Ok(normalized) => Ok(normalized.stable(&mut *tables)),
+            Err(_) => Err(Error::new(format!("failed to normalize `{ty}`"))),
+        }
+    }
+
     fn rigid_ty_discriminant_ty(&self, ty: &RigidTy) -> stable_mir::ty::Ty {
         let mut tables = self.0.borrow_mut();
         let tcx = tables.tcx;
@@ -514,6 +586,31 @@ fn requires_monomorphization(&self, def_id: stable_mir::DefId) -> bool {
         result
     }
 
+    fn all_mono_items(&self) -> Vec<MonoItem> {
+        let mut tables = self.0.borrow_mut();
+        let items = tables.mono_items().to_vec();
+        items.iter().map(|item| item.stable(&mut *tables)).collect()
+    }
+
+    fn mono_item_uses(&self, item: &MonoItem) -> Vec<MonoItemUse> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let internal = match item {
+            // Global assembly can't be converted back, so look for it among the collected items.
+            MonoItem::GlobalAsm(_) => {
+                let items = tables.mono_items().to_vec();
+                match items.into_iter().find(|internal| internal.stable(&mut *tables) == *item) {
+                    Some(internal) => internal,
+                    None => return vec![],
+                }
+            }
+            _ => item.internal(&mut *tables, tcx),
+        };
+        let (_, usage_map) = tcx.collect_mono_items(());
+        let uses = usage_map.get_uses(internal);
+        uses.iter().map(|used| used.stable(&mut *tables)).collect()
+    }
+
     fn resolve_instance(
         &self,
         def: stable_mir::ty::FnDef,
@@ -753,3 +753,31 @@ fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
         }
     }
 }
+
+impl<'tcx> Stable<'tcx> for rustc_middle::mir::mono::MonoItemUse<'tcx> {
+    type T = stable_mir::mir::mono::MonoItemUse;
+
+    fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
+        stable_mir::mir::mono::MonoItemUse {
+            item: self.item.stable(tables),
+            kind: self.kind.stable(tables),
+            span: self.span.stable(tables),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_middle::mir::mono::UseKind {
+    type T = stable_mir::mir::mono::UseKind;
+
+    fn stable(&self, _: &mut Tables<'_>) -> Self::T {
+        use rustc_middle::mir::mono::UseKind;
+        use stable_mir::mir::mono::UseKind as StableUseKind;
+        match self {
+            UseKind::Call => StableUseKind::Call,
+            UseKind::DropGlue => StableUseKind::DropGlue,
+            UseKind::VtableMethod => StableUseKind::VtableMethod,
+            UseKind::FnPointer => StableUseKind::FnPointer,
+            UseKind::Static => StableUseKind::Static,
+        }
+    }
+}
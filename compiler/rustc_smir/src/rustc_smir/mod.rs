@@ -10,6 +10,7 @@
 use rustc_hir::def::DefKind;
 use rustc_middle::mir;
 use rustc_middle::mir::interpret::AllocId;
+use rustc_middle::mir::mono::MonoItem;
 use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
 use rustc_span::def_id::{CrateNum, DefId, LOCAL_CRATE};
 use stable_mir::abi::Layout;
@@ -25,6 +26,7 @@
 mod builder;
 pub(crate) mod context;
 mod convert;
+mod replace;
 
 pub struct Tables<'tcx> {
     pub(crate) tcx: TyCtxt<'tcx>,
@@ -35,6 +37,7 @@ pub struct Tables<'tcx> {
     pub(crate) instances: IndexMap<ty::Instance<'tcx>, InstanceDef>,
     pub(crate) constants: IndexMap<mir::Const<'tcx>, ConstId>,
     pub(crate) layouts: IndexMap<rustc_target::abi::Layout<'tcx>, Layout>,
+    pub(crate) mono_items: Option<Vec<MonoItem<'tcx>>>,
 }
 
 impl<'tcx> Tables<'tcx> {
@@ -56,6 +59,17 @@ pub(crate) fn has_body(&self, instance: Instance<'tcx>) -> bool {
                     | ty::InstanceDef::Item(..)
             )
     }
+
+    /// The mono items collected for code generation, sorted by symbol name.
+    pub(crate) fn mono_items(&mut self) -> &[MonoItem<'tcx>] {
+        let tcx = self.tcx;
+        self.mono_items.get_or_insert_with(|| {
+            let (items, _) = tcx.collect_mono_items(());
+            let mut items: Vec<_> = items.iter().copied().collect();
+            items.sort_by_cached_key(|item| item.symbol_name(tcx).name);
+            items
+        })
+    }
 }
 
 /// Build a stable mir crate from a given crate number.
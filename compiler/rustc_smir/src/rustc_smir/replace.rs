@@ -0,0 +1,296 @@
+//! Logic required to replace the optimized MIR of a function by a stable body, e.g. one
+//! instrumented by a tool.
+//!
+//! The stable body is converted back to the internal representation, on top of the current body
+//! of the function for what StableMIR doesn't represent, such as source scopes. The result is then
+//! checked by the MIR validator before replacing the current body.
+
+use crate::rustc_internal::RustcInternal;
+use crate::rustc_smir::Tables;
+use rustc_const_eval::transform::validate::validate_body;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_index::IndexVec;
+use rustc_middle::mir;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::def_id::DefId;
+use rustc_span::source_map::Spanned;
+use rustc_span::Symbol;
+use stable_mir::mir::visit::{Location, PlaceContext};
+use stable_mir::mir::{
+    Body, Local, MirVisitor, Statement, StatementKind, Terminator, TerminatorKind,
+};
+use stable_mir::Error;
+
+/// Replaces the optimized MIR of `def_id` by `body`, if it's a valid body for this function.
+pub(crate) fn replace_body<'tcx>(
+    tables: &mut Tables<'tcx>,
+    def_id: DefId,
+    body: &Body,
+) -> Result<(), Error> {
+    let tcx = tables.tcx;
+    if tcx.sess.opts.incremental.is_some() {
+        // The incremental compilation cache doesn't know about the replaced bodies.
+        return Err(Error::new(
+            "the MIR of a function can't be replaced in incremental compilation".to_string(),
+        ));
+    }
+    if !def_id.is_local() || !tcx.def_kind(def_id).is_fn_like() || !tcx.is_mir_available(def_id) {
+        return Err(Error::new(format!(
+            "`{}` isn't a local function with a body",
+            tcx.def_path_str(def_id)
+        )));
+    }
+    let current = tcx.instance_mir(ty::InstanceDef::Item(def_id));
+    check_body(tables, body, current).map_err(|error| {
+        Error::new(format!("invalid body for `{}`: {error}", tcx.def_path_str(def_id)))
+    })?;
+
+    let new_body = internal_body(tables, body, current);
+    let when = "after the replacement of its body through StableMIR";
+    if let Some((location, msg)) = validate_body(tcx, when, new_body.phase, &new_body).first() {
+        return Err(Error::new(format!(
+            "invalid body for `{}` at {location:?}: {msg}",
+            tcx.def_path_str(def_id)
+        )));
+    }
+    tcx.replace_optimized_mir(def_id, new_body).map_err(|reason| {
+        Error::new(format!("the MIR of `{}` can't be replaced: {reason}", tcx.def_path_str(def_id)))
+    })
+}
+
+/// Checks what the conversion to the internal representation relies on: the body keeps the
+/// signature of the function, only refers to blocks, locals and source scopes which exist, and
+/// only contains constructs which can be converted.
+fn check_body<'tcx>(
+    tables: &mut Tables<'tcx>,
+    body: &Body,
+    current: &mir::Body<'tcx>,
+) -> Result<(), String> {
+    let tcx = tables.tcx;
+    if body.arg_locals().len() != current.arg_count
+        || body.spread_arg() != current.spread_arg.map(|local| local.as_usize())
+    {
+        return Err("the arguments of the function changed".to_string());
+    }
+    for (local, decl) in body.locals()[..=current.arg_count].iter().enumerate() {
+        if decl.ty.internal(tables, tcx) != current.local_decls[mir::Local::from_usize(local)].ty {
+            return Err(format!("the type of `_{local}` changed"));
+        }
+    }
+    if body.blocks.is_empty() {
+        return Err("it has no basic blocks".to_string());
+    }
+    for info in &body.var_debug_info {
+        if info.source_info.scope as usize >= current.source_scopes.len() {
+            return Err(format!("the debug info of `{}` refers to an unknown scope", info.name));
+        }
+    }
+
+    let mut checker = BodyChecker { body, error: None };
+    checker.visit_body(body);
+    checker.error.map_or(Ok(()), Err)
+}
+
+struct BodyChecker<'a> {
+    body: &'a Body,
+    error: Option<String>,
+}
+
+impl<'a> BodyChecker<'a> {
+    fn fail(&mut self, error: String) {
+        self.error.get_or_insert(error);
+    }
+}
+
+impl<'a> MirVisitor for BodyChecker<'a> {
+    fn visit_statement(&mut self, stmt: &Statement, location: Location) {
+        match &stmt.kind {
+            StatementKind::FakeRead(..)
+            | StatementKind::AscribeUserType { .. }
+            | StatementKind::Coverage(_) => {
+                self.fail(format!("`{:?}` statements can't be converted", stmt.kind));
+            }
+            _ => {}
+        }
+        self.super_statement(stmt, location)
+    }
+
+    fn visit_terminator(&mut self, term: &Terminator, location: Location) {
+        if let TerminatorKind::InlineAsm { .. } = term.kind {
+            self.fail("inline assembly can't be converted".to_string());
+        }
+        let blocks = self.body.blocks.len();
+        if let Some(target) = term.kind.successors().into_iter().find(|bb| *bb >= blocks) {
+            self.fail(format!("the basic block `bb{target}` doesn't exist"));
+        }
+        self.super_terminator(term, location)
+    }
+
+    fn visit_local(&mut self, local: &Local, _ptx: PlaceContext, _location: Location) {
+        if *local >= self.body.locals().len() {
+            self.fail(format!("the local `_{local}` isn't declared"));
+        }
+    }
+}
+
+/// Converts `body` to the internal representation. StableMIR doesn't represent the source scopes
+/// of statements: they keep the scope of the statements of `current` with the same span, and new
+/// statements are in the outermost scope.
+fn internal_body<'tcx>(
+    tables: &mut Tables<'tcx>,
+    body: &Body,
+    current: &mir::Body<'tcx>,
+) -> mir::Body<'tcx> {
+    let tcx = tables.tcx;
+    let mut scopes = FxHashMap::default();
+    for data in current.basic_blocks.iter() {
+        let source_infos = data.statements.iter().map(|statement| statement.source_info);
+        for source_info in source_infos.chain([data.terminator().source_info]) {
+            scopes.entry(source_info.span).or_insert(source_info.scope);
+        }
+    }
+    let source_info = |span: stable_mir::ty::Span, tables: &mut Tables<'tcx>| {
+        let span = span.internal(tables, tcx);
+        let scope = scopes.get(&span).copied().unwrap_or(mir::OUTERMOST_SOURCE_SCOPE);
+        mir::SourceInfo { span, scope }
+    };
+
+    let mut blocks: IndexVec<mir::BasicBlock, mir::BasicBlockData<'tcx>> = body
+        .blocks
+        .iter()
+        .map(|block| {
+            let statements = block
+                .statements
+                .iter()
+                .map(|statement| mir::Statement {
+                    source_info: source_info(statement.span, tables),
+                    kind: statement.kind.internal(tables, tcx),
+                })
+                .collect();
+            let terminator = mir::Terminator {
+                source_info: source_info(block.terminator.span, tables),
+                kind: terminator_kind(&block.terminator, tables, tcx),
+            };
+            mir::BasicBlockData { statements, terminator: Some(terminator), is_cleanup: false }
+        })
+        .collect();
+    // StableMIR doesn't distinguish the cleanup blocks: they are the ones reached when unwinding.
+    let mut cleanup_blocks: Vec<_> = blocks
+        .iter()
+        .filter_map(|data| match data.terminator().unwind() {
+            Some(mir::UnwindAction::Cleanup(bb)) => Some(*bb),
+            _ => None,
+        })
+        .collect();
+    while let Some(bb) = cleanup_blocks.pop() {
+        if !blocks[bb].is_cleanup {
+            blocks[bb].is_cleanup = true;
+            cleanup_blocks.extend(blocks[bb].terminator().successors());
+        }
+    }
+
+    let local_decls = body
+        .locals()
+        .iter()
+        .enumerate()
+        .map(|(local, decl)| {
+            let new_decl = decl.internal(tables, tcx);
+            match current.local_decls.get(mir::Local::from_usize(local)) {
+                // Keep what StableMIR doesn't represent about the existing locals.
+                Some(current_decl) => mir::LocalDecl {
+                    mutability: new_decl.mutability,
+                    ty: new_decl.ty,
+                    source_info: mir::SourceInfo {
+                        span: new_decl.source_info.span,
+                        scope: current_decl.source_info.scope,
+                    },
+                    ..current_decl.clone()
+                },
+                None => new_decl,
+            }
+        })
+        .collect();
+
+    let var_debug_info = body
+        .var_debug_info
+        .iter()
+        .map(|info| mir::VarDebugInfo {
+            name: Symbol::intern(&info.name),
+            source_info: mir::SourceInfo {
+                span: info.source_info.span.internal(tables, tcx),
+                scope: mir::SourceScope::from_u32(info.source_info.scope),
+            },
+            composite: info
+                .composite
+                .as_ref()
+                .map(|fragment| Box::new(fragment.internal(tables, tcx))),
+            value: info.value.internal(tables, tcx),
+            argument_index: info.argument_index,
+        })
+        .collect();
+
+    let mut new_body = current.clone();
+    *new_body.basic_blocks_mut() = blocks;
+    new_body.local_decls = local_decls;
+    new_body.var_debug_info = var_debug_info;
+    new_body
+}
+
+fn terminator_kind<'tcx>(
+    terminator: &Terminator,
+    tables: &mut Tables<'tcx>,
+    tcx: TyCtxt<'tcx>,
+) -> mir::TerminatorKind<'tcx> {
+    let span = terminator.span.internal(tables, tcx);
+    match &terminator.kind {
+        TerminatorKind::Goto { target } => {
+            mir::TerminatorKind::Goto { target: mir::BasicBlock::from_usize(*target) }
+        }
+        TerminatorKind::SwitchInt { discr, targets } => mir::TerminatorKind::SwitchInt {
+            discr: discr.internal(tables, tcx),
+            targets: mir::SwitchTargets::new(
+                targets.branches().map(|(value, bb)| (value, mir::BasicBlock::from_usize(bb))),
+                mir::BasicBlock::from_usize(targets.otherwise()),
+            ),
+        },
+        TerminatorKind::Resume => mir::TerminatorKind::UnwindResume,
+        // StableMIR doesn't keep the reason, which only changes the message of the panic.
+        TerminatorKind::Abort => {
+            mir::TerminatorKind::UnwindTerminate(mir::UnwindTerminateReason::Abi)
+        }
+        TerminatorKind::Return => mir::TerminatorKind::Return,
+        TerminatorKind::Unreachable => mir::TerminatorKind::Unreachable,
+        TerminatorKind::Drop { place, target, unwind } => mir::TerminatorKind::Drop {
+            place: place.internal(tables, tcx),
+            target: mir::BasicBlock::from_usize(*target),
+            unwind: unwind.internal(tables, tcx),
+            replace: false,
+        },
+        TerminatorKind::Call { func, args, destination, target, unwind } => {
+            mir::TerminatorKind::Call {
+                func: func.internal(tables, tcx),
+                args: args
+                    .iter()
+                    .map(|arg| Spanned { node: arg.internal(tables, tcx), span })
+                    .collect(),
+                destination: destination.internal(tables, tcx),
+                target: target.map(mir::BasicBlock::from_usize),
+                unwind: unwind.internal(tables, tcx),
+                call_source: mir::CallSource::Normal,
+                fn_span: span,
+            }
+        }
+        TerminatorKind::Assert { cond, expected, msg, target, unwind } => {
+            mir::TerminatorKind::Assert {
+                cond: cond.internal(tables, tcx),
+                expected: *expected,
+                msg: Box::new(msg.internal(tables, tcx)),
+                target: mir::BasicBlock::from_usize(*target),
+                unwind: unwind.internal(tables, tcx),
+            }
+        }
+        TerminatorKind::InlineAsm { .. } => {
+            unreachable!("inline assembly is rejected before the conversion")
+        }
+    }
+}
@@ -1498,10 +1498,14 @@ pub struct SourceFileHash {
 
 impl SourceFileHash {
     pub fn new(kind: SourceFileHashAlgorithm, src: &str) -> SourceFileHash {
+        SourceFileHash::new_from_bytes(kind, src.as_bytes())
+    }
+
+    /// Hashes the contents of a file which isn't necessarily valid UTF-8.
+    pub fn new_from_bytes(kind: SourceFileHashAlgorithm, data: &[u8]) -> SourceFileHash {
         let mut hash = SourceFileHash { kind, value: Default::default() };
         let len = hash.hash_len();
         let value = &mut hash.value[..len];
-        let data = src.as_bytes();
         match kind {
             SourceFileHashAlgorithm::Md5 => {
                 value.copy_from_slice(&Md5::digest(data));
@@ -543,6 +543,8 @@
         cmp_partialord_lt,
         cmpxchg16b_target_feature,
         cmse_nonsecure_entry,
+        codegen_unit,
+        codegen_unit_hints,
         coerce_unsized,
         cold,
         collapse_debuginfo,
@@ -1046,6 +1048,7 @@
         len,
         let_chains,
         let_else,
+        level,
         lhs,
         lib,
         libc,
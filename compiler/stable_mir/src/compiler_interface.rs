@@ -7,14 +7,14 @@
 
 use crate::abi::{FnAbi, Layout, LayoutShape};
 use crate::mir::alloc::{AllocId, GlobalAlloc};
-use crate::mir::mono::{Instance, InstanceDef, StaticDef};
+use crate::mir::mono::{Instance, InstanceDef, MonoItem, MonoItemUse, StaticDef};
 use crate::mir::{BinOp, Body, Place};
 use crate::target::MachineInfo;
 use crate::ty::{
-    AdtDef, AdtKind, Allocation, ClosureDef, ClosureKind, Const, FieldDef, FnDef, ForeignDef,
-    ForeignItemKind, ForeignModule, ForeignModuleDef, GenericArgs, GenericPredicates, Generics,
-    ImplDef, ImplTrait, LineInfo, PolyFnSig, RigidTy, Span, TraitDecl, TraitDef, Ty, TyKind,
-    UintTy, VariantDef,
+    AdtDef, AdtKind, Allocation, AssocItem, ClosureDef, ClosureKind, Const, FieldDef, FnDef,
+    ForeignDef, ForeignItemKind, ForeignModule, ForeignModuleDef, GenericArgs, GenericPredicates,
+    Generics, ImplDef, ImplTrait, LineInfo, PolyFnSig, RigidTy, Span, TraitDecl, TraitDef, Ty,
+    TyKind, UintTy, VariantDef,
 };
 use crate::{
     mir, Crate, CrateItem, CrateItems, CrateNum, DefId, Error, Filename, ImplTraitDecls, ItemKind,
@@ -32,6 +32,8 @@ pub trait Context {
     fn mir_body(&self, item: DefId) -> mir::Body;
     /// Check whether the body of a function is available.
     fn has_body(&self, item: DefId) -> bool;
+    /// Replace the optimized MIR of a local function, which will be used by code generation.
+    fn replace_mir_body(&self, item: DefId, body: &mir::Body) -> Result<(), Error>;
     fn foreign_modules(&self, crate_num: CrateNum) -> Vec<ForeignModuleDef>;
     fn foreign_module(&self, mod_def: ForeignModuleDef) -> ForeignModule;
     fn foreign_items(&self, mod_def: ForeignModuleDef) -> Vec<ForeignDef>;
@@ -41,6 +43,7 @@ pub trait Context {
     fn all_trait_impls(&self) -> ImplTraitDecls;
     fn trait_impls(&self, crate_num: CrateNum) -> ImplTraitDecls;
     fn trait_impl(&self, trait_impl: &ImplDef) -> ImplTrait;
+    fn trait_associated_items(&self, trait_def: TraitDef) -> Vec<AssocItem>;
     fn generics_of(&self, def_id: DefId) -> Generics;
     fn predicates_of(&self, def_id: DefId) -> GenericPredicates;
     fn explicit_predicates_of(&self, def_id: DefId) -> GenericPredicates;
@@ -137,6 +140,17 @@ pub trait Context {
     /// Obtain the representation of a type.
     fn ty_kind(&self, ty: Ty) -> TyKind;
 
+    /// Check whether a type implements a trait with the given arguments after `Self`.
+    fn ty_implements_trait(
+        &self,
+        ty: Ty,
+        trait_def: TraitDef,
+        args: &GenericArgs,
+    ) -> Result<bool, Error>;
+
+    /// Normalize the associated types and type aliases in a type.
+    fn normalize_ty(&self, ty: Ty) -> Result<Ty, Error>;
+
     // Get the discriminant Ty for this Ty if there's one.
     fn rigid_ty_discriminant_ty(&self, ty: &RigidTy) -> Ty;
 
@@ -165,6 +179,12 @@ pub trait Context {
     /// Item requires monomorphization.
     fn requires_monomorphization(&self, def_id: DefId) -> bool;
 
+    /// Collect the items that code generation needs.
+    fn all_mono_items(&self) -> Vec<MonoItem>;
+
+    /// Retrieve the items used by a mono item.
+    fn mono_item_uses(&self, item: &MonoItem) -> Vec<MonoItemUse>;
+
     /// Resolve an instance from the given function definition and generic arguments.
     fn resolve_instance(&self, def: FnDef, args: &GenericArgs) -> Option<Instance>;
 
@@ -0,0 +1,770 @@
+//! A versioned on-disk format for the StableMIR of a crate, so that analyses can run outside of
+//! the compiler process.
+//!
+//! [`CrateDump::collect`] records, from within the compiler, the bodies of the items of the local
+//! crate, along with the types, layouts, ADT definitions, names and spans they refer to. This is
+//! what `rustc -Zdump-stable-mir=PATH` writes. [`CrateDump::run`] then provides a [`Context`]
+//! answering the queries from the recorded data, so that the usual APIs can be used:
+//!
+//! ```ignore (requires a dump)
+//! let dump = CrateDump::read(Path::new("krate.smir.json"))?;
+//! dump.run(|| {
+//!     for item in stable_mir::all_local_items() {
+//!         println!("{}: {} blocks", item.name(), item.body().blocks.len());
+//!     }
+//! })?;
+//! ```
+//!
+//! Only what the local items refer to is recorded. Queries about anything else, and the queries
+//! which need the compiler, such as instance resolution or constant evaluation, fail: the ones
+//! returning a `Result` return an error, and the others panic.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::ops::ControlFlow;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::abi::{FnAbi, Layout, LayoutShape};
+use crate::compiler_interface::{self, with, Context};
+use crate::mir::alloc::{AllocId, GlobalAlloc};
+use crate::mir::mono::{Instance, InstanceDef, MonoItem, MonoItemUse, StaticDef};
+use crate::mir::visit::{Location, PlaceContext};
+use crate::mir::{BinOp, Body, MirVisitor, Place};
+use crate::target::MachineInfo;
+use crate::ty::{
+    AdtDef, AdtKind, Allocation, AssocItem, ClosureDef, ClosureKind, Const, ConstId,
+    ExistentialPredicate, FieldDef, FnDef, ForeignDef, ForeignItemKind, ForeignModule,
+    ForeignModuleDef, GenericArgs, GenericPredicates, Generics, ImplDef, ImplTrait, IndexedVal,
+    LineInfo, PolyFnSig, RigidTy, Span, TraitDecl, TraitDef, Ty, TyKind, UintTy, VariantDef,
+};
+use crate::visitor::{Visitable, Visitor};
+use crate::{
+    error, Crate, CrateItem, CrateItems, CrateNum, DefId, Error, Filename, ImplTraitDecls,
+    ItemKind, Symbol, TraitDecls,
+};
+
+/// The version of the format, which changes whenever the recorded data or the StableMIR types do.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// The StableMIR of a crate, as recorded by [`CrateDump::collect`].
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CrateDump {
+    /// The [`FORMAT_VERSION`] of the dump.
+    pub version: u32,
+    pub krate: Crate,
+    pub external_crates: Vec<Crate>,
+    pub target: MachineInfo,
+    pub entry_fn: Option<CrateItem>,
+    pub items: Vec<ItemDump>,
+    pub defs: Vec<DefDump>,
+    pub types: Vec<TyDump>,
+    pub layouts: Vec<(Layout, LayoutShape)>,
+    pub adts: Vec<AdtDump>,
+    /// The pretty-printed constants.
+    pub consts: Vec<(ConstId, String)>,
+    /// The pretty-printed places.
+    pub places: Vec<(Place, String)>,
+    pub spans: Vec<SpanDump>,
+}
+
+/// An item of the local crate.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ItemDump {
+    pub item: CrateItem,
+    pub kind: ItemKind,
+    pub requires_monomorphization: bool,
+    pub body: Option<Body>,
+}
+
+/// A definition referred to by the recorded data.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DefDump {
+    pub def_id: DefId,
+    pub name: Symbol,
+    pub trimmed_name: Symbol,
+    pub krate: CrateNum,
+    pub span: Span,
+    pub is_foreign_item: bool,
+    /// The type of the definition, only recorded for items, ADTs and fields.
+    pub ty: Option<Ty>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TyDump {
+    pub ty: Ty,
+    pub kind: TyKind,
+    pub pretty: String,
+    /// The layout of the type, or why it has none.
+    pub layout: Result<Layout, String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AdtDump {
+    pub def: AdtDef,
+    pub kind: AdtKind,
+    pub is_box: bool,
+    pub is_simd: bool,
+    pub is_cstr: bool,
+    pub variants: Vec<VariantDump>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct VariantDump {
+    pub name: Symbol,
+    pub fields: Vec<FieldDef>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SpanDump {
+    pub span: Span,
+    pub pretty: String,
+    pub filename: Filename,
+    pub lines: LineInfo,
+}
+
+impl CrateDump {
+    /// Records the StableMIR of the local crate. This must run within a compiler [`Context`].
+    pub fn collect() -> CrateDump {
+        let mut collector = Collector::default();
+        let items = crate::all_local_items()
+            .into_iter()
+            .map(|item| {
+                collector.add_def(item.0, true);
+                let body = with(|cx| cx.has_body(item.0)).then(|| item.body());
+                if let Some(body) = &body {
+                    collector.visit_body(body);
+                }
+                ItemDump {
+                    item,
+                    kind: item.kind(),
+                    requires_monomorphization: item.requires_monomorphization(),
+                    body,
+                }
+            })
+            .collect();
+        let Collector { defs, types, layouts, adts, consts, places, spans, .. } = collector;
+        CrateDump {
+            version: FORMAT_VERSION,
+            krate: crate::local_crate(),
+            external_crates: crate::external_crates(),
+            target: MachineInfo::target(),
+            entry_fn: crate::entry_fn(),
+            items,
+            defs,
+            types,
+            layouts,
+            adts,
+            consts,
+            places,
+            spans,
+        }
+    }
+
+    /// Reads a dump, checking that it has the version of the format this crate implements.
+    pub fn read(path: &Path) -> Result<CrateDump, Error> {
+        #[derive(Deserialize)]
+        struct Version {
+            version: u32,
+        }
+
+        let read_error = |e: io::Error| error!("failed to read `{}`: {e}", path.display());
+        let parse_error = |e: serde_json::Error| error!("invalid dump `{}`: {e}", path.display());
+        let contents = std::fs::read(path).map_err(read_error)?;
+        let Version { version } = serde_json::from_slice(&contents).map_err(parse_error)?;
+        if version != FORMAT_VERSION {
+            return Err(error!(
+                "`{}` has version {version} of the format, expected version {FORMAT_VERSION}",
+                path.display()
+            ));
+        }
+        serde_json::from_slice(&contents).map_err(parse_error)
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(&mut file, self)?;
+        file.flush()
+    }
+
+    /// Executes `f` with a [`Context`] answering the queries from the dump.
+    pub fn run<F, T>(&self, f: F) -> Result<T, Error>
+    where
+        F: FnOnce() -> T,
+    {
+        compiler_interface::run(&DumpContext::new(self), f)
+    }
+}
+
+/// Records everything the bodies of the local items refer to.
+#[derive(Default)]
+struct Collector {
+    defs: Vec<DefDump>,
+    types: Vec<TyDump>,
+    layouts: Vec<(Layout, LayoutShape)>,
+    adts: Vec<AdtDump>,
+    consts: Vec<(ConstId, String)>,
+    places: Vec<(Place, String)>,
+    spans: Vec<SpanDump>,
+    seen_defs: HashSet<DefId>,
+    seen_types: HashSet<Ty>,
+    seen_layouts: HashSet<Layout>,
+    seen_adts: HashSet<AdtDef>,
+    seen_consts: HashSet<ConstId>,
+    seen_places: HashSet<Place>,
+    seen_spans: HashSet<Span>,
+}
+
+impl Collector {
+    fn add_def(&mut self, def_id: DefId, with_ty: bool) {
+        if !self.seen_defs.insert(def_id) {
+            return;
+        }
+        let (name, trimmed_name, krate, span, is_foreign_item) = with(|cx| {
+            (
+                cx.def_name(def_id, false),
+                cx.def_name(def_id, true),
+                cx.krate(def_id).id,
+                cx.span_of_an_item(def_id),
+                cx.is_foreign_item(def_id),
+            )
+        });
+        let ty = with_ty.then(|| with(|cx| cx.def_ty(def_id)));
+        self.defs.push(DefDump { def_id, name, trimmed_name, krate, span, is_foreign_item, ty });
+        self.add_span(span);
+        if let Some(ty) = ty {
+            self.add_ty(ty);
+        }
+    }
+
+    fn add_ty(&mut self, ty: Ty) {
+        if !self.seen_types.insert(ty) {
+            return;
+        }
+        let kind = ty.kind();
+        let layout = ty.layout();
+        if let Ok(layout) = layout {
+            if self.seen_layouts.insert(layout) {
+                self.layouts.push((layout, layout.shape()));
+            }
+        }
+        match &kind {
+            TyKind::RigidTy(RigidTy::Adt(def, _)) => self.add_adt(*def),
+            TyKind::RigidTy(RigidTy::Foreign(def)) => self.add_def(def.0, false),
+            TyKind::RigidTy(RigidTy::FnDef(def, _)) => self.add_def(def.0, false),
+            TyKind::RigidTy(RigidTy::Closure(def, _)) => self.add_def(def.0, false),
+            TyKind::RigidTy(RigidTy::Coroutine(def, _, _)) => self.add_def(def.0, false),
+            TyKind::RigidTy(RigidTy::CoroutineWitness(def, _)) => self.add_def(def.0, false),
+            TyKind::RigidTy(RigidTy::Dynamic(predicates, _, _)) => {
+                for predicate in predicates {
+                    match &predicate.value {
+                        ExistentialPredicate::Trait(trait_ref) => {
+                            self.add_def(trait_ref.def_id.0, false)
+                        }
+                        ExistentialPredicate::Projection(projection) => {
+                            self.add_def(projection.def_id.0, false)
+                        }
+                        ExistentialPredicate::AutoTrait(def) => self.add_def(def.0, false),
+                    }
+                }
+            }
+            TyKind::Alias(_, alias) => self.add_def(alias.def_id.0, false),
+            _ => {}
+        }
+        self.types.push(TyDump {
+            ty,
+            kind,
+            pretty: with(|cx| cx.ty_pretty(ty)),
+            layout: layout.map_err(|e| e.to_string()),
+        });
+        let _ = ty.super_visit(self);
+    }
+
+    fn add_adt(&mut self, def: AdtDef) {
+        if !self.seen_adts.insert(def) {
+            return;
+        }
+        self.add_def(def.0, true);
+        let variants: Vec<_> = def
+            .variants_iter()
+            .map(|variant| VariantDump { name: variant.name(), fields: variant.fields() })
+            .collect();
+        for field in variants.iter().flat_map(|variant| &variant.fields) {
+            self.add_def(field.def, true);
+        }
+        self.adts.push(AdtDump {
+            def,
+            kind: def.kind(),
+            is_box: def.is_box(),
+            is_simd: def.is_simd(),
+            is_cstr: with(|cx| cx.adt_is_cstr(def)),
+            variants,
+        });
+    }
+
+    fn add_const(&mut self, constant: &Const) {
+        if self.seen_consts.insert(constant.id) {
+            self.consts.push((constant.id, with(|cx| cx.const_pretty(constant))));
+        }
+        let _ = constant.super_visit(self);
+    }
+
+    fn add_span(&mut self, span: Span) {
+        if self.seen_spans.insert(span) {
+            self.spans.push(SpanDump {
+                span,
+                pretty: with(|cx| cx.span_to_string(span)),
+                filename: span.get_filename(),
+                lines: span.get_lines(),
+            });
+        }
+    }
+}
+
+impl MirVisitor for Collector {
+    fn visit_span(&mut self, span: &Span) {
+        self.add_span(*span);
+    }
+
+    fn visit_place(&mut self, place: &Place, ptx: PlaceContext, location: Location) {
+        if self.seen_places.insert(place.clone()) {
+            self.places.push((place.clone(), with(|cx| cx.place_pretty(place))));
+        }
+        self.super_place(place, ptx, location)
+    }
+
+    fn visit_ty(&mut self, ty: &Ty, _location: Location) {
+        self.add_ty(*ty);
+    }
+
+    fn visit_const(&mut self, constant: &Const, _location: Location) {
+        self.add_const(constant);
+    }
+}
+
+impl Visitor for Collector {
+    type Break = ();
+
+    fn visit_ty(&mut self, ty: &Ty) -> ControlFlow<()> {
+        self.add_ty(*ty);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_const(&mut self, constant: &Const) -> ControlFlow<()> {
+        self.add_const(constant);
+        ControlFlow::Continue(())
+    }
+}
+
+/// A [`Context`] answering the queries from a [`CrateDump`].
+struct DumpContext<'a> {
+    dump: &'a CrateDump,
+    items: HashMap<DefId, &'a ItemDump>,
+    defs: HashMap<DefId, &'a DefDump>,
+    types: HashMap<Ty, &'a TyDump>,
+    layouts: HashMap<Layout, &'a LayoutShape>,
+    adts: HashMap<AdtDef, &'a AdtDump>,
+    consts: HashMap<ConstId, &'a str>,
+    places: HashMap<&'a Place, &'a str>,
+    spans: HashMap<Span, &'a SpanDump>,
+}
+
+impl<'a> DumpContext<'a> {
+    fn new(dump: &'a CrateDump) -> Self {
+        DumpContext {
+            dump,
+            items: dump.items.iter().map(|item| (item.item.0, item)).collect(),
+            defs: dump.defs.iter().map(|def| (def.def_id, def)).collect(),
+            types: dump.types.iter().map(|ty| (ty.ty, ty)).collect(),
+            layouts: dump.layouts.iter().map(|(layout, shape)| (*layout, shape)).collect(),
+            adts: dump.adts.iter().map(|adt| (adt.def, adt)).collect(),
+            consts: dump.consts.iter().map(|(id, pretty)| (*id, &**pretty)).collect(),
+            places: dump.places.iter().map(|(place, pretty)| (place, &**pretty)).collect(),
+            spans: dump.spans.iter().map(|span| (span.span, span)).collect(),
+        }
+    }
+
+    fn item(&self, def_id: DefId) -> &'a ItemDump {
+        self.items.get(&def_id).unwrap_or_else(|| missing("local item", def_id.0))
+    }
+
+    fn def(&self, def_id: DefId) -> &'a DefDump {
+        self.defs.get(&def_id).unwrap_or_else(|| missing("definition", def_id.0))
+    }
+
+    fn ty(&self, ty: Ty) -> &'a TyDump {
+        self.types.get(&ty).unwrap_or_else(|| missing("type", ty.to_index()))
+    }
+
+    fn adt(&self, def: AdtDef) -> &'a AdtDump {
+        self.adts.get(&def).unwrap_or_else(|| missing("ADT", def.0.0))
+    }
+
+    fn span(&self, span: Span) -> &'a SpanDump {
+        self.spans.get(&span).unwrap_or_else(|| missing("span", span.to_index()))
+    }
+
+    fn crate_by_num(&self, num: CrateNum) -> Crate {
+        std::iter::once(&self.dump.krate)
+            .chain(&self.dump.external_crates)
+            .find(|krate| krate.id == num)
+            .cloned()
+            .unwrap_or_else(|| missing("crate", num))
+    }
+}
+
+fn missing(what: &str, id: usize) -> ! {
+    panic!("{what} #{id} isn't recorded in the StableMIR dump")
+}
+
+fn unsupported(query: &str) -> ! {
+    panic!("`{query}` isn't supported on a StableMIR dump")
+}
+
+/// The error of the fallible queries which aren't supported, which the callers can handle.
+fn unsupported_error(query: &str) -> Error {
+    error!("`{query}` isn't supported on a StableMIR dump")
+}
+
+impl Context for DumpContext<'_> {
+    fn entry_fn(&self) -> Option<CrateItem> {
+        self.dump.entry_fn
+    }
+
+    fn all_local_items(&self) -> CrateItems {
+        self.dump.items.iter().map(|item| item.item).collect()
+    }
+
+    fn mir_body(&self, item: DefId) -> Body {
+        self.item(item).body.clone().unwrap_or_else(|| missing("body of the item", item.0))
+    }
+
+    /// Only the bodies of the local items are recorded.
+    fn has_body(&self, item: DefId) -> bool {
+        self.items.get(&item).is_some_and(|item| item.body.is_some())
+    }
+
+    fn replace_mir_body(&self, _item: DefId, _body: &Body) -> Result<(), Error> {
+        Err(unsupported_error("replace_mir_body"))
+    }
+
+    fn foreign_modules(&self, _crate_num: CrateNum) -> Vec<ForeignModuleDef> {
+        unsupported("foreign_modules")
+    }
+
+    fn foreign_module(&self, _mod_def: ForeignModuleDef) -> ForeignModule {
+        unsupported("foreign_module")
+    }
+
+    fn foreign_items(&self, _mod_def: ForeignModuleDef) -> Vec<ForeignDef> {
+        unsupported("foreign_items")
+    }
+
+    fn all_trait_decls(&self) -> TraitDecls {
+        unsupported("all_trait_decls")
+    }
+
+    fn trait_decls(&self, _crate_num: CrateNum) -> TraitDecls {
+        unsupported("trait_decls")
+    }
+
+    fn trait_decl(&self, _trait_def: &TraitDef) -> TraitDecl {
+        unsupported("trait_decl")
+    }
+
+    fn all_trait_impls(&self) -> ImplTraitDecls {
+        unsupported("all_trait_impls")
+    }
+
+    fn trait_impls(&self, _crate_num: CrateNum) -> ImplTraitDecls {
+        unsupported("trait_impls")
+    }
+
+    fn trait_impl(&self, _trait_impl: &ImplDef) -> ImplTrait {
+        unsupported("trait_impl")
+    }
+
+    fn trait_associated_items(&self, _trait_def: TraitDef) -> Vec<AssocItem> {
+        unsupported("trait_associated_items")
+    }
+
+    fn generics_of(&self, _def_id: DefId) -> Generics {
+        unsupported("generics_of")
+    }
+
+    fn predicates_of(&self, _def_id: DefId) -> GenericPredicates {
+        unsupported("predicates_of")
+    }
+
+    fn explicit_predicates_of(&self, _def_id: DefId) -> GenericPredicates {
+        unsupported("explicit_predicates_of")
+    }
+
+    fn local_crate(&self) -> Crate {
+        self.dump.krate.clone()
+    }
+
+    fn external_crates(&self) -> Vec<Crate> {
+        self.dump.external_crates.clone()
+    }
+
+    fn find_crates(&self, name: &str) -> Vec<Crate> {
+        std::iter::once(&self.dump.krate)
+            .chain(&self.dump.external_crates)
+            .filter(|krate| krate.name == name)
+            .cloned()
+            .collect()
+    }
+
+    fn def_name(&self, def_id: DefId, trimmed: bool) -> Symbol {
+        let def = self.def(def_id);
+        if trimmed { def.trimmed_name.clone() } else { def.name.clone() }
+    }
+
+    fn span_to_string(&self, span: Span) -> String {
+        self.span(span).pretty.clone()
+    }
+
+    fn get_filename(&self, span: &Span) -> Filename {
+        self.span(*span).filename.clone()
+    }
+
+    fn get_lines(&self, span: &Span) -> LineInfo {
+        self.span(*span).lines
+    }
+
+    fn item_kind(&self, item: CrateItem) -> ItemKind {
+        self.item(item.0).kind
+    }
+
+    fn is_foreign_item(&self, item: DefId) -> bool {
+        self.def(item).is_foreign_item
+    }
+
+    fn foreign_item_kind(&self, _def: ForeignDef) -> ForeignItemKind {
+        unsupported("foreign_item_kind")
+    }
+
+    fn adt_kind(&self, def: AdtDef) -> AdtKind {
+        self.adt(def).kind
+    }
+
+    fn adt_is_box(&self, def: AdtDef) -> bool {
+        self.adt(def).is_box
+    }
+
+    fn adt_is_simd(&self, def: AdtDef) -> bool {
+        self.adt(def).is_simd
+    }
+
+    fn adt_is_cstr(&self, def: AdtDef) -> bool {
+        self.adt(def).is_cstr
+    }
+
+    fn fn_sig(&self, _def: FnDef, _args: &GenericArgs) -> PolyFnSig {
+        unsupported("fn_sig")
+    }
+
+    fn closure_sig(&self, _args: &GenericArgs) -> PolyFnSig {
+        unsupported("closure_sig")
+    }
+
+    fn adt_variants_len(&self, def: AdtDef) -> usize {
+        self.adt(def).variants.len()
+    }
+
+    fn variant_name(&self, def: VariantDef) -> Symbol {
+        self.adt(def.adt_def).variants[def.idx.to_index()].name.clone()
+    }
+
+    fn variant_fields(&self, def: VariantDef) -> Vec<FieldDef> {
+        self.adt(def.adt_def).variants[def.idx.to_index()].fields.clone()
+    }
+
+    fn eval_target_usize(&self, _cnst: &Const) -> Result<u64, Error> {
+        Err(unsupported_error("eval_target_usize"))
+    }
+
+    fn try_new_const_zst(&self, _ty: Ty) -> Result<Const, Error> {
+        Err(unsupported_error("try_new_const_zst"))
+    }
+
+    fn new_const_str(&self, _value: &str) -> Const {
+        unsupported("new_const_str")
+    }
+
+    fn new_const_bool(&self, _value: bool) -> Const {
+        unsupported("new_const_bool")
+    }
+
+    fn try_new_const_uint(&self, _value: u128, _uint_ty: UintTy) -> Result<Const, Error> {
+        Err(unsupported_error("try_new_const_uint"))
+    }
+
+    fn new_rigid_ty(&self, _kind: RigidTy) -> Ty {
+        unsupported("new_rigid_ty")
+    }
+
+    fn new_box_ty(&self, _ty: Ty) -> Ty {
+        unsupported("new_box_ty")
+    }
+
+    fn def_ty(&self, item: DefId) -> Ty {
+        self.def(item).ty.unwrap_or_else(|| missing("type of the definition", item.0))
+    }
+
+    fn def_ty_with_args(&self, _item: DefId, _args: &GenericArgs) -> Ty {
+        unsupported("def_ty_with_args")
+    }
+
+    fn const_pretty(&self, cnst: &Const) -> String {
+        self.consts
+            .get(&cnst.id)
+            .unwrap_or_else(|| missing("constant", cnst.id.to_index()))
+            .to_string()
+    }
+
+    fn span_of_an_item(&self, def_id: DefId) -> Span {
+        self.def(def_id).span
+    }
+
+    fn ty_pretty(&self, ty: Ty) -> String {
+        self.ty(ty).pretty.clone()
+    }
+
+    fn ty_kind(&self, ty: Ty) -> TyKind {
+        self.ty(ty).kind.clone()
+    }
+
+    fn ty_implements_trait(
+        &self,
+        _ty: Ty,
+        _trait_def: TraitDef,
+        _args: &GenericArgs,
+    ) -> Result<bool, Error> {
+        Err(unsupported_error("ty_implements_trait"))
+    }
+
+    fn normalize_ty(&self, _ty: Ty) -> Result<Ty, Error> {
+        Err(unsupported_error("normalize_ty"))
+    }
+
+    fn rigid_ty_discriminant_ty(&self, _ty: &RigidTy) -> Ty {
+        unsupported("rigid_ty_discriminant_ty")
+    }
+
+    fn instance_body(&self, _instance: InstanceDef) -> Option<Body> {
+        unsupported("instance_body")
+    }
+
+    fn instance_ty(&self, _instance: InstanceDef) -> Ty {
+        unsupported("instance_ty")
+    }
+
+    fn instance_args(&self, _def: InstanceDef) -> GenericArgs {
+        unsupported("instance_args")
+    }
+
+    fn instance_def_id(&self, _instance: InstanceDef) -> DefId {
+        unsupported("instance_def_id")
+    }
+
+    fn instance_mangled_name(&self, _instance: InstanceDef) -> Symbol {
+        unsupported("instance_mangled_name")
+    }
+
+    fn is_empty_drop_shim(&self, _def: InstanceDef) -> bool {
+        unsupported("is_empty_drop_shim")
+    }
+
+    fn mono_instance(&self, _def_id: DefId) -> Instance {
+        unsupported("mono_instance")
+    }
+
+    fn requires_monomorphization(&self, def_id: DefId) -> bool {
+        self.item(def_id).requires_monomorphization
+    }
+
+    fn all_mono_items(&self) -> Vec<MonoItem> {
+        unsupported("all_mono_items")
+    }
+
+    fn mono_item_uses(&self, _item: &MonoItem) -> Vec<MonoItemUse> {
+        unsupported("mono_item_uses")
+    }
+
+    fn resolve_instance(&self, _def: FnDef, _args: &GenericArgs) -> Option<Instance> {
+        unsupported("resolve_instance")
+    }
+
+    fn resolve_drop_in_place(&self, _ty: Ty) -> Instance {
+        unsupported("resolve_drop_in_place")
+    }
+
+    fn resolve_for_fn_ptr(&self, _def: FnDef, _args: &GenericArgs) -> Option<Instance> {
+        unsupported("resolve_for_fn_ptr")
+    }
+
+    fn resolve_closure(
+        &self,
+        _def: ClosureDef,
+        _args: &GenericArgs,
+        _kind: ClosureKind,
+    ) -> Option<Instance> {
+        unsupported("resolve_closure")
+    }
+
+    fn eval_static_initializer(&self, _def: StaticDef) -> Result<Allocation, Error> {
+        Err(unsupported_error("eval_static_initializer"))
+    }
+
+    fn eval_instance(&self, _def: InstanceDef, _const_ty: Ty) -> Result<Allocation, Error> {
+        Err(unsupported_error("eval_instance"))
+    }
+
+    fn global_alloc(&self, _id: AllocId) -> GlobalAlloc {
+        unsupported("global_alloc")
+    }
+
+    fn vtable_allocation(&self, _global_alloc: &GlobalAlloc) -> Option<AllocId> {
+        unsupported("vtable_allocation")
+    }
+
+    fn krate(&self, def_id: DefId) -> Crate {
+        self.crate_by_num(self.def(def_id).krate)
+    }
+
+    fn instance_name(&self, _def: InstanceDef, _trimmed: bool) -> Symbol {
+        unsupported("instance_name")
+    }
+
+    fn intrinsic_name(&self, _def: InstanceDef) -> Symbol {
+        unsupported("intrinsic_name")
+    }
+
+    fn target_info(&self) -> MachineInfo {
+        self.dump.target.clone()
+    }
+
+    fn instance_abi(&self, _def: InstanceDef) -> Result<FnAbi, Error> {
+        Err(unsupported_error("instance_abi"))
+    }
+
+    fn ty_layout(&self, ty: Ty) -> Result<Layout, Error> {
+        self.ty(ty).layout.clone().map_err(Error)
+    }
+
+    fn layout_shape(&self, id: Layout) -> LayoutShape {
+        (*self.layouts.get(&id).unwrap_or_else(|| missing("layout", id.to_index()))).clone()
+    }
+
+    fn place_pretty(&self, place: &Place) -> String {
+        self.places.get(place).unwrap_or_else(|| missing("place of local", place.local)).to_string()
+    }
+
+    fn binop_ty(&self, _bin_op: BinOp, _rhs: Ty, _lhs: Ty) -> Ty {
+        unsupported("binop_ty")
+    }
+}
@@ -19,6 +19,7 @@
 #[macro_use]
 extern crate scoped_tls;
 
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::Debug;
 use std::io;
@@ -35,6 +36,7 @@
 #[macro_use]
 pub mod crate_def;
 pub mod compiler_interface;
+pub mod dump;
 #[macro_use]
 pub mod error;
 pub mod mir;
@@ -77,7 +79,7 @@ fn to_index(&self) -> usize {
 pub type ImplTraitDecls = Vec<ImplDef>;
 
 /// Holds information about a crate.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct Crate {
     pub id: CrateNum,
     pub name: Symbol,
@@ -101,7 +103,7 @@ pub fn trait_impls(&self) -> ImplTraitDecls {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 pub enum ItemKind {
     Fn,
     Static,
@@ -109,7 +111,7 @@ pub enum ItemKind {
     Ctor(CtorKind),
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 pub enum CtorKind {
     Const,
     Fn,
@@ -127,6 +129,17 @@ pub fn body(&self) -> mir::Body {
         with(|cx| cx.mir_body(self.0))
     }
 
+    /// Replace the body of this function for code generation, e.g. with an instrumented copy of
+    /// [CrateItem::body].
+    ///
+    /// The new body must keep the arguments and the return type of the function, and it's checked
+    /// by the MIR validator before replacing the current one. The replacement fails once the
+    /// current body may have been used elsewhere, e.g. inlined in a function whose body was
+    /// retrieved before, so tools should replace bodies before looking at their callers.
+    pub fn replace_body(&self, body: &mir::Body) -> Result<(), Error> {
+        with(|cx| cx.replace_mir_body(self.0, body))
+    }
+
     pub fn span(&self) -> Span {
         with(|cx| cx.span_of_an_item(self.0))
     }
@@ -187,8 +200,16 @@ pub fn all_trait_impls() -> ImplTraitDecls {
     with(|cx| cx.all_trait_impls())
 }
 
+/// Collect the functions, statics and global assembly code generation needs for the local crate,
+/// i.e. the items reachable from the ones it exports, instantiated with concrete types.
+///
+/// Use [mir::mono::MonoItem::uses] to get the edges between them.
+pub fn all_mono_items() -> Vec<mir::mono::MonoItem> {
+    with(|cx| cx.all_mono_items())
+}
+
 /// A type that provides internal information but that can still be used for debug purpose.
-#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Opaque(String);
 
 impl std::fmt::Display for Opaque {
@@ -0,0 +1,243 @@
+//! # Dataflow analyses on StableMIR
+//!
+//! A generic engine computing the fixpoint of forward and backward dataflow analyses of a
+//! [Body], similar to the one of `rustc_mir_dataflow`, with a few bundled analyses:
+//! [MaybeLiveLocals], [MaybeInitializedPlaces] and [ReachingDefinitions].
+//!
+//! An analysis implements [Analysis], defining its domain and the effect of statements and
+//! terminators on it. [Analysis::iterate_to_fixpoint] computes the state at the entry of every
+//! block, and a [ResultsCursor] gives the state before or after any statement or terminator:
+//!
+//! ```ignore
+//! let results = MaybeLiveLocals.iterate_to_fixpoint(&body);
+//! let mut cursor = results.cursor(&body);
+//! cursor.seek_before(Location { block: 0, statement_index: 0 });
+//! let live_at_entry: Vec<Local> = cursor.get().iter().collect();
+//! ```
+//!
+//! The effect of a terminator applies to all the edges leaving its block, e.g. the destination
+//! of a call is also considered assigned when the call unwinds.
+
+mod impls;
+mod lattice;
+
+pub use impls::{
+    Definition, MaybeInitializedPlaces, MaybeLiveLocals, MovePathIndex, MovePaths,
+    ReachingDefinitions,
+};
+pub use lattice::{BitSet, GenKill, JoinSemiLattice};
+
+use crate::mir::{BasicBlockIdx, Body, Statement, Terminator};
+use std::collections::VecDeque;
+
+/// The location of a statement or terminator in the control-flow graph.
+///
+/// The terminator of a block is at the index following its last statement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location {
+    pub block: BasicBlockIdx,
+    pub statement_index: usize,
+}
+
+impl Location {
+    /// The location of the first statement, or the terminator, of the entry block.
+    pub const START: Location = Location { block: 0, statement_index: 0 };
+}
+
+/// The direction in which the states of an analysis flow through the control-flow graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// From the entry block to the returns, e.g. to track which places are initialized.
+    Forward,
+    /// From the returns to the entry block, e.g. to track which locals are live.
+    Backward,
+}
+
+/// A dataflow analysis, defined by its domain and transfer functions.
+pub trait Analysis {
+    /// The state of the analysis at a point of the body.
+    type Domain: Clone + JoinSemiLattice;
+
+    const DIRECTION: Direction;
+
+    /// The initial state of every block, which must be the identity of the join operation.
+    fn bottom_value(&self, body: &Body) -> Self::Domain;
+
+    /// Sets up the state at the entry of the body: the entry of the first block for forward
+    /// analyses, and the exit of the blocks without successors for backward ones.
+    fn initialize_start_block(&self, body: &Body, state: &mut Self::Domain);
+
+    /// Applies the effect of `statement`, at `location`, to `state`.
+    fn apply_statement_effect(
+        &self,
+        state: &mut Self::Domain,
+        statement: &Statement,
+        location: Location,
+    );
+
+    /// Applies the effect of `terminator`, at `location`, to `state`.
+    fn apply_terminator_effect(
+        &self,
+        state: &mut Self::Domain,
+        terminator: &Terminator,
+        location: Location,
+    );
+
+    /// Computes the fixpoint of the analysis over `body`.
+    fn iterate_to_fixpoint(self, body: &Body) -> Results<Self>
+    where
+        Self: Sized,
+    {
+        let blocks = body.blocks.len();
+        let mut entry_sets: Vec<_> = (0..blocks).map(|_| self.bottom_value(body)).collect();
+        let mut predecessors = vec![Vec::new(); blocks];
+        for (block, data) in body.blocks.iter().enumerate() {
+            for successor in data.terminator.successors() {
+                predecessors[successor].push(block);
+            }
+        }
+        match Self::DIRECTION {
+            Direction::Forward => self.initialize_start_block(body, &mut entry_sets[0]),
+            Direction::Backward => {
+                for (block, data) in body.blocks.iter().enumerate() {
+                    if data.terminator.successors().is_empty() {
+                        self.initialize_start_block(body, &mut entry_sets[block]);
+                    }
+                }
+            }
+        }
+
+        // Visiting the blocks in order for forward analyses, and in reverse order for backward
+        // ones, usually propagates the states in fewer iterations.
+        let mut worklist: VecDeque<BasicBlockIdx> = match Self::DIRECTION {
+            Direction::Forward => (0..blocks).collect(),
+            Direction::Backward => (0..blocks).rev().collect(),
+        };
+        let mut in_worklist = vec![true; blocks];
+        while let Some(block) = worklist.pop_front() {
+            in_worklist[block] = false;
+            let mut state = entry_sets[block].clone();
+            let statements = body.blocks[block].statements.len();
+            for step in 0..=statements {
+                apply_effect(&self, body, &mut state, block, step);
+            }
+            let targets = match Self::DIRECTION {
+                Direction::Forward => body.blocks[block].terminator.successors(),
+                Direction::Backward => predecessors[block].clone(),
+            };
+            for target in targets {
+                if entry_sets[target].join(&state) && !in_worklist[target] {
+                    in_worklist[target] = true;
+                    worklist.push_back(target);
+                }
+            }
+        }
+        Results { analysis: self, entry_sets }
+    }
+}
+
+/// Applies the effect of the `step`-th statement or terminator of `block` in the direction of
+/// the analysis, i.e. the terminator is the first step of backward analyses.
+fn apply_effect<A: Analysis>(
+    analysis: &A,
+    body: &Body,
+    state: &mut A::Domain,
+    block: BasicBlockIdx,
+    step: usize,
+) {
+    let data = &body.blocks[block];
+    let statement_index = match A::DIRECTION {
+        Direction::Forward => step,
+        Direction::Backward => data.statements.len() - step,
+    };
+    let location = Location { block, statement_index };
+    match data.statements.get(statement_index) {
+        Some(statement) => analysis.apply_statement_effect(state, statement, location),
+        None => analysis.apply_terminator_effect(state, &data.terminator, location),
+    }
+}
+
+/// The fixpoint of an analysis.
+pub struct Results<A: Analysis> {
+    pub analysis: A,
+    entry_sets: Vec<A::Domain>,
+}
+
+impl<A: Analysis> Results<A> {
+    /// The state at the entry of `block` in the direction of the analysis, i.e. before its
+    /// first statement for forward analyses, and after its terminator for backward ones.
+    pub fn entry_set(&self, block: BasicBlockIdx) -> &A::Domain {
+        &self.entry_sets[block]
+    }
+
+    /// Returns a cursor to inspect the state at any location of `body`, which must be the body
+    /// the analysis ran on.
+    pub fn cursor<'a>(&'a self, body: &'a Body) -> ResultsCursor<'a, A> {
+        ResultsCursor { body, results: self, state: self.entry_sets[0].clone(), position: None }
+    }
+}
+
+/// Gives the state of an analysis before or after any statement or terminator.
+///
+/// Seeking to successive locations of a block in the direction of the analysis only applies the
+/// effects in between.
+pub struct ResultsCursor<'a, A: Analysis> {
+    body: &'a Body,
+    results: &'a Results<A>,
+    state: A::Domain,
+    /// The block of the state, and the number of effects of the block applied to it.
+    position: Option<(BasicBlockIdx, usize)>,
+}
+
+impl<'a, A: Analysis> ResultsCursor<'a, A> {
+    pub fn results(&self) -> &'a Results<A> {
+        self.results
+    }
+
+    /// The state at the current position.
+    pub fn get(&self) -> &A::Domain {
+        &self.state
+    }
+
+    /// Moves to the state before the statement or terminator at `location`, in program order.
+    pub fn seek_before(&mut self, location: Location) {
+        let steps = match A::DIRECTION {
+            Direction::Forward => location.statement_index,
+            Direction::Backward => self.block_steps(location.block) - location.statement_index,
+        };
+        self.seek(location.block, steps);
+    }
+
+    /// Moves to the state after the statement or terminator at `location`, in program order.
+    pub fn seek_after(&mut self, location: Location) {
+        let steps = match A::DIRECTION {
+            Direction::Forward => location.statement_index + 1,
+            Direction::Backward => self.block_steps(location.block) - location.statement_index - 1,
+        };
+        self.seek(location.block, steps);
+    }
+
+    /// Moves to the state at the entry of `block`, in the direction of the analysis.
+    pub fn seek_to_block_entry(&mut self, block: BasicBlockIdx) {
+        self.seek(block, 0);
+    }
+
+    fn block_steps(&self, block: BasicBlockIdx) -> usize {
+        self.body.blocks[block].statements.len() + 1
+    }
+
+    fn seek(&mut self, block: BasicBlockIdx, steps: usize) {
+        assert!(steps <= self.block_steps(block), "invalid location in `bb{block}`");
+        let applied = match self.position {
+            Some((current, applied)) if current == block && applied <= steps => applied,
+            _ => {
+                self.state.clone_from(&self.results.entry_sets[block]);
+                0
+            }
+        };
+        for step in applied..steps {
+            apply_effect(&self.results.analysis, self.body, &mut self.state, block, step);
+        }
+        self.position = Some((block, steps));
+    }
+}
@@ -0,0 +1,408 @@
+//! The dataflow analyses bundled with StableMIR.
+
+use crate::mir::dataflow::{Analysis, BitSet, Direction, GenKill, Location};
+use crate::mir::visit::{self, PlaceContext};
+use crate::mir::{
+    Body, InlineAsmOperand, Local, MirVisitor, Operand, Place, ProjectionElem, Statement,
+    StatementKind, Terminator, TerminatorKind,
+};
+use std::collections::HashMap;
+
+/// Collects the locals a statement or terminator refers to.
+#[derive(Default)]
+struct LocalUses(Vec<Local>);
+
+impl MirVisitor for LocalUses {
+    fn visit_local(&mut self, local: &Local, _ptx: PlaceContext, _location: visit::Location) {
+        self.0.push(*local);
+    }
+}
+
+/// Records a write to `place` in `defs` if it overwrites a whole local, and the locals used to
+/// compute its address in `uses`.
+fn destination(place: &Place, defs: &mut Vec<Local>, uses: &mut LocalUses) {
+    if place.projection.is_empty() {
+        defs.push(place.local);
+    }
+    if place.projection.contains(&ProjectionElem::Deref) {
+        uses.0.push(place.local);
+    }
+    for elem in &place.projection {
+        if let ProjectionElem::Index(local) = elem {
+            uses.0.push(*local);
+        }
+    }
+}
+
+/// Computes the locals fully overwritten, and the locals used, by a statement or terminator.
+fn defs_and_uses(
+    statement_or_terminator: Result<&Statement, &Terminator>,
+) -> (Vec<Local>, Vec<Local>) {
+    let mut defs = Vec::new();
+    let mut uses = LocalUses::default();
+    match statement_or_terminator {
+        Ok(statement) => {
+            let location = visit::Location::new(statement.span);
+            match &statement.kind {
+                StatementKind::Assign(place, rvalue) => {
+                    destination(place, &mut defs, &mut uses);
+                    uses.visit_rvalue(rvalue, location);
+                }
+                StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => {
+                    defs.push(*local);
+                }
+                _ => uses.visit_statement(statement, location),
+            }
+        }
+        Err(terminator) => {
+            let location = visit::Location::new(terminator.span);
+            match &terminator.kind {
+                TerminatorKind::Call { func, args, destination: place, .. } => {
+                    destination(place, &mut defs, &mut uses);
+                    uses.visit_operand(func, location);
+                    for arg in args {
+                        uses.visit_operand(arg, location);
+                    }
+                }
+                TerminatorKind::InlineAsm { operands, .. } => {
+                    for InlineAsmOperand { in_value, out_place, raw_rpr: _ } in operands {
+                        if let Some(place) = out_place {
+                            destination(place, &mut defs, &mut uses);
+                        }
+                        if let Some(operand) = in_value {
+                            uses.visit_operand(operand, location);
+                        }
+                    }
+                }
+                _ => uses.visit_terminator(terminator, location),
+            }
+        }
+    }
+    (defs, uses.0)
+}
+
+/// The locals which may be read later, i.e. before being overwritten or going out of storage.
+///
+/// A local is live before a statement using it, and dead before a statement overwriting it
+/// entirely. Assignments to a part of a local neither use nor overwrite it.
+pub struct MaybeLiveLocals;
+
+impl Analysis for MaybeLiveLocals {
+    type Domain = BitSet;
+
+    const DIRECTION: Direction = Direction::Backward;
+
+    fn bottom_value(&self, body: &Body) -> BitSet {
+        BitSet::new_empty(body.locals().len())
+    }
+
+    fn initialize_start_block(&self, _body: &Body, _state: &mut BitSet) {
+        // No local is live after the function returns: the return terminator uses the return
+        // place, which makes it live before.
+    }
+
+    fn apply_statement_effect(&self, state: &mut BitSet, statement: &Statement, _: Location) {
+        let (defs, uses) = defs_and_uses(Ok(statement));
+        state.kill_all(defs);
+        state.gen_all(uses);
+    }
+
+    fn apply_terminator_effect(&self, state: &mut BitSet, terminator: &Terminator, _: Location) {
+        let (defs, uses) = defs_and_uses(Err(terminator));
+        state.kill_all(defs);
+        state.gen_all(uses);
+    }
+}
+
+pub type MovePathIndex = usize;
+
+/// The places whose initialization is tracked separately: the locals, and their fields which are
+/// moved or assigned in the body, possibly of an enum variant.
+///
+/// Places which can't be moved out of, e.g. behind a reference or an index, aren't tracked.
+pub struct MovePaths {
+    places: Vec<Place>,
+    parents: Vec<Option<MovePathIndex>>,
+}
+
+impl MovePaths {
+    pub fn new(body: &Body) -> MovePaths {
+        struct Collector(MovePaths);
+        impl MirVisitor for Collector {
+            fn visit_place(&mut self, place: &Place, _ptx: PlaceContext, _: visit::Location) {
+                self.0.register(place);
+            }
+        }
+
+        let places = (0..body.locals().len()).map(Place::from).collect();
+        let parents = vec![None; body.locals().len()];
+        let mut collector = Collector(MovePaths { places, parents });
+        collector.visit_body(body);
+        collector.0
+    }
+
+    /// The tracked place at `index`.
+    pub fn place(&self, index: MovePathIndex) -> &Place {
+        &self.places[index]
+    }
+
+    pub fn len(&self) -> usize {
+        self.places.len()
+    }
+
+    /// Finds the move path of `place`, if it's tracked.
+    pub fn find(&self, place: &Place) -> Option<MovePathIndex> {
+        if place.projection.is_empty() {
+            return (place.local < self.places.len()).then_some(place.local);
+        }
+        self.places.iter().position(|tracked| tracked == place)
+    }
+
+    /// The parent of the move path at `index`, e.g. `_1` for `_1.0`.
+    pub fn parent(&self, index: MovePathIndex) -> Option<MovePathIndex> {
+        self.parents[index]
+    }
+
+    /// The move path at `index` and the ones within it, e.g. `_1`, `_1.0` and `_1.0.1`.
+    pub fn with_descendants(&self, index: MovePathIndex) -> Vec<MovePathIndex> {
+        (0..self.places.len())
+            .filter(|descendant| {
+                let mut path = Some(*descendant);
+                while let Some(current) = path {
+                    if current == index {
+                        return true;
+                    }
+                    path = self.parents[current];
+                }
+                false
+            })
+            .collect()
+    }
+
+    /// Tracks the prefixes of `place` made of fields and enum variants.
+    fn register(&mut self, place: &Place) {
+        let mut parent = place.local;
+        for (idx, elem) in place.projection.iter().enumerate() {
+            if !matches!(elem, ProjectionElem::Field(..) | ProjectionElem::Downcast(_)) {
+                break;
+            }
+            let prefix =
+                Place { local: place.local, projection: place.projection[..=idx].to_vec() };
+            parent = match self.find(&prefix) {
+                Some(path) => path,
+                None => {
+                    self.places.push(prefix);
+                    self.parents.push(Some(parent));
+                    self.places.len() - 1
+                }
+            };
+        }
+    }
+}
+
+/// The places which may be initialized, indexed by their [MovePathIndex].
+///
+/// Assigning a place initializes it, and moving out of it, dropping it or ending the storage of
+/// its local deinitializes it. Both apply to the places within it. The arguments are initialized
+/// at the start of the function.
+pub struct MaybeInitializedPlaces {
+    move_paths: MovePaths,
+}
+
+impl MaybeInitializedPlaces {
+    pub fn new(body: &Body) -> MaybeInitializedPlaces {
+        MaybeInitializedPlaces { move_paths: MovePaths::new(body) }
+    }
+
+    pub fn move_paths(&self) -> &MovePaths {
+        &self.move_paths
+    }
+
+    fn initialize(&self, state: &mut BitSet, place: &Place) {
+        if let Some(path) = self.move_paths.find(place) {
+            state.gen_all(self.move_paths.with_descendants(path));
+        }
+    }
+
+    fn deinitialize(&self, state: &mut BitSet, place: &Place) {
+        if let Some(path) = self.move_paths.find(place) {
+            state.kill_all(self.move_paths.with_descendants(path));
+        }
+    }
+}
+
+/// Collects the places moved out of.
+#[derive(Default)]
+struct Moves(Vec<Place>);
+
+impl MirVisitor for Moves {
+    fn visit_operand(&mut self, operand: &Operand, location: visit::Location) {
+        if let Operand::Move(place) = operand {
+            self.0.push(place.clone());
+        }
+        self.super_operand(operand, location);
+    }
+}
+
+impl Analysis for MaybeInitializedPlaces {
+    type Domain = BitSet;
+
+    const DIRECTION: Direction = Direction::Forward;
+
+    fn bottom_value(&self, _body: &Body) -> BitSet {
+        BitSet::new_empty(self.move_paths.len())
+    }
+
+    fn initialize_start_block(&self, body: &Body, state: &mut BitSet) {
+        for arg in 1..=body.arg_locals().len() {
+            self.initialize(state, &Place::from(arg));
+        }
+    }
+
+    fn apply_statement_effect(&self, state: &mut BitSet, statement: &Statement, _: Location) {
+        let mut moves = Moves::default();
+        moves.visit_statement(statement, visit::Location::new(statement.span));
+        for place in &moves.0 {
+            self.deinitialize(state, place);
+        }
+        match &statement.kind {
+            StatementKind::Assign(place, _) => self.initialize(state, place),
+            StatementKind::Deinit(place) => self.deinitialize(state, place),
+            StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => {
+                self.deinitialize(state, &Place::from(*local))
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_terminator_effect(&self, state: &mut BitSet, terminator: &Terminator, _: Location) {
+        let mut moves = Moves::default();
+        moves.visit_terminator(terminator, visit::Location::new(terminator.span));
+        for place in &moves.0 {
+            self.deinitialize(state, place);
+        }
+        match &terminator.kind {
+            TerminatorKind::Call { destination, .. } => self.initialize(state, destination),
+            TerminatorKind::Drop { place, .. } => self.deinitialize(state, place),
+            TerminatorKind::InlineAsm { operands, .. } => {
+                for place in operands.iter().filter_map(|operand| operand.out_place.as_ref()) {
+                    self.initialize(state, place);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A definition of a local, i.e. an assignment to it or to a part of it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Definition {
+    /// The place assigned, whose projections don't dereference pointers.
+    pub place: Place,
+    /// The location of the assignment, or `None` for the arguments, defined on entry.
+    pub location: Option<Location>,
+}
+
+/// The definitions which may reach a point of the body, indexed by their position in
+/// [ReachingDefinitions::definitions].
+///
+/// Assigning a whole local kills its previous definitions, while assigning a part of it only
+/// adds a definition. Assignments through pointers aren't definitions.
+pub struct ReachingDefinitions {
+    definitions: Vec<Definition>,
+    /// The indices of the definitions of each local.
+    by_local: Vec<Vec<usize>>,
+    /// The indices of the definitions at each location.
+    by_location: HashMap<Location, Vec<usize>>,
+}
+
+impl ReachingDefinitions {
+    pub fn new(body: &Body) -> ReachingDefinitions {
+        let mut definitions: Vec<_> = (1..=body.arg_locals().len())
+            .map(|arg| Definition { place: Place::from(arg), location: None })
+            .collect();
+        for (block, data) in body.blocks.iter().enumerate() {
+            let statements = data.statements.iter().map(|statement| match &statement.kind {
+                StatementKind::Assign(place, _) => vec![place],
+                _ => vec![],
+            });
+            let terminator = match &data.terminator.kind {
+                TerminatorKind::Call { destination, .. } => vec![destination],
+                TerminatorKind::InlineAsm { operands, .. } => {
+                    operands.iter().filter_map(|operand| operand.out_place.as_ref()).collect()
+                }
+                _ => vec![],
+            };
+            for (statement_index, places) in statements.chain([terminator]).enumerate() {
+                let location = Some(Location { block, statement_index });
+                definitions.extend(
+                    places
+                        .into_iter()
+                        .filter(|place| !place.projection.contains(&ProjectionElem::Deref))
+                        .map(|place| Definition { place: place.clone(), location }),
+                );
+            }
+        }
+
+        let mut by_local = vec![Vec::new(); body.locals().len()];
+        let mut by_location: HashMap<_, Vec<_>> = HashMap::new();
+        for (index, definition) in definitions.iter().enumerate() {
+            by_local[definition.place.local].push(index);
+            if let Some(location) = definition.location {
+                by_location.entry(location).or_default().push(index);
+            }
+        }
+        ReachingDefinitions { definitions, by_local, by_location }
+    }
+
+    pub fn definitions(&self) -> &[Definition] {
+        &self.definitions
+    }
+
+    /// The indices of the definitions of `local`.
+    pub fn definitions_of(&self, local: Local) -> &[usize] {
+        &self.by_local[local]
+    }
+
+    fn apply_effect(&self, state: &mut BitSet, location: Location) {
+        let Some(definitions) = self.by_location.get(&location) else { return };
+        for &index in definitions {
+            let place = &self.definitions[index].place;
+            if place.projection.is_empty() {
+                state.kill_all(self.definitions_of(place.local).iter().copied());
+            }
+        }
+        state.gen_all(definitions.iter().copied());
+    }
+}
+
+impl Analysis for ReachingDefinitions {
+    type Domain = BitSet;
+
+    const DIRECTION: Direction = Direction::Forward;
+
+    fn bottom_value(&self, _body: &Body) -> BitSet {
+        BitSet::new_empty(self.definitions.len())
+    }
+
+    fn initialize_start_block(&self, body: &Body, state: &mut BitSet) {
+        // The definitions of the arguments come first.
+        state.gen_all(0..body.arg_locals().len());
+    }
+
+    fn apply_statement_effect(
+        &self,
+        state: &mut BitSet,
+        statement: &Statement,
+        location: Location,
+    ) {
+        if let StatementKind::StorageDead(local) = statement.kind {
+            state.kill_all(self.definitions_of(local).iter().copied());
+        }
+        self.apply_effect(state, location);
+    }
+
+    fn apply_terminator_effect(&self, state: &mut BitSet, _: &Terminator, location: Location) {
+        self.apply_effect(state, location);
+    }
+}
@@ -0,0 +1,147 @@
+//! The domains of the dataflow analyses.
+
+use std::fmt;
+
+/// A lattice whose elements can be joined, i.e. combined into their least upper bound.
+///
+/// The state of an analysis at the entry of a block is the join of the states flowing into it,
+/// and the fixpoint iteration only terminates if joining can't go on increasing the state forever.
+pub trait JoinSemiLattice: Eq {
+    /// Computes the least upper bound of `self` and `other`, and stores it in `self`.
+    ///
+    /// Returns whether `self` changed.
+    fn join(&mut self, other: &Self) -> bool;
+}
+
+/// A domain whose elements are indexed by `T`, to which the transfer functions of an analysis
+/// add elements ("gen") or remove elements ("kill").
+pub trait GenKill<T> {
+    fn gen(&mut self, elem: T);
+
+    fn kill(&mut self, elem: T);
+
+    fn gen_all(&mut self, elems: impl IntoIterator<Item = T>) {
+        for elem in elems {
+            self.gen(elem);
+        }
+    }
+
+    fn kill_all(&mut self, elems: impl IntoIterator<Item = T>) {
+        for elem in elems {
+            self.kill(elem);
+        }
+    }
+}
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A fixed-size set of indices in `0..domain_size`, joined by union.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    domain_size: usize,
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    /// Creates an empty set of indices in `0..domain_size`.
+    pub fn new_empty(domain_size: usize) -> BitSet {
+        BitSet { domain_size, words: vec![0; domain_size.div_ceil(WORD_BITS)] }
+    }
+
+    /// Creates the set of all the indices in `0..domain_size`.
+    pub fn new_filled(domain_size: usize) -> BitSet {
+        let mut set = BitSet::new_empty(domain_size);
+        set.insert_all();
+        set
+    }
+
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    pub fn contains(&self, elem: usize) -> bool {
+        let (word, mask) = self.word_and_mask(elem);
+        self.words[word] & mask != 0
+    }
+
+    /// Inserts `elem`, and returns whether the set changed.
+    pub fn insert(&mut self, elem: usize) -> bool {
+        let (word, mask) = self.word_and_mask(elem);
+        let changed = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        changed
+    }
+
+    /// Removes `elem`, and returns whether the set changed.
+    pub fn remove(&mut self, elem: usize) -> bool {
+        let (word, mask) = self.word_and_mask(elem);
+        let changed = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        changed
+    }
+
+    pub fn insert_all(&mut self) {
+        self.words.fill(!0);
+        if self.domain_size % WORD_BITS != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << (self.domain_size % WORD_BITS)) - 1;
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Adds the elements of `other`, and returns whether the set changed.
+    pub fn union(&mut self, other: &BitSet) -> bool {
+        assert_eq!(self.domain_size, other.domain_size);
+        let mut changed = false;
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            let new = *word | other;
+            changed |= new != *word;
+            *word = new;
+        }
+        changed
+    }
+
+    /// Iterates over the elements of the set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.domain_size).filter(|elem| self.contains(*elem))
+    }
+
+    fn word_and_mask(&self, elem: usize) -> (usize, u64) {
+        assert!(elem < self.domain_size, "{elem} is out of the domain of the set");
+        (elem / WORD_BITS, 1 << (elem % WORD_BITS))
+    }
+}
+
+impl JoinSemiLattice for BitSet {
+    fn join(&mut self, other: &Self) -> bool {
+        self.union(other)
+    }
+}
+
+impl GenKill<usize> for BitSet {
+    fn gen(&mut self, elem: usize) {
+        self.insert(elem);
+    }
+
+    fn kill(&mut self, elem: usize) {
+        self.remove(elem);
+    }
+}
+
+impl fmt::Debug for BitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
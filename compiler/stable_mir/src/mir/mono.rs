@@ -2,18 +2,54 @@
 use crate::crate_def::CrateDef;
 use crate::mir::Body;
 use crate::ty::{Allocation, ClosureDef, ClosureKind, FnDef, GenericArgs, IndexedVal, Ty};
-use crate::{with, CrateItem, DefId, Error, ItemKind, Opaque, Symbol};
+use crate::{with, CrateItem, DefId, Error, ItemKind, Opaque, Span, Symbol};
+use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Formatter};
 use std::io;
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum MonoItem {
     Fn(Instance),
     Static(StaticDef),
     GlobalAsm(Opaque),
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+impl MonoItem {
+    /// Get the items used by this item, with how and where they are used.
+    ///
+    /// These are the edges of the graph of the items returned by [crate::all_mono_items]. The
+    /// calls through trait objects aren't edges: the methods of a vtable are used by the item
+    /// creating the trait object instead.
+    pub fn uses(&self) -> Vec<MonoItemUse> {
+        with(|cx| cx.mono_item_uses(self))
+    }
+}
+
+/// A use of a mono item by another one.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MonoItemUse {
+    pub item: MonoItem,
+    pub kind: UseKind,
+    /// Where the item is used. Uses through constants have a dummy span.
+    pub span: Span,
+}
+
+/// How a mono item uses another one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UseKind {
+    /// A direct call, including the calls to panic functions inserted for assertions.
+    Call,
+    /// The drop glue of a type, called by a drop or referenced by a vtable.
+    DropGlue,
+    /// A method referenced by a vtable, which can be called through a trait object.
+    VtableMethod,
+    /// A function reified to a function pointer, or referenced by a constant or a `sym` operand.
+    FnPointer,
+    /// A static, or the shim accessing a thread local static.
+    Static,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Instance {
     /// The type of instance.
     pub kind: InstanceKind,
@@ -22,7 +58,7 @@ pub struct Instance {
     pub def: InstanceDef,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum InstanceKind {
     /// A user defined item.
     Item,
@@ -235,7 +271,7 @@ fn from(value: StaticDef) -> Self {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct InstanceDef(usize);
 
 impl CrateDef for InstanceDef {
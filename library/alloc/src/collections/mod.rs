@@ -8,6 +8,9 @@
 mod btree;
 #[cfg(not(no_global_oom_handling))]
 pub mod linked_list;
+pub mod small_vec;
+#[cfg(not(no_global_oom_handling))]
+pub mod small_vec_deque;
 #[cfg(not(no_global_oom_handling))]
 pub mod vec_deque;
 
@@ -47,6 +50,15 @@ pub mod btree_set {
 #[doc(no_inline)]
 pub use linked_list::LinkedList;
 
+#[unstable(feature = "small_vec", issue = "none")]
+#[doc(no_inline)]
+pub use small_vec::SmallVec;
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "small_vec", issue = "none")]
+#[doc(no_inline)]
+pub use small_vec_deque::SmallVecDeque;
+
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[doc(no_inline)]
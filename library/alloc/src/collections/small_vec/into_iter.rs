@@ -0,0 +1,95 @@
+use core::fmt;
+use core::iter::{FusedIterator, TrustedLen};
+use core::ptr;
+use core::slice;
+
+use super::SmallVec;
+
+/// An iterator that moves out of a `SmallVec<T, N>`.
+///
+/// This `struct` is created by the `into_iter` method on [`SmallVec`]
+/// (provided by the [`IntoIterator`] trait).
+pub struct IntoIter<T, const N: usize> {
+    /// The vector whose buffer holds the elements. Its length is kept at zero
+    /// so that only the buffer itself is freed when it is dropped.
+    vec: SmallVec<T, N>,
+    /// The elements at `start..end` have not been yielded yet.
+    start: usize,
+    end: usize,
+}
+
+impl<T, const N: usize> IntoIter<T, N> {
+    pub(super) fn new(mut vec: SmallVec<T, N>) -> Self {
+        let end = vec.len();
+        // SAFETY: ownership of the elements is taken over by the iterator.
+        unsafe { vec.set_len(0) };
+        IntoIter { vec, start: 0, end }
+    }
+
+    /// Returns the remaining items of this iterator as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the elements at `start..end` are initialized.
+        unsafe { slice::from_raw_parts(self.vec.as_ptr().add(self.start), self.end - self.start) }
+    }
+
+    /// Returns the remaining items of this iterator as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the elements at `start..end` are initialized.
+        unsafe {
+            slice::from_raw_parts_mut(self.vec.as_mut_ptr().add(self.start), self.end - self.start)
+        }
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for IntoIter<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        let index = self.start;
+        self.start += 1;
+        // SAFETY: `index` was in `start..end` and is never read again.
+        Some(unsafe { ptr::read(self.vec.as_ptr().add(index)) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        // SAFETY: `end` was in `start..end` and is never read again.
+        Some(unsafe { ptr::read(self.vec.as_ptr().add(self.end)) })
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+unsafe impl<T, const N: usize> TrustedLen for IntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> Drop for IntoIter<T, N> {
+    fn drop(&mut self) {
+        // SAFETY: the remaining elements are initialized and owned by the
+        // iterator; the buffer itself is freed when `self.vec` is dropped.
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
@@ -0,0 +1,1139 @@
+//! A contiguous growable array type that stores a small number of elements
+//! inline before spilling to the heap, written as `SmallVec<T, N>`.
+//!
+//! A [`SmallVec<T, N>`] behaves like a [`Vec<T>`] whose first `N` elements
+//! live directly inside the `SmallVec` value itself. As long as the length
+//! stays at or below `N` no heap allocation is performed; once it grows past
+//! `N` the elements are moved into a heap buffer and the `SmallVec` behaves
+//! exactly like a `Vec<T>` from then on.
+//!
+//! This is useful for collections which are usually very short, but which
+//! occasionally need to hold many elements, such as the argument lists or
+//! path segments found throughout compilers and similar tools.
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(small_vec)]
+//! use std::collections::SmallVec;
+//!
+//! let mut v: SmallVec<i32, 4> = SmallVec::new();
+//! v.push(1);
+//! v.push(2);
+//! assert!(!v.spilled());
+//!
+//! v.extend([3, 4, 5]);
+//! assert!(v.spilled());
+//! assert_eq!(v, [1, 2, 3, 4, 5]);
+//! ```
+//!
+//! [`Vec<T>`]: crate::vec::Vec
+
+#![unstable(feature = "small_vec", issue = "none")]
+
+use core::cmp::{self, Ordering};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::ops::{self, RangeBounds};
+use core::ptr::{self, NonNull};
+use core::slice;
+
+use crate::boxed::Box;
+use crate::collections::{TryReserveError, TryReserveErrorKind};
+use crate::vec::Vec;
+
+pub use self::drain::Drain;
+pub use self::extract_if::ExtractIf;
+pub use self::into_iter::IntoIter;
+pub use self::splice::Splice;
+
+mod drain;
+mod extract_if;
+mod into_iter;
+mod splice;
+
+/// A contiguous growable array type with inline storage for `N` elements.
+///
+/// See the [module-level documentation](self) for more.
+pub struct SmallVec<T, const N: usize> {
+    repr: Repr<T, N>,
+}
+
+enum Repr<T, const N: usize> {
+    /// The elements are stored in `buf[..len]`, which is initialized.
+    Inline { len: usize, buf: [MaybeUninit<T>; N] },
+    /// The elements have spilled over into a heap allocation.
+    Heap(Vec<T>),
+}
+
+impl<T, const N: usize> SmallVec<T, N> {
+    /// Constructs a new, empty `SmallVec<T, N>`.
+    ///
+    /// The vector will not allocate until more than `N` elements are pushed
+    /// onto it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(small_vec)]
+    /// use std::collections::SmallVec;
+    ///
+    /// let v: SmallVec<u8, 16> = SmallVec::new();
+    /// assert_eq!(v.capacity(), 16);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        SmallVec { repr: Repr::Inline { len: 0, buf: [const { MaybeUninit::uninit() }; N] } }
+    }
+
+    /// Constructs a new, empty `SmallVec<T, N>` with at least the specified
+    /// capacity.
+    ///
+    /// If `capacity` is at most `N` the vector is created with inline storage
+    /// and does not allocate. Otherwise a heap buffer is allocated up front.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` _bytes_.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity <= N {
+            Self::new()
+        } else {
+            SmallVec { repr: Repr::Heap(Vec::with_capacity(capacity)) }
+        }
+    }
+
+    /// Creates a `SmallVec<T, N>` that takes ownership of the heap buffer of
+    /// `vec`, without copying its elements.
+    ///
+    /// The resulting vector is [spilled](SmallVec::spilled), even if `vec`
+    /// holds `N` or fewer elements; call [`shrink_to_fit`] to move the
+    /// elements back inline.
+    ///
+    /// [`shrink_to_fit`]: SmallVec::shrink_to_fit
+    #[inline]
+    pub fn from_vec(vec: Vec<T>) -> Self {
+        SmallVec { repr: Repr::Heap(vec) }
+    }
+
+    /// Creates a `SmallVec<T, N>` whose inline storage is completely filled
+    /// with the elements of `buf`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(small_vec)]
+    /// use std::collections::SmallVec;
+    ///
+    /// let v = SmallVec::from_buf([1, 2, 3]);
+    /// assert!(!v.spilled());
+    /// assert_eq!(v, [1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn from_buf(buf: [T; N]) -> Self {
+        let buf = ManuallyDrop::new(buf);
+        // SAFETY: `[T; N]` and `[MaybeUninit<T>; N]` have the same layout, and
+        // ownership of the elements is transferred since `buf` is not dropped.
+        let buf = unsafe { ptr::read(&*buf as *const [T; N] as *const [MaybeUninit<T>; N]) };
+        SmallVec { repr: Repr::Inline { len: N, buf } }
+    }
+
+    /// Returns the number of elements that can be stored without spilling to
+    /// the heap, which is always `N`.
+    #[inline]
+    pub const fn inline_capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the elements of this vector are stored on the heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(small_vec)]
+    /// use std::collections::SmallVec;
+    ///
+    /// let mut v: SmallVec<i32, 1> = SmallVec::new();
+    /// v.push(1);
+    /// assert!(!v.spilled());
+    /// v.push(2);
+    /// assert!(v.spilled());
+    /// ```
+    #[inline]
+    pub fn spilled(&self) -> bool {
+        matches!(self.repr, Repr::Heap(_))
+    }
+
+    /// Returns the total number of elements the vector can hold without
+    /// reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        match &self.repr {
+            Repr::Inline { .. } => N,
+            Repr::Heap(vec) => vec.capacity(),
+        }
+    }
+
+    /// Returns the number of elements in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match &self.repr {
+            Repr::Inline { len, .. } => *len,
+            Repr::Heap(vec) => vec.len(),
+        }
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forces the length of the vector to `new_len`.
+    ///
+    /// # Safety
+    ///
+    /// - `new_len` must be less than or equal to [`capacity()`].
+    /// - The elements at `old_len..new_len` must be initialized.
+    ///
+    /// [`capacity()`]: SmallVec::capacity
+    #[inline]
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= self.capacity());
+        match &mut self.repr {
+            Repr::Inline { len, .. } => *len = new_len,
+            // SAFETY: the caller upholds the contract of `Vec::set_len`.
+            Repr::Heap(vec) => unsafe { vec.set_len(new_len) },
+        }
+    }
+
+    /// Returns a raw pointer to the vector's buffer.
+    ///
+    /// The pointer is invalidated whenever the vector spills to the heap or
+    /// moves back inline, as well as whenever the `SmallVec` itself is moved
+    /// while its elements are stored inline.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        match &self.repr {
+            Repr::Inline { buf, .. } => MaybeUninit::slice_as_ptr(buf),
+            Repr::Heap(vec) => vec.as_ptr(),
+        }
+    }
+
+    /// Returns an unsafe mutable pointer to the vector's buffer.
+    ///
+    /// See [`as_ptr`](SmallVec::as_ptr) for when the returned pointer is
+    /// invalidated.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        match &mut self.repr {
+            Repr::Inline { buf, .. } => MaybeUninit::slice_as_mut_ptr(buf),
+            Repr::Heap(vec) => vec.as_mut_ptr(),
+        }
+    }
+
+    /// Extracts a slice containing the entire vector.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `len` elements are always initialized.
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.len()) }
+    }
+
+    /// Extracts a mutable slice of the entire vector.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the first `len` elements are always initialized.
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len()) }
+    }
+
+    /// Reserves capacity for at least `additional` more elements.
+    ///
+    /// If the inline storage cannot hold `len + additional` elements, the
+    /// vector spills to a heap buffer large enough to amortize future
+    /// insertions, like [`Vec::reserve`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` _bytes_.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn reserve(&mut self, additional: usize) {
+        // SAFETY: `len` is the number of initialized elements.
+        unsafe { self.reserve_for(self.len(), additional, false) }
+    }
+
+    /// Reserves the minimum capacity for at least `additional` more elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` _bytes_.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn reserve_exact(&mut self, additional: usize) {
+        // SAFETY: `len` is the number of initialized elements.
+        unsafe { self.reserve_for(self.len(), additional, true) }
+    }
+
+    /// Tries to reserve capacity for at least `additional` more elements.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an
+    /// error is returned and the vector is left unchanged.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.try_reserve_inner(additional, false)
+    }
+
+    /// Tries to reserve the minimum capacity for at least `additional` more
+    /// elements.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an
+    /// error is returned and the vector is left unchanged.
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.try_reserve_inner(additional, true)
+    }
+
+    fn try_reserve_inner(&mut self, additional: usize, exact: bool) -> Result<(), TryReserveError> {
+        let len = self.len();
+        match &mut self.repr {
+            Repr::Heap(vec) if exact => vec.try_reserve_exact(additional),
+            Repr::Heap(vec) => vec.try_reserve(additional),
+            Repr::Inline { .. } if additional <= N - len => Ok(()),
+            Repr::Inline { buf, .. } => {
+                let Some(required) = len.checked_add(additional) else {
+                    return Err(TryReserveErrorKind::CapacityOverflow.into());
+                };
+                let mut vec = Vec::new();
+                if exact {
+                    vec.try_reserve_exact(required)?;
+                } else {
+                    vec.try_reserve(cmp::max(required, N.saturating_mul(2)))?;
+                }
+                // SAFETY: `vec` has room for `len` elements, which are moved
+                // out of the inline buffer and never dropped from there.
+                unsafe {
+                    ptr::copy_nonoverlapping(MaybeUninit::slice_as_ptr(buf), vec.as_mut_ptr(), len);
+                    vec.set_len(len);
+                }
+                self.repr = Repr::Heap(vec);
+                Ok(())
+            }
+        }
+    }
+
+    /// Makes room for `additional` elements past the first `used` slots of the
+    /// buffer, spilling to the heap if the inline storage is too small.
+    ///
+    /// # Safety
+    ///
+    /// `used` must be at least `self.len()` and at most `self.capacity()`. The
+    /// first `used` slots are moved bitwise into the new buffer, whether or not
+    /// they are initialized.
+    #[cfg(not(no_global_oom_handling))]
+    unsafe fn reserve_for(&mut self, used: usize, additional: usize, exact: bool) {
+        let len = self.len();
+        debug_assert!(len <= used && used <= self.capacity());
+        match &mut self.repr {
+            Repr::Heap(vec) if exact => vec.reserve_exact(used - len + additional),
+            Repr::Heap(vec) => vec.reserve(used - len + additional),
+            Repr::Inline { .. } if additional <= N - used => {}
+            Repr::Inline { buf, .. } => {
+                let Some(required) = used.checked_add(additional) else {
+                    capacity_overflow();
+                };
+                let cap = if exact { required } else { cmp::max(required, N.saturating_mul(2)) };
+                let mut vec = Vec::with_capacity(cap);
+                // SAFETY: `vec` has room for `used` elements. The caller
+                // guarantees that the first `len` of them are initialized.
+                unsafe {
+                    ptr::copy_nonoverlapping(
+                        MaybeUninit::slice_as_ptr(buf),
+                        vec.as_mut_ptr(),
+                        used,
+                    );
+                    vec.set_len(len);
+                }
+                self.repr = Repr::Heap(vec);
+            }
+        }
+    }
+
+    /// Shrinks the capacity of the vector as much as possible.
+    ///
+    /// If the vector has spilled but its elements now fit into the inline
+    /// storage, they are moved back inline and the heap buffer is freed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(small_vec)]
+    /// use std::collections::SmallVec;
+    ///
+    /// let mut v: SmallVec<i32, 2> = SmallVec::from_iter([1, 2, 3]);
+    /// assert!(v.spilled());
+    /// v.pop();
+    /// v.shrink_to_fit();
+    /// assert!(!v.spilled());
+    /// assert_eq!(v, [1, 2]);
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    pub fn shrink_to_fit(&mut self) {
+        let Repr::Heap(vec) = &mut self.repr else { return };
+        let len = vec.len();
+        if len > N {
+            vec.shrink_to_fit();
+            return;
+        }
+        let mut buf = [const { MaybeUninit::uninit() }; N];
+        // SAFETY: `buf` has room for all `len` elements, which are moved out of
+        // `vec` before it is dropped.
+        unsafe {
+            ptr::copy_nonoverlapping(vec.as_ptr(), MaybeUninit::slice_as_mut_ptr(&mut buf), len);
+            vec.set_len(0);
+        }
+        self.repr = Repr::Inline { len, buf };
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and dropping the
+    /// rest.
+    ///
+    /// If `len` is greater or equal to the vector's current length, this has
+    /// no effect. This never moves the elements back inline.
+    pub fn truncate(&mut self, len: usize) {
+        let old_len = self.len();
+        if len >= old_len {
+            return;
+        }
+        // SAFETY: the elements at `len..old_len` are initialized, and are no
+        // longer considered part of the vector when they are dropped, so a
+        // panicking destructor cannot cause a double drop.
+        unsafe {
+            let tail = ptr::slice_from_raw_parts_mut(self.as_mut_ptr().add(len), old_len - len);
+            self.set_len(len);
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Clears the vector, removing all values.
+    ///
+    /// This has no effect on the allocated capacity of the vector.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Appends an element to the back of the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` _bytes_.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        let len = self.len();
+        if len == self.capacity() {
+            self.reserve(1);
+        }
+        // SAFETY: there is room for at least one more element.
+        unsafe {
+            ptr::write(self.as_mut_ptr().add(len), value);
+            self.set_len(len + 1);
+        }
+    }
+
+    /// Removes the last element from the vector and returns it, or [`None`]
+    /// if it is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        // SAFETY: the element at `len - 1` is initialized and is no longer
+        // considered part of the vector after it is read.
+        unsafe {
+            self.set_len(len - 1);
+            Some(ptr::read(self.as_ptr().add(len - 1)))
+        }
+    }
+
+    /// Inserts an element at position `index`, shifting all elements after it
+    /// to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    #[cfg(not(no_global_oom_handling))]
+    #[track_caller]
+    pub fn insert(&mut self, index: usize, element: T) {
+        #[cold]
+        #[cfg_attr(not(feature = "panic_immediate_abort"), inline(never))]
+        #[track_caller]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("insertion index (is {index}) should be <= len (is {len})");
+        }
+
+        let len = self.len();
+        if index > len {
+            assert_failed(index, len);
+        }
+        if len == self.capacity() {
+            self.reserve(1);
+        }
+        // SAFETY: there is room for one more element, and `index <= len`.
+        unsafe {
+            let p = self.as_mut_ptr().add(index);
+            if index < len {
+                ptr::copy(p, p.add(1), len - index);
+            }
+            ptr::write(p, element);
+            self.set_len(len + 1);
+        }
+    }
+
+    /// Removes and returns the element at position `index`, shifting all
+    /// elements after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn remove(&mut self, index: usize) -> T {
+        #[cold]
+        #[cfg_attr(not(feature = "panic_immediate_abort"), inline(never))]
+        #[track_caller]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("removal index (is {index}) should be < len (is {len})");
+        }
+
+        let len = self.len();
+        if index >= len {
+            assert_failed(index, len);
+        }
+        // SAFETY: `index` is in bounds; the hole it leaves is closed before
+        // the length is updated.
+        unsafe {
+            let p = self.as_mut_ptr().add(index);
+            let ret = ptr::read(p);
+            ptr::copy(p.add(1), p, len - index - 1);
+            self.set_len(len - 1);
+            ret
+        }
+    }
+
+    /// Removes an element from the vector and returns it, replacing it with
+    /// the last element.
+    ///
+    /// This does not preserve ordering, but is *O*(1).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        #[cold]
+        #[cfg_attr(not(feature = "panic_immediate_abort"), inline(never))]
+        #[track_caller]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("swap_remove index (is {index}) should be < len (is {len})");
+        }
+
+        let len = self.len();
+        if index >= len {
+            assert_failed(index, len);
+        }
+        // SAFETY: `index` is in bounds, and the last element is moved into its
+        // place before the length is updated.
+        unsafe {
+            let base_ptr = self.as_mut_ptr();
+            let value = ptr::read(base_ptr.add(index));
+            ptr::copy(base_ptr.add(len - 1), base_ptr.add(index), 1);
+            self.set_len(len - 1);
+            value
+        }
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` _bytes_.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn append<const M: usize>(&mut self, other: &mut SmallVec<T, M>) {
+        let count = other.len();
+        self.reserve(count);
+        let len = self.len();
+        // SAFETY: `self` has room for `count` more elements, which are moved
+        // out of `other` and forgotten there.
+        unsafe {
+            ptr::copy_nonoverlapping(other.as_ptr(), self.as_mut_ptr().add(len), count);
+            other.set_len(0);
+            self.set_len(len + count);
+        }
+    }
+
+    /// Splits the vector into two at the given index.
+    ///
+    /// Returns a newly allocated vector containing the elements in the range
+    /// `[at, len)`. After the call, the original vector will be left
+    /// containing the elements `[0, at)` with its previous capacity unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    #[cfg(not(no_global_oom_handling))]
+    #[must_use = "use `.truncate()` if you don't need the other half"]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        #[cold]
+        #[cfg_attr(not(feature = "panic_immediate_abort"), inline(never))]
+        #[track_caller]
+        fn assert_failed(at: usize, len: usize) -> ! {
+            panic!("`at` split index (is {at}) should be <= len (is {len})");
+        }
+
+        let len = self.len();
+        if at > len {
+            assert_failed(at, len);
+        }
+        let other_len = len - at;
+        let mut other = Self::with_capacity(other_len);
+        // SAFETY: `other` has room for the `other_len` elements, which are
+        // moved out of `self`.
+        unsafe {
+            self.set_len(at);
+            ptr::copy_nonoverlapping(self.as_ptr().add(at), other.as_mut_ptr(), other_len);
+            other.set_len(other_len);
+        }
+        other
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all elements `e` for which `f(&e)` returns
+    /// `false`. This method operates in place and preserves the order of the
+    /// retained elements.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|elem| f(elem));
+    }
+
+    /// Retains only the elements specified by the predicate, passing a mutable
+    /// reference to it.
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        self.extract_if(|elem| !f(elem)).for_each(drop);
+    }
+
+    /// Removes all but the first of consecutive elements in the vector that
+    /// resolve to the same key.
+    #[inline]
+    pub fn dedup_by_key<F, K>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    /// Removes all but the first of consecutive elements in the vector
+    /// satisfying a given equality relation.
+    ///
+    /// The `same_bucket` function is passed references to two elements from
+    /// the vector and must determine if the elements compare equal. The
+    /// elements are passed in opposite order from their order in the slice, so
+    /// if `same_bucket(a, b)` returns `true`, `a` is removed.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let len = self.len();
+        if len <= 1 {
+            return;
+        }
+
+        /* INVARIANT: vec.len() > read >= write > write-1 >= 0 */
+        struct FillGapOnDrop<'a, T, const N: usize> {
+            /* Offset of the element we want to check if it is duplicate */
+            read: usize,
+
+            /* Offset of the place where we want to place the non-duplicate
+             * when we find it. */
+            write: usize,
+
+            /* The SmallVec that would need correction if `same_bucket` panicked */
+            vec: &'a mut SmallVec<T, N>,
+        }
+
+        impl<'a, T, const N: usize> Drop for FillGapOnDrop<'a, T, N> {
+            fn drop(&mut self) {
+                /* This code gets executed when `same_bucket` panics */
+
+                /* SAFETY: invariant guarantees that `read - write`
+                 * and `len - read` never overflow and that the copy is always
+                 * in-bounds. */
+                unsafe {
+                    let ptr = self.vec.as_mut_ptr();
+                    let len = self.vec.len();
+
+                    /* How many items were left when `same_bucket` panicked.
+                     * Basically vec[read..].len() */
+                    let items_left = len.wrapping_sub(self.read);
+
+                    /* Pointer to first item in vec[write..write+items_left] slice */
+                    let dropped_ptr = ptr.add(self.write);
+                    /* Pointer to first item in vec[read..] slice */
+                    let valid_ptr = ptr.add(self.read);
+
+                    /* Copy `vec[read..]` to `vec[write..write+items_left]`.
+                     * The slices can overlap, so `copy_nonoverlapping` cannot be used */
+                    ptr::copy(valid_ptr, dropped_ptr, items_left);
+
+                    /* How many items have been already dropped
+                     * Basically count of duplicate elements */
+                    let dropped = self.read.wrapping_sub(self.write);
+
+                    self.vec.set_len(len - dropped);
+                }
+            }
+        }
+
+        let mut gap = FillGapOnDrop { read: 1, write: 1, vec: self };
+        let ptr = gap.vec.as_mut_ptr();
+
+        /* Drop items while going through SmallVec, it should be more efficient
+         * than doing slice partition_dedup + truncate */
+
+        /* SAFETY: Because of the invariant, read_ptr, prev_ptr and write_ptr
+         * are always in-bounds and read_ptr never aliases prev_ptr */
+        unsafe {
+            while gap.read < len {
+                let read_ptr = ptr.add(gap.read);
+                let prev_ptr = ptr.add(gap.write.wrapping_sub(1));
+
+                if same_bucket(&mut *read_ptr, &mut *prev_ptr) {
+                    // Increase `gap.read` now since the drop may panic.
+                    gap.read += 1;
+                    /* We have found duplicate, drop it in-place */
+                    ptr::drop_in_place(read_ptr);
+                } else {
+                    let write_ptr = ptr.add(gap.write);
+
+                    /* Because `read_ptr` can be equal to `write_ptr`, we either
+                     * have to use `copy` or conditional `copy_nonoverlapping`.
+                     * Looks like the first option is faster. */
+                    ptr::copy(read_ptr, write_ptr, 1);
+
+                    /* We have filled that place, so go further */
+                    gap.write += 1;
+                    gap.read += 1;
+                }
+            }
+
+            /* Technically we could let `gap` clean up with its Drop, but
+             * when `same_bucket` is guaranteed to not panic, this bloats a little
+             * the codegen, so we just do it manually */
+            gap.vec.set_len(gap.write);
+            mem::forget(gap);
+        }
+    }
+
+    /// Removes the specified range from the vector in bulk, returning all
+    /// removed elements as an iterator.
+    ///
+    /// See [`Vec::drain`] for the exact semantics; the same rules apply here.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, N>
+    where
+        R: RangeBounds<usize>,
+    {
+        let len = self.len();
+        let ops::Range { start, end } = slice::range(range, ..len);
+
+        // SAFETY: the drained range is in bounds, and the length is set to
+        // `start` to be safe in case `Drain` is leaked.
+        unsafe {
+            self.set_len(start);
+            let range_slice = slice::from_raw_parts(self.as_ptr().add(start), end - start);
+            Drain {
+                tail_start: end,
+                tail_len: len - end,
+                iter: range_slice.iter(),
+                vec: NonNull::from(self),
+            }
+        }
+    }
+
+    /// Creates a splicing iterator that replaces the specified range in the
+    /// vector with the given `replace_with` iterator and yields the removed
+    /// items.
+    ///
+    /// See [`Vec::splice`] for the exact semantics; the same rules apply here.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter, N>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        Splice { drain: self.drain(range), replace_with: replace_with.into_iter() }
+    }
+
+    /// Creates an iterator which uses a closure to determine if an element
+    /// should be removed.
+    ///
+    /// See [`Vec::extract_if`] for the exact semantics; the same rules apply
+    /// here.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(small_vec)]
+    /// use std::collections::SmallVec;
+    ///
+    /// let mut numbers: SmallVec<i32, 8> = SmallVec::from_iter([1, 2, 3, 4, 5, 6]);
+    /// let evens: Vec<_> = numbers.extract_if(|x| *x % 2 == 0).collect();
+    ///
+    /// assert_eq!(evens, [2, 4, 6]);
+    /// assert_eq!(numbers, [1, 3, 5]);
+    /// ```
+    pub fn extract_if<F>(&mut self, filter: F) -> ExtractIf<'_, T, F, N>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let old_len = self.len();
+
+        // Guard against us getting leaked (leak amplification)
+        unsafe {
+            self.set_len(0);
+        }
+
+        ExtractIf { vec: self, idx: 0, del: 0, old_len, pred: filter }
+    }
+
+    /// Converts the vector into a [`Vec<T>`].
+    ///
+    /// If the vector has spilled, its heap buffer is reused without copying.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn into_vec(self) -> Vec<T> {
+        let mut this = ManuallyDrop::new(self);
+        match &mut this.repr {
+            Repr::Heap(vec) => mem::take(vec),
+            Repr::Inline { len, buf } => {
+                let len = *len;
+                let mut vec = Vec::with_capacity(len);
+                // SAFETY: `vec` has room for `len` elements, which are moved
+                // out of the inline buffer; `this` is never dropped.
+                unsafe {
+                    ptr::copy_nonoverlapping(MaybeUninit::slice_as_ptr(buf), vec.as_mut_ptr(), len);
+                    vec.set_len(len);
+                }
+                vec
+            }
+        }
+    }
+
+    /// Converts the vector into [`Box<[T]>`][owned slice].
+    ///
+    /// [owned slice]: Box
+    #[cfg(not(no_global_oom_handling))]
+    pub fn into_boxed_slice(self) -> Box<[T]> {
+        self.into_vec().into_boxed_slice()
+    }
+
+    /// Converts the vector into `[T; N]` if it holds exactly `N` elements,
+    /// or returns it unchanged otherwise.
+    pub fn into_inner(self) -> Result<[T; N], Self> {
+        if self.len() != N {
+            return Err(self);
+        }
+        let mut this = ManuallyDrop::new(self);
+        // SAFETY: the vector holds exactly `N` initialized elements, which are
+        // moved out; the heap buffer, if any, is freed without dropping them.
+        unsafe {
+            let array = ptr::read(this.as_ptr() as *const [T; N]);
+            this.set_len(0);
+            ptr::drop_in_place(&mut this.repr);
+            Ok(array)
+        }
+    }
+}
+
+impl<T: Clone, const N: usize> SmallVec<T, N> {
+    /// Clones and appends all elements in a slice to the vector.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn extend_from_slice(&mut self, other: &[T]) {
+        self.extend(other.iter().cloned())
+    }
+
+    /// Resizes the vector in-place so that `len` is equal to `new_len`,
+    /// filling any new slots with clones of `value`.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn resize(&mut self, new_len: usize, value: T) {
+        let len = self.len();
+        if new_len > len {
+            self.extend(core::iter::repeat_n(value, new_len - len));
+        } else {
+            self.truncate(new_len);
+        }
+    }
+}
+
+impl<T, const N: usize> SmallVec<T, N> {
+    /// Resizes the vector in-place so that `len` is equal to `new_len`,
+    /// filling any new slots with values returned by calling `f`.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn resize_with<F>(&mut self, new_len: usize, f: F)
+    where
+        F: FnMut() -> T,
+    {
+        let len = self.len();
+        if new_len > len {
+            self.extend(core::iter::repeat_with(f).take(new_len - len));
+        } else {
+            self.truncate(new_len);
+        }
+    }
+}
+
+impl<T: PartialEq, const N: usize> SmallVec<T, N> {
+    /// Removes consecutive repeated elements in the vector according to the
+    /// [`PartialEq`] trait implementation.
+    #[inline]
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b)
+    }
+}
+
+unsafe impl<#[may_dangle] T, const N: usize> Drop for SmallVec<T, N> {
+    fn drop(&mut self) {
+        if let Repr::Inline { len, buf } = &mut self.repr {
+            // SAFETY: the first `len` elements are initialized and owned by
+            // the vector. A heap buffer is dropped by `Vec` itself.
+            unsafe {
+                ptr::drop_in_place(MaybeUninit::slice_assume_init_mut(&mut buf[..*len]));
+            }
+        }
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[cold]
+#[cfg_attr(not(feature = "panic_immediate_abort"), inline(never))]
+fn capacity_overflow() -> ! {
+    panic!("capacity overflow");
+}
+
+impl<T, const N: usize> ops::Deref for SmallVec<T, N> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> ops::DerefMut for SmallVec<T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for SmallVec<T, N> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, const N: usize> AsMut<[T]> for SmallVec<T, N> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, const N: usize> Default for SmallVec<T, N> {
+    /// Creates an empty `SmallVec<T, N>`.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T: Clone, const N: usize> Clone for SmallVec<T, N> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.clear();
+        self.extend_from_slice(source);
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for SmallVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: Hash, const N: usize> Hash for SmallVec<T, N> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(&**self, state)
+    }
+}
+
+impl<T, U, const N: usize, const M: usize> PartialEq<SmallVec<U, M>> for SmallVec<T, N>
+where
+    T: PartialEq<U>,
+{
+    #[inline]
+    fn eq(&self, other: &SmallVec<U, M>) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<T, U, const N: usize> PartialEq<[U]> for SmallVec<T, N>
+where
+    T: PartialEq<U>,
+{
+    #[inline]
+    fn eq(&self, other: &[U]) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<T, U, const N: usize, const M: usize> PartialEq<[U; M]> for SmallVec<T, N>
+where
+    T: PartialEq<U>,
+{
+    #[inline]
+    fn eq(&self, other: &[U; M]) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<T, U, const N: usize> PartialEq<Vec<U>> for SmallVec<T, N>
+where
+    T: PartialEq<U>,
+{
+    #[inline]
+    fn eq(&self, other: &Vec<U>) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for SmallVec<T, N> {}
+
+impl<T: PartialOrd, const N: usize> PartialOrd for SmallVec<T, N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<T: Ord, const N: usize> Ord for SmallVec<T, N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T, const N: usize> Extend<T> for SmallVec<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        let (lower_bound, _) = iter.size_hint();
+        self.reserve(lower_bound);
+        iter.for_each(move |element| self.push(element));
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<'a, T: Copy + 'a, const N: usize> Extend<&'a T> for SmallVec<T, N> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T, const N: usize> FromIterator<T> for SmallVec<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = SmallVec::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<T, const N: usize> IntoIterator for SmallVec<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self)
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a SmallVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut SmallVec<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, const N: usize> From<Vec<T>> for SmallVec<T, N> {
+    /// Takes ownership of the heap buffer of `vec`. See [`SmallVec::from_vec`].
+    fn from(vec: Vec<T>) -> Self {
+        Self::from_vec(vec)
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for SmallVec<T, N> {
+    fn from(buf: [T; N]) -> Self {
+        Self::from_buf(buf)
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T: Clone, const N: usize> From<&[T]> for SmallVec<T, N> {
+    fn from(s: &[T]) -> Self {
+        s.iter().cloned().collect()
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T, const N: usize> From<SmallVec<T, N>> for Vec<T> {
+    fn from(vec: SmallVec<T, N>) -> Self {
+        vec.into_vec()
+    }
+}
@@ -0,0 +1,816 @@
+//! A double-ended queue implemented with a growable ring buffer that stores a
+//! small number of elements inline before spilling to the heap, written as
+//! `SmallVecDeque<T, N>`.
+//!
+//! A [`SmallVecDeque<T, N>`] is to [`VecDeque<T>`] what a
+//! [`SmallVec<T, N>`] is to a `Vec<T>`: as long as it holds at most `N`
+//! elements they are kept in a ring buffer inside the `SmallVecDeque` value
+//! itself. Once it grows past `N` the elements are moved into a `VecDeque<T>`
+//! and the `SmallVecDeque` behaves exactly like one from then on.
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(small_vec)]
+//! use std::collections::SmallVecDeque;
+//!
+//! let mut d: SmallVecDeque<i32, 4> = SmallVecDeque::new();
+//! d.push_back(2);
+//! d.push_back(3);
+//! d.push_front(1);
+//! assert!(!d.spilled());
+//!
+//! d.extend([4, 5]);
+//! assert!(d.spilled());
+//! assert_eq!(d, [1, 2, 3, 4, 5]);
+//! ```
+//!
+//! [`VecDeque<T>`]: crate::collections::VecDeque
+//! [`SmallVec<T, N>`]: crate::collections::SmallVec
+
+#![unstable(feature = "small_vec", issue = "none")]
+
+use core::cmp::{self, Ordering};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::ops::{Index, IndexMut};
+use core::ptr;
+
+use crate::collections::vec_deque::{Iter, IterMut, VecDeque};
+
+pub use self::into_iter::IntoIter;
+
+mod into_iter;
+
+/// A double-ended queue with inline storage for `N` elements.
+///
+/// See the [module-level documentation](self) for more.
+pub struct SmallVecDeque<T, const N: usize> {
+    repr: Repr<T, N>,
+}
+
+enum Repr<T, const N: usize> {
+    /// The element at index `i` is stored in `buf[(head + i) % N]`, and the
+    /// `len` slots starting at `head` are initialized.
+    Inline { head: usize, len: usize, buf: [MaybeUninit<T>; N] },
+    /// The elements have spilled over into a heap-allocated `VecDeque`.
+    Heap(VecDeque<T>),
+}
+
+/// Returns the physical index of `index` in a ring buffer of capacity `cap`,
+/// for any `index < 2 * cap`.
+#[inline]
+fn wrap_index(index: usize, cap: usize) -> usize {
+    if index >= cap { index - cap } else { index }
+}
+
+impl<T, const N: usize> SmallVecDeque<T, N> {
+    /// Constructs a new, empty `SmallVecDeque<T, N>`.
+    ///
+    /// The deque will not allocate until more than `N` elements are pushed
+    /// onto it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(small_vec)]
+    /// use std::collections::SmallVecDeque;
+    ///
+    /// let d: SmallVecDeque<u8, 16> = SmallVecDeque::new();
+    /// assert_eq!(d.capacity(), 16);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        SmallVecDeque {
+            repr: Repr::Inline { head: 0, len: 0, buf: [const { MaybeUninit::uninit() }; N] },
+        }
+    }
+
+    /// Constructs a new, empty `SmallVecDeque<T, N>` with at least the
+    /// specified capacity.
+    ///
+    /// If `capacity` is at most `N` the deque is created with inline storage
+    /// and does not allocate. Otherwise a heap buffer is allocated up front.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` _bytes_.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity <= N {
+            Self::new()
+        } else {
+            SmallVecDeque { repr: Repr::Heap(VecDeque::with_capacity(capacity)) }
+        }
+    }
+
+    /// Creates a `SmallVecDeque<T, N>` that takes ownership of the heap
+    /// buffer of `deque`, without copying its elements.
+    ///
+    /// The resulting deque is [spilled](SmallVecDeque::spilled), even if
+    /// `deque` holds `N` or fewer elements; call [`shrink_to_fit`] to move the
+    /// elements back inline.
+    ///
+    /// [`shrink_to_fit`]: SmallVecDeque::shrink_to_fit
+    #[inline]
+    pub fn from_vec_deque(deque: VecDeque<T>) -> Self {
+        SmallVecDeque { repr: Repr::Heap(deque) }
+    }
+
+    /// Creates a `SmallVecDeque<T, N>` whose inline storage is completely
+    /// filled with the elements of `buf`, in order from front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(small_vec)]
+    /// use std::collections::SmallVecDeque;
+    ///
+    /// let d = SmallVecDeque::from_buf([1, 2, 3]);
+    /// assert!(!d.spilled());
+    /// assert_eq!(d.front(), Some(&1));
+    /// ```
+    #[inline]
+    pub fn from_buf(buf: [T; N]) -> Self {
+        let buf = ManuallyDrop::new(buf);
+        // SAFETY: `[T; N]` and `[MaybeUninit<T>; N]` have the same layout, and
+        // ownership of the elements is transferred since `buf` is not dropped.
+        let buf = unsafe { ptr::read(&*buf as *const [T; N] as *const [MaybeUninit<T>; N]) };
+        SmallVecDeque { repr: Repr::Inline { head: 0, len: N, buf } }
+    }
+
+    /// Returns the number of elements the deque can hold without spilling to
+    /// the heap, which is always `N`.
+    #[inline]
+    pub const fn inline_capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the elements have been moved into a heap allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(small_vec)]
+    /// use std::collections::SmallVecDeque;
+    ///
+    /// let mut d: SmallVecDeque<u8, 1> = SmallVecDeque::new();
+    /// d.push_back(1);
+    /// assert!(!d.spilled());
+    /// d.push_front(0);
+    /// assert!(d.spilled());
+    /// ```
+    #[inline]
+    pub fn spilled(&self) -> bool {
+        matches!(self.repr, Repr::Heap(_))
+    }
+
+    /// Returns the number of elements the deque can hold without
+    /// reallocating.
+    ///
+    /// This is `N` while the elements are stored inline.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        match &self.repr {
+            Repr::Inline { .. } => N,
+            Repr::Heap(deque) => deque.capacity(),
+        }
+    }
+
+    /// Returns the number of elements in the deque.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match &self.repr {
+            Repr::Inline { len, .. } => *len,
+            Repr::Heap(deque) => deque.len(),
+        }
+    }
+
+    /// Returns `true` if the deque contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Provides a reference to the element at the given index.
+    ///
+    /// Element at index 0 is the front of the queue.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        match &self.repr {
+            Repr::Inline { head, len, buf } => {
+                if index >= *len {
+                    return None;
+                }
+                // SAFETY: `index < len`, so the slot is initialized.
+                Some(unsafe { buf[wrap_index(*head + index, N)].assume_init_ref() })
+            }
+            Repr::Heap(deque) => deque.get(index),
+        }
+    }
+
+    /// Provides a mutable reference to the element at the given index.
+    ///
+    /// Element at index 0 is the front of the queue.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        match &mut self.repr {
+            Repr::Inline { head, len, buf } => {
+                if index >= *len {
+                    return None;
+                }
+                // SAFETY: `index < len`, so the slot is initialized.
+                Some(unsafe { buf[wrap_index(*head + index, N)].assume_init_mut() })
+            }
+            Repr::Heap(deque) => deque.get_mut(index),
+        }
+    }
+
+    /// Provides a reference to the front element, or `None` if the deque is
+    /// empty.
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Provides a mutable reference to the front element, or `None` if the
+    /// deque is empty.
+    #[inline]
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Provides a reference to the back element, or `None` if the deque is
+    /// empty.
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len().wrapping_sub(1))
+    }
+
+    /// Provides a mutable reference to the back element, or `None` if the
+    /// deque is empty.
+    #[inline]
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len().wrapping_sub(1))
+    }
+
+    /// Appends an element to the back of the deque.
+    ///
+    /// If the inline storage is full, the elements are first moved into a
+    /// heap allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` _bytes_.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn push_back(&mut self, value: T) {
+        if let Repr::Inline { head, len, buf } = &mut self.repr {
+            if *len < N {
+                buf[wrap_index(*head + *len, N)].write(value);
+                *len += 1;
+                return;
+            }
+        }
+        self.spill(1).push_back(value);
+    }
+
+    /// Prepends an element to the front of the deque.
+    ///
+    /// If the inline storage is full, the elements are first moved into a
+    /// heap allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` _bytes_.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn push_front(&mut self, value: T) {
+        if let Repr::Inline { head, len, buf } = &mut self.repr {
+            if *len < N {
+                *head = if *head == 0 { N - 1 } else { *head - 1 };
+                buf[*head].write(value);
+                *len += 1;
+                return;
+            }
+        }
+        self.spill(1).push_front(value);
+    }
+
+    /// Removes the last element and returns it, or `None` if the deque is
+    /// empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        match &mut self.repr {
+            Repr::Inline { head, len, buf } => {
+                if *len == 0 {
+                    return None;
+                }
+                *len -= 1;
+                // SAFETY: the slot of the last element is initialized, and is
+                // no longer part of the deque.
+                Some(unsafe { buf[wrap_index(*head + *len, N)].assume_init_read() })
+            }
+            Repr::Heap(deque) => deque.pop_back(),
+        }
+    }
+
+    /// Removes the first element and returns it, or `None` if the deque is
+    /// empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        match &mut self.repr {
+            Repr::Inline { head, len, buf } => {
+                if *len == 0 {
+                    return None;
+                }
+                // SAFETY: the slot of the first element is initialized, and is
+                // no longer part of the deque once `head` has moved past it.
+                let value = unsafe { buf[*head].assume_init_read() };
+                *head = wrap_index(*head + 1, N);
+                *len -= 1;
+                Some(value)
+            }
+            Repr::Heap(deque) => deque.pop_front(),
+        }
+    }
+
+    /// Inserts an element at `index` within the deque, shifting all elements
+    /// with indices greater than or equal to `index` towards the back.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the deque's length, or if the new
+    /// capacity exceeds `isize::MAX` _bytes_.
+    #[cfg(not(no_global_oom_handling))]
+    #[track_caller]
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(index <= self.len(), "index out of bounds");
+        if self.spilled() || self.len() == N {
+            self.spill(1).insert(index, value);
+            return;
+        }
+        self.make_contiguous();
+        let Repr::Inline { len, buf, .. } = &mut self.repr else { unreachable!() };
+        // SAFETY: the elements are stored in `buf[..len]` after
+        // `make_contiguous`, and `len < N`, so there is room to shift them.
+        unsafe {
+            let p = buf.as_mut_ptr().add(index);
+            ptr::copy(p, p.add(1), *len - index);
+            (*p).write(value);
+        }
+        *len += 1;
+    }
+
+    /// Removes and returns the element at `index` from the deque, shifting
+    /// all elements after it towards the front. Returns `None` if `index` is
+    /// out of bounds.
+    ///
+    /// Element at index 0 is the front of the queue.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if let Repr::Heap(deque) = &mut self.repr {
+            return deque.remove(index);
+        }
+        if index >= self.len() {
+            return None;
+        }
+        self.make_contiguous();
+        let Repr::Inline { len, buf, .. } = &mut self.repr else { unreachable!() };
+        // SAFETY: the elements are stored in `buf[..len]` after
+        // `make_contiguous`, and `index < len`. The removed slot is
+        // overwritten by the elements after it.
+        let value = unsafe {
+            let p = buf.as_mut_ptr().add(index);
+            let value = (*p).assume_init_read();
+            ptr::copy(p.add(1), p, *len - index - 1);
+            value
+        };
+        *len -= 1;
+        Some(value)
+    }
+
+    /// Swaps the elements at indices `i` and `j`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    #[track_caller]
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let len = self.len();
+        assert!(i < len);
+        assert!(j < len);
+        match &mut self.repr {
+            Repr::Inline { head, buf, .. } => {
+                buf.swap(wrap_index(*head + i, N), wrap_index(*head + j, N))
+            }
+            Repr::Heap(deque) => deque.swap(i, j),
+        }
+    }
+
+    /// Shortens the deque, keeping the first `len` elements and dropping the
+    /// rest.
+    ///
+    /// If `len` is greater than or equal to the deque's current length, this
+    /// has no effect. A spilled deque stays spilled.
+    pub fn truncate(&mut self, len: usize) {
+        if let Repr::Heap(deque) = &mut self.repr {
+            deque.truncate(len);
+            return;
+        }
+        while self.len() > len {
+            self.pop_back();
+        }
+    }
+
+    /// Removes all elements from the deque.
+    ///
+    /// A spilled deque keeps its heap allocation; call [`shrink_to_fit`] to
+    /// move back to inline storage.
+    ///
+    /// [`shrink_to_fit`]: SmallVecDeque::shrink_to_fit
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Returns `true` if the deque contains an element equal to the given
+    /// value.
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let (a, b) = self.as_slices();
+        a.contains(x) || b.contains(x)
+    }
+
+    /// Reserves capacity for at least `additional` more elements.
+    ///
+    /// If the elements are stored inline and do not fit together with
+    /// `additional` more, they are moved into a heap allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` _bytes_.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn reserve(&mut self, additional: usize) {
+        if let Repr::Heap(deque) = &mut self.repr {
+            deque.reserve(additional);
+        } else if additional > N - self.len() {
+            self.spill(additional);
+        }
+    }
+
+    /// Moves the elements back into inline storage if they fit, or shrinks
+    /// the heap allocation as much as possible otherwise.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn shrink_to_fit(&mut self) {
+        let Repr::Heap(deque) = &mut self.repr else { return };
+        let len = deque.len();
+        if len > N {
+            deque.shrink_to_fit();
+            return;
+        }
+        let mut buf = [const { MaybeUninit::uninit() }; N];
+        for slot in &mut buf[..len] {
+            slot.write(deque.pop_front().unwrap());
+        }
+        self.repr = Repr::Inline { head: 0, len, buf };
+    }
+
+    /// Moves the elements into a heap allocation with room for at least
+    /// `additional` more, and returns it. Does nothing if the deque has
+    /// already spilled.
+    #[cfg(not(no_global_oom_handling))]
+    fn spill(&mut self, additional: usize) -> &mut VecDeque<T> {
+        if let Repr::Inline { head, len, buf } = &mut self.repr {
+            let Some(required) = len.checked_add(additional) else {
+                capacity_overflow();
+            };
+            let mut deque = VecDeque::with_capacity(cmp::max(required, N.saturating_mul(2)));
+            for i in 0..*len {
+                // SAFETY: the `len` slots starting at `head` are initialized,
+                // and each one is moved out exactly once. The inline buffer is
+                // replaced below without dropping its contents.
+                deque.push_back(unsafe { buf[wrap_index(*head + i, N)].assume_init_read() });
+            }
+            self.repr = Repr::Heap(deque);
+        }
+        match &mut self.repr {
+            Repr::Heap(deque) => deque,
+            Repr::Inline { .. } => unreachable!(),
+        }
+    }
+
+    /// Returns a pair of slices which contain, in order, the contents of the
+    /// deque.
+    ///
+    /// If [`make_contiguous`] was previously called, all elements of the
+    /// deque will be in the first slice and the second slice will be empty.
+    ///
+    /// [`make_contiguous`]: SmallVecDeque::make_contiguous
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        match &self.repr {
+            Repr::Inline { head, len, buf } => {
+                let (wrapped, tail) = buf.split_at(*head);
+                let front_len = cmp::min(*len, N - *head);
+                // SAFETY: the `len` slots starting at `head` are initialized.
+                // The first `front_len` of them are at the start of `tail`,
+                // and the remaining ones have wrapped around to the start of
+                // `buf`.
+                unsafe {
+                    (
+                        MaybeUninit::slice_assume_init_ref(&tail[..front_len]),
+                        MaybeUninit::slice_assume_init_ref(&wrapped[..*len - front_len]),
+                    )
+                }
+            }
+            Repr::Heap(deque) => deque.as_slices(),
+        }
+    }
+
+    /// Returns a pair of slices which contain, in order, the contents of the
+    /// deque.
+    ///
+    /// If [`make_contiguous`] was previously called, all elements of the
+    /// deque will be in the first slice and the second slice will be empty.
+    ///
+    /// [`make_contiguous`]: SmallVecDeque::make_contiguous
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        match &mut self.repr {
+            Repr::Inline { head, len, buf } => {
+                let (wrapped, tail) = buf.split_at_mut(*head);
+                let front_len = cmp::min(*len, N - *head);
+                // SAFETY: see `as_slices`.
+                unsafe {
+                    (
+                        MaybeUninit::slice_assume_init_mut(&mut tail[..front_len]),
+                        MaybeUninit::slice_assume_init_mut(&mut wrapped[..*len - front_len]),
+                    )
+                }
+            }
+            Repr::Heap(deque) => deque.as_mut_slices(),
+        }
+    }
+
+    /// Rearranges the internal storage of the deque so it is one contiguous
+    /// slice, which is then returned.
+    ///
+    /// This does not change the order of the elements, and never spills the
+    /// deque to the heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(small_vec)]
+    /// use std::collections::SmallVecDeque;
+    ///
+    /// let mut d: SmallVecDeque<i32, 4> = SmallVecDeque::new();
+    /// d.push_back(2);
+    /// d.push_back(3);
+    /// d.push_front(1);
+    /// assert_eq!(d.make_contiguous(), &[1, 2, 3]);
+    /// assert_eq!(d.as_slices(), (&[1, 2, 3][..], &[][..]));
+    /// ```
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        match &mut self.repr {
+            Repr::Inline { head, len, buf } => {
+                // Rotating moves the slot at `head` to the start of the
+                // buffer, keeping the order of the others.
+                buf.rotate_left(*head);
+                *head = 0;
+                // SAFETY: the elements are now stored in `buf[..len]`.
+                unsafe { MaybeUninit::slice_assume_init_mut(&mut buf[..*len]) }
+            }
+            Repr::Heap(deque) => deque.make_contiguous(),
+        }
+    }
+
+    /// Returns a front-to-back iterator.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (a, b) = self.as_slices();
+        Iter::new(a.iter(), b.iter())
+    }
+
+    /// Returns a front-to-back iterator that returns mutable references.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (a, b) = self.as_mut_slices();
+        IterMut::new(a.iter_mut(), b.iter_mut())
+    }
+
+    /// Converts the deque into a [`VecDeque<T>`].
+    ///
+    /// If the deque has spilled this reuses its heap allocation; otherwise
+    /// the elements are moved into a new allocation.
+    ///
+    /// [`VecDeque<T>`]: crate::collections::VecDeque
+    #[cfg(not(no_global_oom_handling))]
+    pub fn into_vec_deque(mut self) -> VecDeque<T> {
+        if let Repr::Heap(deque) = &mut self.repr {
+            return mem::take(deque);
+        }
+        let mut deque = VecDeque::with_capacity(self.len());
+        while let Some(value) = self.pop_front() {
+            deque.push_back(value);
+        }
+        deque
+    }
+}
+
+unsafe impl<#[may_dangle] T, const N: usize> Drop for SmallVecDeque<T, N> {
+    fn drop(&mut self) {
+        /// Runs the destructor for all items in the slice when it gets
+        /// dropped (normally or during unwinding).
+        struct Dropper<'a, T>(&'a mut [T]);
+
+        impl<'a, T> Drop for Dropper<'a, T> {
+            fn drop(&mut self) {
+                // SAFETY: see below.
+                unsafe {
+                    ptr::drop_in_place(self.0);
+                }
+            }
+        }
+
+        // A heap buffer is dropped by `VecDeque` itself.
+        if let Repr::Inline { .. } = self.repr {
+            let (front, back) = self.as_mut_slices();
+            // SAFETY: the elements of both slices are initialized and owned
+            // by the deque, which is never used again.
+            unsafe {
+                let _back_dropper = Dropper(back);
+                ptr::drop_in_place(front);
+            }
+        }
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[cold]
+#[cfg_attr(not(feature = "panic_immediate_abort"), inline(never))]
+fn capacity_overflow() -> ! {
+    panic!("capacity overflow");
+}
+
+impl<T, const N: usize> Index<usize> for SmallVecDeque<T, N> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("Out of bounds access")
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for SmallVecDeque<T, N> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("Out of bounds access")
+    }
+}
+
+impl<T, const N: usize> Default for SmallVecDeque<T, N> {
+    /// Creates an empty `SmallVecDeque<T, N>`.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T: Clone, const N: usize> Clone for SmallVecDeque<T, N> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for SmallVecDeque<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Hash, const N: usize> Hash for SmallVecDeque<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_length_prefix(self.len());
+        // Hash the elements one by one, like `VecDeque` does, so that the
+        // result does not depend on where the ring buffer wraps around.
+        self.iter().for_each(|elem| elem.hash(state));
+    }
+}
+
+impl<T, U, const N: usize, const M: usize> PartialEq<SmallVecDeque<U, M>> for SmallVecDeque<T, N>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &SmallVecDeque<U, M>) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
+    }
+}
+
+impl<T, U, const N: usize, const M: usize> PartialEq<[U; M]> for SmallVecDeque<T, N>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U; M]) -> bool {
+        self.len() == M && self.iter().zip(other).all(|(a, b)| a == b)
+    }
+}
+
+impl<T, U, const N: usize> PartialEq<VecDeque<U>> for SmallVecDeque<T, N>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &VecDeque<U>) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for SmallVecDeque<T, N> {}
+
+impl<T: PartialOrd, const N: usize> PartialOrd for SmallVecDeque<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord, const N: usize> Ord for SmallVecDeque<T, N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T, const N: usize> Extend<T> for SmallVecDeque<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        let (lower_bound, _) = iter.size_hint();
+        self.reserve(lower_bound);
+        iter.for_each(move |element| self.push_back(element));
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<'a, T: Copy + 'a, const N: usize> Extend<&'a T> for SmallVecDeque<T, N> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T, const N: usize> FromIterator<T> for SmallVecDeque<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = SmallVecDeque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T, const N: usize> IntoIterator for SmallVecDeque<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    /// Consumes the deque into a front-to-back iterator yielding elements by
+    /// value.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self)
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a SmallVecDeque<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut SmallVecDeque<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, const N: usize> From<VecDeque<T>> for SmallVecDeque<T, N> {
+    /// Takes ownership of the heap buffer of `deque`. See
+    /// [`SmallVecDeque::from_vec_deque`].
+    fn from(deque: VecDeque<T>) -> Self {
+        Self::from_vec_deque(deque)
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for SmallVecDeque<T, N> {
+    fn from(buf: [T; N]) -> Self {
+        Self::from_buf(buf)
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T, const N: usize> From<SmallVecDeque<T, N>> for VecDeque<T> {
+    fn from(deque: SmallVecDeque<T, N>) -> Self {
+        deque.into_vec_deque()
+    }
+}
@@ -16,7 +16,7 @@ pub struct Iter<'a, T: 'a> {
 }
 
 impl<'a, T> Iter<'a, T> {
-    pub(super) fn new(i1: slice::Iter<'a, T>, i2: slice::Iter<'a, T>) -> Self {
+    pub(crate) fn new(i1: slice::Iter<'a, T>, i2: slice::Iter<'a, T>) -> Self {
         Self { i1, i2 }
     }
 }
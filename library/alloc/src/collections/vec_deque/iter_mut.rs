@@ -16,7 +16,7 @@ pub struct IterMut<'a, T: 'a> {
 }
 
 impl<'a, T> IterMut<'a, T> {
-    pub(super) fn new(i1: slice::IterMut<'a, T>, i2: slice::IterMut<'a, T>) -> Self {
+    pub(crate) fn new(i1: slice::IterMut<'a, T>, i2: slice::IterMut<'a, T>) -> Self {
         Self { i1, i2 }
     }
 }
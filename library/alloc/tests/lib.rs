@@ -32,6 +32,7 @@
 #![feature(iter_next_chunk)]
 #![feature(round_char_boundary)]
 #![feature(slice_partition_dedup)]
+#![feature(small_vec)]
 #![feature(string_remove_matches)]
 #![feature(const_btree_len)]
 #![feature(const_trait_impl)]
@@ -63,6 +64,8 @@
 mod linked_list;
 mod rc;
 mod slice;
+mod small_vec;
+mod small_vec_deque;
 mod str;
 mod string;
 mod task;
@@ -0,0 +1,168 @@
+use std::collections::SmallVec;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::rc::Rc;
+
+#[test]
+fn test_push_spills_past_inline_capacity() {
+    let mut v: SmallVec<u32, 3> = SmallVec::new();
+    assert_eq!(v.capacity(), 3);
+    for i in 0..3 {
+        v.push(i);
+    }
+    assert!(!v.spilled());
+    v.push(3);
+    assert!(v.spilled());
+    assert!(v.capacity() >= 4);
+    assert_eq!(v, [0, 1, 2, 3]);
+    assert_eq!(v.pop(), Some(3));
+    assert_eq!(v.len(), 3);
+}
+
+#[test]
+fn test_shrink_to_fit_moves_inline() {
+    let mut v: SmallVec<String, 2> = SmallVec::from_vec(vec!["a".to_string()]);
+    assert!(v.spilled());
+    v.shrink_to_fit();
+    assert!(!v.spilled());
+    assert_eq!(v, ["a"]);
+    v.extend(["b".to_string(), "c".to_string()]);
+    assert!(v.spilled());
+    v.truncate(2);
+    v.shrink_to_fit();
+    assert!(!v.spilled());
+    assert_eq!(v, ["a", "b"]);
+}
+
+#[test]
+fn test_insert_remove() {
+    let mut v: SmallVec<i32, 4> = SmallVec::from_buf([1, 2, 4, 5]);
+    v.insert(2, 3);
+    assert!(v.spilled());
+    assert_eq!(v, [1, 2, 3, 4, 5]);
+    assert_eq!(v.remove(0), 1);
+    assert_eq!(v.swap_remove(0), 2);
+    assert_eq!(v, [5, 3, 4]);
+}
+
+#[test]
+fn test_drain_inline_and_spilled() {
+    let mut v: SmallVec<i32, 8> = (0..6).collect();
+    assert!(!v.spilled());
+    let drained: Vec<_> = v.drain(1..4).collect();
+    assert_eq!(drained, [1, 2, 3]);
+    assert_eq!(v, [0, 4, 5]);
+
+    let mut v: SmallVec<i32, 2> = (0..6).collect();
+    assert!(v.spilled());
+    let drained: Vec<_> = v.drain(..2).rev().collect();
+    assert_eq!(drained, [1, 0]);
+    assert_eq!(v, [2, 3, 4, 5]);
+}
+
+#[test]
+fn test_drain_drops_unyielded() {
+    let rc = Rc::new(());
+    let mut v: SmallVec<Rc<()>, 4> = (0..4).map(|_| rc.clone()).collect();
+    let mut drain = v.drain(1..3);
+    drop(drain.next());
+    drop(drain);
+    assert_eq!(v.len(), 2);
+    assert_eq!(Rc::strong_count(&rc), 3);
+    drop(v);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn test_splice_spills() {
+    let mut v: SmallVec<i32, 4> = SmallVec::from_buf([1, 2, 3, 4]);
+    let removed: Vec<_> = v.splice(1..3, [10, 11, 12, 13]).collect();
+    assert_eq!(removed, [2, 3]);
+    assert!(v.spilled());
+    assert_eq!(v, [1, 10, 11, 12, 13, 4]);
+
+    let mut v: SmallVec<i32, 4> = SmallVec::from_buf([1, 2, 3, 4]);
+    v.splice(1..3, (20..22).filter(|_| true));
+    assert!(!v.spilled());
+    assert_eq!(v, [1, 20, 21, 4]);
+}
+
+#[test]
+fn test_extract_if() {
+    let mut v: SmallVec<i32, 4> = (1..=10).collect();
+    let evens: Vec<_> = v.extract_if(|x| *x % 2 == 0).collect();
+    assert_eq!(evens, [2, 4, 6, 8, 10]);
+    assert_eq!(v, [1, 3, 5, 7, 9]);
+
+    v.retain(|x| *x > 3);
+    assert_eq!(v, [5, 7, 9]);
+}
+
+#[test]
+fn test_extract_if_unconsumed_and_panic() {
+    let mut v: SmallVec<i32, 8> = (1..=6).collect();
+    {
+        let mut iter = v.extract_if(|x| *x % 2 == 0);
+        assert_eq!(iter.next(), Some(2));
+    }
+    assert_eq!(v, [1, 3, 4, 5, 6]);
+
+    let mut v: SmallVec<Rc<i32>, 2> = (0..5).map(Rc::new).collect();
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        v.extract_if(|x| if **x == 3 { panic!() } else { **x == 1 }).for_each(drop);
+    }));
+    assert!(res.is_err());
+    assert_eq!(v.iter().map(|x| **x).collect::<Vec<_>>(), [0, 2, 3, 4]);
+}
+
+#[test]
+fn test_dedup_and_split_off() {
+    let mut v: SmallVec<i32, 4> = SmallVec::from_iter([1, 1, 2, 3, 3, 3, 4]);
+    v.dedup();
+    assert_eq!(v, [1, 2, 3, 4]);
+    let tail = v.split_off(1);
+    assert!(!tail.spilled());
+    assert_eq!(v, [1]);
+    assert_eq!(tail, [2, 3, 4]);
+}
+
+#[test]
+fn test_into_vec_and_into_iter() {
+    let v: SmallVec<String, 2> = SmallVec::from_iter(["a".to_string(), "b".to_string()]);
+    assert_eq!(v.clone().into_vec(), ["a", "b"]);
+    let mut iter = v.into_iter();
+    assert_eq!(iter.next_back().as_deref(), Some("b"));
+    assert_eq!(iter.as_slice(), ["a"]);
+
+    let spilled: SmallVec<i32, 1> = SmallVec::from_iter([1, 2, 3]);
+    assert_eq!(spilled.into_iter().collect::<Vec<_>>(), [1, 2, 3]);
+}
+
+#[test]
+fn test_into_inner() {
+    let v: SmallVec<i32, 3> = SmallVec::from_iter([1, 2, 3]);
+    assert_eq!(v.into_inner(), Ok([1, 2, 3]));
+    let v: SmallVec<i32, 3> = SmallVec::from_iter([1, 2]);
+    assert_eq!(v.into_inner().unwrap_err(), [1, 2]);
+}
+
+#[test]
+fn test_zero_sized() {
+    let mut v: SmallVec<(), 2> = SmallVec::new();
+    for _ in 0..5 {
+        v.push(());
+    }
+    assert_eq!(v.len(), 5);
+    assert_eq!(v.drain(1..3).count(), 2);
+    assert_eq!(v.len(), 3);
+}
+
+#[test]
+fn test_try_reserve() {
+    let mut v: SmallVec<u8, 4> = SmallVec::new();
+    assert!(v.try_reserve(4).is_ok());
+    assert!(!v.spilled());
+    assert!(v.try_reserve(usize::MAX).is_err());
+    assert!(!v.spilled());
+    assert!(v.try_reserve_exact(5).is_ok());
+    assert!(v.spilled());
+}
@@ -0,0 +1,128 @@
+use std::collections::{SmallVecDeque, VecDeque};
+use std::rc::Rc;
+
+#[test]
+fn test_push_both_ends_spills_past_inline_capacity() {
+    let mut d: SmallVecDeque<u32, 3> = SmallVecDeque::new();
+    assert_eq!(d.capacity(), 3);
+    d.push_back(1);
+    d.push_front(0);
+    d.push_back(2);
+    assert!(!d.spilled());
+    assert_eq!(d, [0, 1, 2]);
+    d.push_front(u32::MAX);
+    assert!(d.spilled());
+    assert!(d.capacity() >= 4);
+    assert_eq!(d, [u32::MAX, 0, 1, 2]);
+    assert_eq!(d.pop_front(), Some(u32::MAX));
+    assert_eq!(d.pop_back(), Some(2));
+    assert_eq!(d.len(), 2);
+}
+
+#[test]
+fn test_wrapping_ring_buffer() {
+    let mut d: SmallVecDeque<u32, 4> = SmallVecDeque::new();
+    for i in 0..10 {
+        d.push_back(i);
+        if d.len() == 4 {
+            assert_eq!(d.pop_front(), Some(i - 3));
+        }
+    }
+    assert!(!d.spilled());
+    assert_eq!(d, [7, 8, 9]);
+    assert_eq!(d.front(), Some(&7));
+    assert_eq!(d.back(), Some(&9));
+    assert_eq!(d[1], 8);
+    assert_eq!(d.get(3), None);
+    *d.back_mut().unwrap() += 1;
+    assert_eq!(d.iter().rev().copied().collect::<Vec<_>>(), [10, 8, 7]);
+
+    let (a, b) = d.as_slices();
+    assert_eq!(a.len() + b.len(), 3);
+    assert_eq!(d.make_contiguous(), &[7, 8, 10]);
+    assert_eq!(d.as_slices(), (&[7, 8, 10][..], &[][..]));
+}
+
+#[test]
+fn test_insert_remove_swap() {
+    let mut d: SmallVecDeque<u32, 4> = SmallVecDeque::new();
+    d.push_back(2);
+    d.push_front(0);
+    d.insert(1, 1);
+    assert_eq!(d, [0, 1, 2]);
+    d.swap(0, 2);
+    assert_eq!(d, [2, 1, 0]);
+    assert_eq!(d.remove(1), Some(1));
+    assert_eq!(d.remove(2), None);
+    d.insert(2, 5);
+    d.insert(0, 6);
+    assert!(!d.spilled());
+    d.insert(4, 7);
+    assert!(d.spilled());
+    assert_eq!(d, [6, 2, 0, 5, 7]);
+    assert!(d.contains(&5));
+    assert!(!d.contains(&1));
+}
+
+#[test]
+fn test_shrink_to_fit_moves_inline() {
+    let mut d: SmallVecDeque<String, 2> = SmallVecDeque::new();
+    d.extend(["a", "b", "c"].map(String::from));
+    assert!(d.spilled());
+    d.pop_front();
+    d.shrink_to_fit();
+    assert!(!d.spilled());
+    assert_eq!(d, ["b", "c"]);
+
+    let mut d: SmallVecDeque<u8, 2> = SmallVecDeque::from_vec_deque(VecDeque::from([1]));
+    assert!(d.spilled());
+    d.clear();
+    assert!(d.spilled());
+    d.shrink_to_fit();
+    assert!(!d.spilled());
+    assert!(d.is_empty());
+}
+
+#[test]
+fn test_drops_wrapped_elements() {
+    let rc = Rc::new(());
+    let mut d: SmallVecDeque<Rc<()>, 3> = SmallVecDeque::new();
+    d.push_back(rc.clone());
+    d.push_back(rc.clone());
+    d.pop_front();
+    d.push_back(rc.clone());
+    d.push_back(rc.clone());
+    assert_eq!(Rc::strong_count(&rc), 4);
+    d.truncate(2);
+    assert_eq!(Rc::strong_count(&rc), 3);
+    drop(d);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn test_into_vec_deque_and_into_iter() {
+    let mut d: SmallVecDeque<u32, 4> = SmallVecDeque::new();
+    d.push_back(1);
+    d.push_front(0);
+    assert_eq!(d.clone().into_vec_deque(), VecDeque::from([0, 1]));
+    assert_eq!(VecDeque::from(d.clone()), [0, 1]);
+    assert_eq!(d.into_iter().rev().collect::<Vec<_>>(), [1, 0]);
+
+    let d: SmallVecDeque<u32, 1> = (0..4).collect();
+    assert!(d.spilled());
+    let mut iter = d.into_iter();
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.next_back(), Some(3));
+    assert_eq!(iter.collect::<Vec<_>>(), [1, 2]);
+}
+
+#[test]
+fn test_zero_sized() {
+    let mut d: SmallVecDeque<u8, 0> = SmallVecDeque::new();
+    assert_eq!(d.pop_front(), None);
+    assert_eq!(d.as_slices(), (&[][..], &[][..]));
+    d.push_front(1);
+    assert!(d.spilled());
+    assert_eq!(d, [1]);
+}
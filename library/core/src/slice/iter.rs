@@ -3423,3 +3423,66 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ChunkByMut").field("slice", &self.slice).finish()
     }
 }
+
+/// An iterator over the elements at the indices given to [`partition_at_indices`], each with the
+/// elements between it and the previous one.
+///
+/// This struct is created by the [`partition_at_indices`] method on [slices], and its variants,
+/// once the slice has been reordered. It yields the pair `(before, element)` for every distinct
+/// index, in increasing order: `before` holds the elements less than or equal to `element` which
+/// come after the previous index. [`into_remainder`] returns the elements after the last index.
+///
+/// [`partition_at_indices`]: slice::partition_at_indices
+/// [`into_remainder`]: PartitionsAtIndices::into_remainder
+/// [slices]: slice
+#[derive(Debug)]
+#[unstable(feature = "slice_partition_at_indices", issue = "none")]
+pub struct PartitionsAtIndices<'a, T: 'a> {
+    v: &'a mut [T],
+    indices: &'a [usize],
+    // The position of `v` in the original slice.
+    offset: usize,
+}
+
+#[unstable(feature = "slice_partition_at_indices", issue = "none")]
+impl<'a, T: 'a> PartitionsAtIndices<'a, T> {
+    #[inline]
+    pub(super) fn new(v: &'a mut [T], indices: &'a [usize]) -> Self {
+        Self { v, indices, offset: 0 }
+    }
+
+    /// Returns the elements of the original slice after the last index returned by the
+    /// iterator. Once the iterator is exhausted, these are the elements greater than or equal to
+    /// the element at the last index.
+    #[must_use = "`self` will be dropped if the result is not used"]
+    #[unstable(feature = "slice_partition_at_indices", issue = "none")]
+    pub fn into_remainder(self) -> &'a mut [T] {
+        self.v
+    }
+}
+
+#[unstable(feature = "slice_partition_at_indices", issue = "none")]
+impl<'a, T> Iterator for PartitionsAtIndices<'a, T> {
+    type Item = (&'a mut [T], &'a mut T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&index, rest) = self.indices.split_first()?;
+        // Duplicates of `index` are returned once.
+        self.indices = &rest[rest.partition_point(|&i| i == index)..];
+        let v = mem::take(&mut self.v);
+        let (before, after) = v.split_at_mut(index - self.offset);
+        let (element, after) = after.split_first_mut()?;
+        self.v = after;
+        self.offset = index + 1;
+        Some((before, element))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (cmp::min(self.indices.len(), 1), Some(self.indices.len()))
+    }
+}
+
+#[unstable(feature = "slice_partition_at_indices", issue = "none")]
+impl<T> FusedIterator for PartitionsAtIndices<'_, T> {}
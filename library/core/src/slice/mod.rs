@@ -10,7 +10,7 @@
 use crate::fmt;
 use crate::hint;
 use crate::intrinsics::exact_div;
-use crate::mem::{self, SizedTypeProperties};
+use crate::mem::{self, MaybeUninit, SizedTypeProperties};
 use crate::num::NonZero;
 use crate::ops::{Bound, OneSidedRange, Range, RangeBounds};
 use crate::ptr;
@@ -68,6 +68,9 @@
 #[unstable(feature = "array_windows", issue = "75027")]
 pub use iter::ArrayWindows;
 
+#[unstable(feature = "slice_partition_at_indices", issue = "none")]
+pub use iter::PartitionsAtIndices;
+
 #[stable(feature = "slice_group_by", since = "1.77.0")]
 pub use iter::{ChunkBy, ChunkByMut};
 
@@ -3008,6 +3011,152 @@ pub fn sort_unstable_by_key<K, F>(&mut self, mut f: F)
         sort::quicksort(self, |a, b| f(a).lt(&f(b)));
     }
 
+    /// Sorts the slice, using `scratch` as temporary storage instead of allocating.
+    ///
+    /// This sort is stable (i.e., does not reorder equal elements) and *O*(*n* \* log(*n*))
+    /// worst-case, like [`sort`], but never allocates, which makes it usable without the `alloc`
+    /// crate.
+    ///
+    /// # Current implementation
+    ///
+    /// The current algorithm is the same adaptive, iterative merge sort inspired by
+    /// [timsort](https://en.wikipedia.org/wiki/Timsort) that is used by [`sort`]. It needs
+    /// `self.len() / 2` elements of scratch space; the contents of `scratch` are left unspecified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scratch.len() < self.len() / 2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_sort_with_buffer)]
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut v = [-5, 4, 1, -3, 2];
+    /// let mut scratch = [MaybeUninit::uninit(); 2];
+    ///
+    /// v.sort_with_buffer(&mut scratch);
+    /// assert!(v == [-5, -3, 1, 2, 4]);
+    /// ```
+    ///
+    /// [`sort`]: ../../std/primitive.slice.html#method.sort
+    #[unstable(feature = "slice_sort_with_buffer", issue = "none")]
+    #[inline]
+    pub fn sort_with_buffer(&mut self, scratch: &mut [MaybeUninit<T>])
+    where
+        T: Ord,
+    {
+        sort::merge_sort_with_buffer(self, &mut T::lt, scratch);
+    }
+
+    /// Sorts the slice with a comparator function, using `scratch` as temporary storage instead
+    /// of allocating.
+    ///
+    /// This sort is stable (i.e., does not reorder equal elements) and *O*(*n* \* log(*n*))
+    /// worst-case. See [`sort_with_buffer`](slice::sort_with_buffer) for the size requirements of
+    /// `scratch`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scratch.len() < self.len() / 2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_sort_with_buffer)]
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut v = [5, 4, 1, 3, 2];
+    /// let mut scratch = [MaybeUninit::uninit(); 2];
+    ///
+    /// v.sort_by_with_buffer(|a, b| b.cmp(a), &mut scratch);
+    /// assert!(v == [5, 4, 3, 2, 1]);
+    /// ```
+    #[unstable(feature = "slice_sort_with_buffer", issue = "none")]
+    #[inline]
+    pub fn sort_by_with_buffer<F>(&mut self, mut compare: F, scratch: &mut [MaybeUninit<T>])
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        sort::merge_sort_with_buffer(self, &mut |a, b| compare(a, b) == Less, scratch);
+    }
+
+    /// Sorts the slice with a key extraction function, using `scratch` as temporary storage
+    /// instead of allocating.
+    ///
+    /// This sort is stable (i.e., does not reorder equal elements) and *O*(*m* \* *n* \* log(*n*))
+    /// worst-case, where the key function is *O*(*m*). See
+    /// [`sort_with_buffer`](slice::sort_with_buffer) for the size requirements of `scratch`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scratch.len() < self.len() / 2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_sort_with_buffer)]
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut v = [-5i32, 4, 1, -3, 2];
+    /// let mut scratch = [MaybeUninit::uninit(); 2];
+    ///
+    /// v.sort_by_key_with_buffer(|k| k.abs(), &mut scratch);
+    /// assert!(v == [1, 2, -3, 4, -5]);
+    /// ```
+    #[unstable(feature = "slice_sort_with_buffer", issue = "none")]
+    #[inline]
+    pub fn sort_by_key_with_buffer<K, F>(&mut self, mut f: F, scratch: &mut [MaybeUninit<T>])
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        sort::merge_sort_with_buffer(self, &mut |a, b| f(a).lt(&f(b)), scratch);
+    }
+
+    /// Sorts the slice with a key extraction function, caching the keys in `keys` instead of an
+    /// allocated buffer.
+    ///
+    /// The key function is called exactly once per element, which makes this the `no_std`
+    /// counterpart of [`sort_by_cached_key`]. The sort is stable (i.e., does not reorder equal
+    /// elements) and *O*(*m* \* *n* + *n* \* log(*n*)) worst-case, where the key function is
+    /// *O*(*m*).
+    ///
+    /// `keys` must hold at least `self.len()` entries. The keys are dropped before this function
+    /// returns, and the contents of `keys` are left unspecified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keys.len() < self.len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_sort_with_buffer)]
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut v = [-5i32, 4, 32, -3, 2];
+    /// let mut keys = [const { MaybeUninit::uninit() }; 5];
+    ///
+    /// v.sort_by_cached_key_with_buffer(|k| k.to_string(), &mut keys);
+    /// assert!(v == [-3, -5, 2, 32, 4]);
+    /// ```
+    ///
+    /// [`sort_by_cached_key`]: ../../std/primitive.slice.html#method.sort_by_cached_key
+    #[unstable(feature = "slice_sort_with_buffer", issue = "none")]
+    #[inline]
+    pub fn sort_by_cached_key_with_buffer<K, F>(
+        &mut self,
+        f: F,
+        keys: &mut [MaybeUninit<(K, usize)>],
+    ) where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        sort::sort_by_cached_key_with_buffer(self, f, keys);
+    }
+
     /// Reorder the slice such that the element at `index` after the reordering is at its final sorted position.
     ///
     /// This reordering has the additional property that any value at position `i < index` will be
@@ -3183,6 +3332,112 @@ pub fn select_nth_unstable_by_key<K, F>(
         select::partition_at_index(self, index, |a: &T, b: &T| f(a).lt(&f(b)))
     }
 
+    /// Reorder the slice such that the element at each of `indices` after the reordering is at
+    /// its final sorted position.
+    ///
+    /// This is the multi-index generalization of [`select_nth_unstable`]: for any two consecutive
+    /// entries `a < b` of `indices`, every value in `self[a + 1..b]` is greater than or equal to
+    /// `self[a]` and less than or equal to `self[b]`. This makes it possible to compute several
+    /// quantiles at once without sorting the whole slice. The reordering is unstable and in-place
+    /// (i.e. does not allocate), and runs in *O*(*n* \* log(*k*)) time for `k` indices.
+    ///
+    /// Returns an iterator over the elements at `indices`, in increasing order and once for
+    /// repeated indices, each with the sub-slice between it and the previous index. The sub-slice
+    /// after the last index is returned by [`into_remainder`].
+    ///
+    /// [`select_nth_unstable`]: slice::select_nth_unstable
+    /// [`into_remainder`]: PartitionsAtIndices::into_remainder
+    ///
+    /// # Panics
+    ///
+    /// Panics when `indices` is not sorted in non-decreasing order, or when any of them is
+    /// `>= len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_partition_at_indices)]
+    ///
+    /// let mut v = [9, 2, 7, 4, 5, 6, 3, 8, 1, 0];
+    ///
+    /// // Find the quartiles.
+    /// let mut partitions = v.partition_at_indices(&[2, 5, 7]);
+    ///
+    /// let (lesser, q1) = partitions.next().unwrap();
+    /// assert_eq!(*q1, 2);
+    /// assert!(lesser.iter().all(|&x| x < 2));
+    /// let (between, q2) = partitions.next().unwrap();
+    /// assert_eq!(*q2, 5);
+    /// assert!(between.iter().all(|&x| 2 < x && x < 5));
+    /// let (_, q3) = partitions.next().unwrap();
+    /// assert_eq!(*q3, 7);
+    /// assert!(partitions.next().is_none());
+    /// assert!(partitions.into_remainder().iter().all(|&x| x > 7));
+    ///
+    /// assert_eq!((v[2], v[5], v[7]), (2, 5, 7));
+    /// ```
+    #[unstable(feature = "slice_partition_at_indices", issue = "none")]
+    #[inline]
+    pub fn partition_at_indices<'a>(
+        &'a mut self,
+        indices: &'a [usize],
+    ) -> PartitionsAtIndices<'a, T>
+    where
+        T: Ord,
+    {
+        select::partition_at_indices(self, indices, T::lt);
+        PartitionsAtIndices::new(self, indices)
+    }
+
+    /// Reorder the slice with a comparator function such that the element at each of `indices`
+    /// after the reordering is at its final sorted position.
+    ///
+    /// See [`partition_at_indices`](slice::partition_at_indices) for the guarantees made about the
+    /// resulting order and the returned iterator.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `indices` is not sorted in non-decreasing order, or when any of them is
+    /// `>= len()`.
+    #[unstable(feature = "slice_partition_at_indices", issue = "none")]
+    #[inline]
+    pub fn partition_at_indices_by<'a, F>(
+        &'a mut self,
+        indices: &'a [usize],
+        mut compare: F,
+    ) -> PartitionsAtIndices<'a, T>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        select::partition_at_indices(self, indices, |a: &T, b: &T| compare(a, b) == Less);
+        PartitionsAtIndices::new(self, indices)
+    }
+
+    /// Reorder the slice with a key extraction function such that the element at each of
+    /// `indices` after the reordering is at its final sorted position.
+    ///
+    /// See [`partition_at_indices`](slice::partition_at_indices) for the guarantees made about the
+    /// resulting order and the returned iterator.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `indices` is not sorted in non-decreasing order, or when any of them is
+    /// `>= len()`.
+    #[unstable(feature = "slice_partition_at_indices", issue = "none")]
+    #[inline]
+    pub fn partition_at_indices_by_key<'a, K, F>(
+        &'a mut self,
+        indices: &'a [usize],
+        mut f: F,
+    ) -> PartitionsAtIndices<'a, T>
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        select::partition_at_indices(self, indices, |a: &T, b: &T| f(a).lt(&f(b)));
+        PartitionsAtIndices::new(self, indices)
+    }
+
     /// Moves all consecutive repeated elements to the end of the slice according to the
     /// [`PartialEq`] trait implementation.
     ///
@@ -157,6 +157,52 @@ pub fn partition_at_index<T, F>(
     (left, pivot, right)
 }
 
+/// Reorder the slice such that the element at each of `indices` is at its final sorted position.
+///
+/// `indices` must be sorted in non-decreasing order. Selecting the median of the remaining
+/// indices first and recursing into both partitions takes *O*(*n* \* log(*k*)) time for `k`
+/// indices.
+pub fn partition_at_indices<T, F>(v: &mut [T], indices: &[usize], mut is_less: F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    if !indices.is_sorted() {
+        panic!("partition_at_indices indices must be sorted in non-decreasing order");
+    }
+    if let Some(&last) = indices.last() {
+        if last >= v.len() {
+            panic!("partition_at_indices index {} greater than length of slice {}", last, v.len());
+        }
+    }
+
+    partition_at_indices_recurse(v, indices, 0, &mut is_less);
+}
+
+/// Partitions `v` around every index in `indices`, which are relative to the start of the whole
+/// slice; `offset` is the position of `v` within it.
+fn partition_at_indices_recurse<T, F>(
+    v: &mut [T],
+    indices: &[usize],
+    offset: usize,
+    is_less: &mut F,
+) where
+    F: FnMut(&T, &T) -> bool,
+{
+    if indices.is_empty() {
+        return;
+    }
+
+    let mid = indices.len() / 2;
+    let index = indices[mid];
+    let (left, _, right) = partition_at_index(v, index - offset, &mut *is_less);
+
+    // Duplicates of `index` are already taken care of.
+    let before = indices[..mid].partition_point(|&i| i < index);
+    let after = mid + 1 + indices[mid + 1..].partition_point(|&i| i <= index);
+    partition_at_indices_recurse(left, &indices[..before], offset, is_less);
+    partition_at_indices_recurse(right, &indices[after..], index + 1, is_less);
+}
+
 /// Selection algorithm to select the k-th element from the slice in guaranteed O(n) time.
 /// This is essentially a quickselect that uses Tukey's Ninther for pivot selection
 fn median_of_medians<T, F: FnMut(&T, &T) -> bool>(mut v: &mut [T], is_less: &mut F, mut k: usize) {
@@ -1308,6 +1308,116 @@ fn drop(&mut self) {
     }
 }
 
+/// Sorts `v` with [`merge_sort`], using `scratch` as the element buffer and a fixed-size array on
+/// the stack for the run stack, so that no memory is allocated.
+///
+/// # Panics
+///
+/// Panics if `scratch` is shorter than `v.len() / 2`.
+pub(super) fn merge_sort_with_buffer<T, F>(
+    v: &mut [T],
+    is_less: &mut F,
+    scratch: &mut [MaybeUninit<T>],
+) where
+    F: FnMut(&T, &T) -> bool,
+{
+    // The invariants upheld by `merge_sort` make the run lengths grow at least as fast as the
+    // Fibonacci numbers, so fewer than 100 runs are ever in flight. `RunVec` starts with 16 slots
+    // and doubles whenever it is full without freeing the old storage first, so these
+    // 16 + 32 + 64 + 128 slots are enough for any slice length.
+    const RUN_STORAGE_LEN: usize = 240;
+
+    if T::IS_ZST {
+        // Sorting has no meaningful behavior on zero-sized types. Do nothing.
+        return;
+    }
+
+    let len = v.len();
+    if scratch.len() < len / 2 {
+        panic!("scratch buffer of length {} is too small to sort {} elements", scratch.len(), len);
+    }
+
+    let scratch_len = scratch.len();
+    let scratch_ptr = MaybeUninit::slice_as_mut_ptr(scratch);
+    let elem_alloc_fn = |len: usize| -> *mut T {
+        assert!(len <= scratch_len);
+        scratch_ptr
+    };
+    let elem_dealloc_fn = |_buf_ptr: *mut T, _len: usize| {};
+
+    let mut runs = [const { MaybeUninit::<TimSortRun>::uninit() }; RUN_STORAGE_LEN];
+    let runs_ptr = MaybeUninit::slice_as_mut_ptr(&mut runs);
+    let runs_used = crate::cell::Cell::new(0);
+    let run_alloc_fn = |len: usize| -> *mut TimSortRun {
+        let start = runs_used.get();
+        assert!(len <= RUN_STORAGE_LEN - start);
+        runs_used.set(start + len);
+        // SAFETY: `start + len` is within `runs`.
+        unsafe { runs_ptr.add(start) }
+    };
+    let run_dealloc_fn = |_buf_ptr: *mut TimSortRun, _len: usize| {};
+
+    merge_sort(v, is_less, elem_alloc_fn, elem_dealloc_fn, run_alloc_fn, run_dealloc_fn);
+}
+
+/// Sorts `v` by the keys returned by `f`, calling `f` only once per element and storing the keys
+/// in `keys` instead of a freshly allocated buffer.
+///
+/// # Panics
+///
+/// Panics if `keys` is shorter than `v`.
+pub(super) fn sort_by_cached_key_with_buffer<T, K, F>(
+    v: &mut [T],
+    mut f: F,
+    keys: &mut [MaybeUninit<(K, usize)>],
+) where
+    F: FnMut(&T) -> K,
+    K: Ord,
+{
+    // When dropped, drops the first `init` entries of `keys`.
+    struct InitGuard<'a, K> {
+        keys: &'a mut [MaybeUninit<(K, usize)>],
+        init: usize,
+    }
+
+    impl<K> Drop for InitGuard<'_, K> {
+        fn drop(&mut self) {
+            // SAFETY: the first `init` entries have been initialized.
+            unsafe {
+                ptr::drop_in_place(MaybeUninit::slice_assume_init_mut(&mut self.keys[..self.init]))
+            }
+        }
+    }
+
+    let len = v.len();
+    if keys.len() < len {
+        panic!("key buffer of length {} is too small to sort {} elements", keys.len(), len);
+    }
+    if len < 2 {
+        return;
+    }
+
+    let mut guard = InitGuard { keys: &mut keys[..len], init: 0 };
+    for (i, elem) in v.iter().enumerate() {
+        guard.keys[i].write((f(elem), i));
+        guard.init += 1;
+    }
+    // SAFETY: all `len` entries have been initialized above.
+    let indices = unsafe { MaybeUninit::slice_assume_init_mut(&mut guard.keys[..]) };
+
+    // The elements of `indices` are unique, as they are indexed, so any sort will be stable with
+    // respect to the original slice.
+    quicksort(indices, <(K, usize)>::lt);
+    for i in 0..len {
+        let mut index = indices[i].1;
+        while index < i {
+            index = indices[index].1;
+        }
+        indices[i].1 = index;
+        v.swap(i, index);
+    }
+}
+
 /// Internal type used by merge_sort.
 #[derive(Clone, Copy, Debug)]
 pub struct TimSortRun {
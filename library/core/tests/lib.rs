@@ -75,6 +75,8 @@
 #![feature(try_trait_v2)]
 #![feature(slice_internals)]
 #![feature(slice_partition_dedup)]
+#![feature(slice_partition_at_indices)]
+#![feature(slice_sort_with_buffer)]
 #![feature(ip)]
 #![feature(iter_advance_by)]
 #![feature(iter_array_chunks)]
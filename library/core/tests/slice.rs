@@ -1991,6 +1991,156 @@ fn select_nth_unstable_past_length() {
     [0i32; 10].select_nth_unstable(20);
 }
 
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+fn sort_with_buffer() {
+    use rand::Rng;
+
+    // Miri is too slow (but still need to `chain` to make the types match)
+    let lens = if cfg!(miri) { (2..20).chain(0..0) } else { (2..25).chain(500..510) };
+    let rounds = if cfg!(miri) { 1 } else { 20 };
+
+    let mut rng = crate::test_rng();
+    let mut scratch = [MaybeUninit::uninit(); 300];
+    let mut keys = [MaybeUninit::uninit(); 600];
+
+    for len in lens {
+        for &modulus in &[5, 10, 1000] {
+            for _ in 0..rounds {
+                // Pair each value with its original position to check stability.
+                let orig: Vec<(i32, usize)> =
+                    (0..len).map(|i| (rng.gen::<i32>() % modulus, i)).collect();
+
+                let mut v = orig.clone();
+                v.sort_by_key_with_buffer(|&(a, _)| a, &mut scratch[..len / 2]);
+                assert!(v.windows(2).all(|w| w[0] <= w[1]));
+
+                let mut v = orig.clone();
+                v.sort_by_with_buffer(|a, b| b.0.cmp(&a.0), &mut scratch[..len / 2]);
+                assert!(
+                    v.windows(2).all(|w| w[0].0 > w[1].0 || (w[0].0 == w[1].0 && w[0].1 < w[1].1))
+                );
+
+                let mut v = orig.clone();
+                v.sort_by_cached_key_with_buffer(|&(a, _)| a, &mut keys[..len]);
+                assert!(v.windows(2).all(|w| w[0] <= w[1]));
+            }
+        }
+    }
+
+    // Should not panic.
+    [0i32; 0].sort_with_buffer(&mut []);
+    [(); 100].sort_with_buffer(&mut []);
+}
+
+#[test]
+#[should_panic(expected = "scratch buffer of length 4 is too small to sort 10 elements")]
+fn sort_with_buffer_too_small() {
+    [0i32; 10].sort_with_buffer(&mut [MaybeUninit::uninit(); 4]);
+}
+
+#[test]
+fn sort_by_cached_key_with_buffer_drops_keys() {
+    let count = Cell::new(0);
+    struct Key<'a>(i32, &'a Cell<usize>);
+    impl PartialEq for Key<'_> {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+    impl Eq for Key<'_> {}
+    impl PartialOrd for Key<'_> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Key<'_> {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+    impl Drop for Key<'_> {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+        }
+    }
+
+    let mut v = [3, 1, 2];
+    let mut keys = [const { MaybeUninit::uninit() }; 3];
+    v.sort_by_cached_key_with_buffer(|&x| Key(x, &count), &mut keys);
+    assert_eq!(v, [1, 2, 3]);
+    assert_eq!(count.get(), 3);
+}
+
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg_attr(miri, ignore)] // Miri is too slow
+fn partition_at_indices() {
+    use rand::Rng;
+
+    let mut rng = crate::test_rng();
+
+    for len in (1..21).chain(500..501) {
+        for &modulus in &[5, 10, 1000] {
+            for _ in 0..10 {
+                let orig: Vec<i32> = (0..len).map(|_| rng.gen::<i32>() % modulus).collect();
+                let v_sorted = {
+                    let mut v = orig.clone();
+                    v.sort();
+                    v
+                };
+
+                for k in 1..5 {
+                    let mut indices: Vec<usize> = (0..k).map(|_| rng.gen_range(0..len)).collect();
+                    indices.sort();
+
+                    let mut distinct = indices.clone();
+                    distinct.dedup();
+
+                    let mut v = orig.clone();
+                    let mut partitions = v.partition_at_indices(&indices);
+                    let mut start = 0;
+                    for &index in &distinct {
+                        let (before, element) = partitions.next().unwrap();
+                        assert_eq!(before.len(), index - start);
+                        assert_eq!(*element, v_sorted[index]);
+                        start = index + 1;
+                    }
+                    assert!(partitions.next().is_none());
+                    assert_eq!(partitions.into_remainder().len(), len - start);
+                    for &index in &indices {
+                        assert_eq!(v_sorted[index], v[index]);
+                        assert!(v[..index].iter().all(|x| *x <= v[index]));
+                        assert!(v[index..].iter().all(|x| *x >= v[index]));
+                    }
+
+                    let mut v = orig.clone();
+                    v.partition_at_indices_by(&indices, |a, b| b.cmp(a));
+                    for &index in &indices {
+                        assert_eq!(v_sorted[len - 1 - index], v[index]);
+                    }
+                }
+            }
+        }
+    }
+
+    // Should not panic.
+    assert_eq!([(); 10].partition_at_indices(&[0, 5, 5, 9]).count(), 3);
+    assert_eq!([0i32; 0].partition_at_indices(&[]).count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "indices must be sorted")]
+fn partition_at_indices_unsorted() {
+    [0i32; 10].partition_at_indices(&[5, 2]);
+}
+
+#[test]
+#[should_panic(expected = "index 10 greater than length of slice")]
+fn partition_at_indices_past_length() {
+    [0i32; 10].partition_at_indices(&[2, 10]);
+}
+
 pub mod memchr {
     use core::slice::memchr::{memchr, memrchr};
 
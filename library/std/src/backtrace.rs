@@ -89,10 +89,12 @@
 // a backtrace or actually symbolizing it.
 
 use crate::backtrace_rs::{self, BytesOrWideString};
+use crate::borrow::Cow;
 use crate::env;
 use crate::ffi::c_void;
 use crate::fmt;
 use crate::panic::UnwindSafe;
+use crate::path::Path;
 use crate::sync::atomic::{AtomicU8, Ordering::Relaxed};
 use crate::sync::LazyLock;
 use crate::sys_common::backtrace::{lock, output_filename, set_image_base};
@@ -159,7 +161,12 @@ enum RawFrame {
     Fake,
 }
 
-struct BacktraceSymbol {
+/// A symbol that a [`BacktraceFrame`] resolved to.
+///
+/// A single frame may resolve to several symbols when functions have been
+/// inlined into each other; they are listed innermost first.
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+pub struct BacktraceSymbol {
     name: Option<Vec<u8>>,
     filename: Option<BytesOrWide>,
     lineno: Option<u32>,
@@ -207,6 +214,7 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[unstable(feature = "backtrace_frames", issue = "79676")]
 impl fmt::Debug for BacktraceSymbol {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         // FIXME: improve formatting: https://github.com/rust-lang/rust/issues/65280
@@ -371,11 +379,261 @@ pub fn status(&self) -> BacktraceStatus {
 
 impl<'a> Backtrace {
     /// Returns an iterator over the backtrace frames.
+    ///
+    /// This resolves the symbols of every frame if that hasn't happened yet,
+    /// see [`Backtrace::resolve`].
     #[must_use]
     #[unstable(feature = "backtrace_frames", issue = "79676")]
     pub fn frames(&'a self) -> &'a [BacktraceFrame] {
         if let Inner::Captured(c) = &self.inner { &c.frames } else { &[] }
     }
+
+    /// Resolves the symbols of all captured frames.
+    ///
+    /// Capturing a backtrace only records the instruction pointer of every
+    /// frame; turning those into symbol names, file names and line numbers is
+    /// deferred until the backtrace is first formatted or its frames are
+    /// inspected, because it is usually far more expensive than the capture.
+    /// This method performs that work eagerly, e.g. before handing the
+    /// backtrace to a context where blocking on debug information would be
+    /// undesirable. Resolution only ever happens once, so calling this
+    /// repeatedly is cheap.
+    ///
+    /// Does nothing if the backtrace is disabled or unsupported.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn resolve(&self) {
+        if let Inner::Captured(c) = &self.inner {
+            LazyLock::force(c);
+        }
+    }
+
+    /// Returns an object that implements [`Display`] for serializing the
+    /// backtrace as JSON, resolving its symbols if that hasn't happened yet.
+    ///
+    /// The output is an object with the `status` of the backtrace, one of
+    /// `"unsupported"`, `"disabled"` and `"captured"`, and its `frames`, which
+    /// are the same as those returned by [`frames`](Backtrace::frames). Each
+    /// frame has its `ip` and `symbol_address`, its `module_base_address` if
+    /// known, and its `symbols`, each with its `name`, `raw_name`, `filename`,
+    /// `lineno` and `colno` where known. The addresses are hexadecimal strings,
+    /// and the names and file names that aren't valid Unicode are converted
+    /// lossily.
+    ///
+    /// [`Display`]: fmt::Display
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(backtrace_frames)]
+    /// use std::backtrace::Backtrace;
+    ///
+    /// let backtrace = Backtrace::force_capture();
+    /// let report = backtrace.json().to_string();
+    /// assert!(report.starts_with(r#"{"status":"#));
+    /// ```
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn json(&self) -> BacktraceJson<'_> {
+        BacktraceJson { backtrace: self }
+    }
+}
+
+/// Helper struct for serializing a [`Backtrace`] as JSON with [`format!`] and
+/// `{}`.
+///
+/// This struct is created by the [`json`](Backtrace::json) method on
+/// [`Backtrace`]. See its documentation for more.
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+pub struct BacktraceJson<'a> {
+    backtrace: &'a Backtrace,
+}
+
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+impl fmt::Debug for BacktraceJson<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_string(), fmt)
+    }
+}
+
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+impl fmt::Display for BacktraceJson<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (status, frames) = match &self.backtrace.inner {
+            Inner::Unsupported => ("unsupported", &[][..]),
+            Inner::Disabled => ("disabled", &[][..]),
+            Inner::Captured(_) => ("captured", self.backtrace.frames()),
+        };
+
+        write!(fmt, r#"{{"status":"{status}","frames":["#)?;
+        for (i, frame) in frames.iter().enumerate() {
+            if i > 0 {
+                fmt.write_str(",")?;
+            }
+            let (ip, symbol_address) = (frame.ip(), frame.symbol_address());
+            write!(fmt, r#"{{"ip":"{ip:p}","symbol_address":"{symbol_address:p}""#)?;
+            if let Some(base) = frame.module_base_address() {
+                write!(fmt, r#","module_base_address":"{base:p}""#)?;
+            }
+            fmt.write_str(r#","symbols":["#)?;
+            for (i, symbol) in frame.symbols.iter().enumerate() {
+                if i > 0 {
+                    fmt.write_str(",")?;
+                }
+                fmt.write_str("{")?;
+                let mut first = true;
+                let mut field = |fmt: &mut fmt::Formatter<'_>, name: &str| {
+                    let separator = if first { "" } else { "," };
+                    first = false;
+                    write!(fmt, r#"{separator}"{name}":"#)
+                };
+                if let Some(name) = symbol.name() {
+                    field(fmt, "name")?;
+                    write_json_str(fmt, &name)?;
+                }
+                if let Some(raw_name) = symbol.raw_name() {
+                    field(fmt, "raw_name")?;
+                    write_json_str(fmt, &String::from_utf8_lossy(raw_name))?;
+                }
+                if let Some(filename) = symbol.filename() {
+                    field(fmt, "filename")?;
+                    write_json_str(fmt, &filename.to_string_lossy())?;
+                }
+                if let Some(lineno) = symbol.lineno {
+                    field(fmt, "lineno")?;
+                    write!(fmt, "{lineno}")?;
+                }
+                if let Some(colno) = symbol.colno {
+                    field(fmt, "colno")?;
+                    write!(fmt, "{colno}")?;
+                }
+                fmt.write_str("}")?;
+            }
+            fmt.write_str("]}")?;
+        }
+        fmt.write_str("]}")
+    }
+}
+
+/// Writes `s` as a JSON string literal.
+fn write_json_str(fmt: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    fmt.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => fmt.write_str("\\\"")?,
+            '\\' => fmt.write_str("\\\\")?,
+            '\n' => fmt.write_str("\\n")?,
+            '\r' => fmt.write_str("\\r")?,
+            '\t' => fmt.write_str("\\t")?,
+            c if c.is_control() => write!(fmt, "\\u{:04x}", c as u32)?,
+            c => fmt.write_str(c.encode_utf8(&mut [0; 4]))?,
+        }
+    }
+    fmt.write_str("\"")
+}
+
+impl BacktraceFrame {
+    /// Returns the current instruction pointer of this frame.
+    ///
+    /// This is normally the next instruction to execute in the frame, not the
+    /// call instruction itself.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn ip(&self) -> *mut c_void {
+        self.frame.ip()
+    }
+
+    /// Returns the starting symbol address of the function this frame belongs
+    /// to, as reported by the unwinder.
+    ///
+    /// On some platforms this is only an approximation, and it may be the same
+    /// as [`ip`](BacktraceFrame::ip).
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn symbol_address(&self) -> *mut c_void {
+        self.frame.symbol_address()
+    }
+
+    /// Returns the base address of the module (executable or shared library)
+    /// this frame belongs to, if known.
+    ///
+    /// Combined with [`ip`](BacktraceFrame::ip), this allows symbolicating the
+    /// frame offline against the module's debug information.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn module_base_address(&self) -> Option<*mut c_void> {
+        self.frame.module_base_address()
+    }
+
+    /// Returns the symbols this frame resolved to.
+    ///
+    /// This is empty if no symbol information is available for the frame.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn symbols(&self) -> &[BacktraceSymbol] {
+        &self.symbols
+    }
+}
+
+impl BacktraceSymbol {
+    /// Returns the demangled name of this symbol, without the trailing hash
+    /// that rustc appends to symbol names.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn name(&self) -> Option<String> {
+        self.name.as_ref().map(|b| format!("{:#}", backtrace_rs::SymbolName::new(b)))
+    }
+
+    /// Returns the name of this symbol exactly as it appears in the symbol
+    /// table, i.e. still mangled.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn raw_name(&self) -> Option<&[u8]> {
+        self.name.as_deref()
+    }
+
+    /// Returns the path of the source file this symbol was defined in.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn filename(&self) -> Option<Cow<'_, Path>> {
+        self.filename.as_ref().map(BytesOrWide::to_path)
+    }
+
+    /// Returns the line number this symbol is currently executing.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn lineno(&self) -> Option<u32> {
+        self.lineno
+    }
+
+    /// Returns the column number this symbol is currently executing.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn colno(&self) -> Option<u32> {
+        self.colno
+    }
+}
+
+impl BytesOrWide {
+    fn to_path(&self) -> Cow<'_, Path> {
+        match self {
+            #[cfg(unix)]
+            BytesOrWide::Bytes(bytes) => {
+                use crate::os::unix::prelude::*;
+                Path::new(crate::ffi::OsStr::from_bytes(bytes)).into()
+            }
+            #[cfg(not(unix))]
+            BytesOrWide::Bytes(bytes) => match String::from_utf8_lossy(bytes) {
+                Cow::Borrowed(s) => Path::new(s).into(),
+                Cow::Owned(s) => Cow::Owned(s.into()),
+            },
+            #[cfg(windows)]
+            BytesOrWide::Wide(wide) => {
+                use crate::os::windows::prelude::*;
+                Cow::Owned(crate::ffi::OsString::from_wide(wide).into())
+            }
+            #[cfg(not(windows))]
+            BytesOrWide::Wide(wide) => Cow::Owned(String::from_utf16_lossy(wide).into()),
+        }
+    }
 }
 
 #[stable(feature = "backtrace", since = "1.65.0")]
@@ -470,4 +728,20 @@ fn ip(&self) -> *mut c_void {
             RawFrame::Fake => crate::ptr::without_provenance_mut(1),
         }
     }
+
+    fn symbol_address(&self) -> *mut c_void {
+        match self {
+            RawFrame::Actual(frame) => frame.symbol_address(),
+            #[cfg(test)]
+            RawFrame::Fake => crate::ptr::without_provenance_mut(1),
+        }
+    }
+
+    fn module_base_address(&self) -> Option<*mut c_void> {
+        match self {
+            RawFrame::Actual(frame) => frame.module_base_address(),
+            #[cfg(test)]
+            RawFrame::Fake => None,
+        }
+    }
 }
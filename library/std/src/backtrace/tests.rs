@@ -93,6 +93,68 @@ fn test_frames() {
     assert!(iter.all(|(f, e)| format!("{f:#?}") == *e));
 }
 
+#[test]
+fn test_frame_accessors() {
+    let backtrace = Backtrace {
+        inner: Inner::Captured(LazyLock::preinit(Capture {
+            actual_start: 1,
+            frames: generate_fake_frames(),
+        })),
+    };
+    backtrace.resolve();
+
+    let frames = backtrace.frames();
+    assert_eq!(frames.len(), 3);
+    assert!(!frames[0].ip().is_null());
+    assert_eq!(frames[0].module_base_address(), None);
+
+    let symbols = frames[2].symbols();
+    assert_eq!(symbols.len(), 2);
+    assert_eq!(symbols[0].name().as_deref(), Some("std::rt::lang_start_internal"));
+    assert_eq!(symbols[0].raw_name(), Some(&b"std::rt::lang_start_internal"[..]));
+    assert_eq!(symbols[0].filename().as_deref(), Some(Path::new("rust/rt.rs")));
+    assert_eq!(symbols[0].lineno(), Some(300));
+    assert_eq!(symbols[0].colno(), Some(5));
+    assert_eq!(symbols[1].colno(), None);
+
+    let symbols = frames[1].symbols();
+    assert_eq!(symbols[0].filename(), None);
+    assert_eq!(symbols[0].lineno(), None);
+}
+
+#[test]
+fn test_json() {
+    let backtrace = Backtrace {
+        inner: Inner::Captured(LazyLock::preinit(Capture {
+            actual_start: 1,
+            frames: generate_fake_frames(),
+        })),
+    };
+
+    let expected = concat!(
+        r#"{"status":"captured","frames":["#,
+        r#"{"ip":"0x1","symbol_address":"0x1","symbols":["#,
+        r#"{"name":"std::backtrace::Backtrace::create","#,
+        r#""raw_name":"std::backtrace::Backtrace::create","#,
+        r#""filename":"rust/backtrace.rs","lineno":100}"#,
+        r#"]},"#,
+        r#"{"ip":"0x1","symbol_address":"0x1","symbols":["#,
+        r#"{"name":"__rust_maybe_catch_panic","raw_name":"__rust_maybe_catch_panic"}"#,
+        r#"]},"#,
+        r#"{"ip":"0x1","symbol_address":"0x1","symbols":["#,
+        r#"{"name":"std::rt::lang_start_internal","raw_name":"std::rt::lang_start_internal","#,
+        r#""filename":"rust/rt.rs","lineno":300,"colno":5},"#,
+        r#"{"name":"std::rt::lang_start","raw_name":"std::rt::lang_start","#,
+        r#""filename":"rust/rt.rs","lineno":400}"#,
+        r#"]}"#,
+        r#"]}"#,
+    );
+    assert_eq!(backtrace.json().to_string(), expected);
+
+    let backtrace = Backtrace { inner: Inner::Disabled };
+    assert_eq!(backtrace.json().to_string(), r#"{"status":"disabled","frames":[]}"#);
+}
+
 #[test]
 fn backtrace_unwind_safe() {
     fn assert_unwind_safe<T: UnwindSafe + RefUnwindSafe>() {}
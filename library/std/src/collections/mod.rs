@@ -415,6 +415,10 @@
 pub use alloc_crate::collections::{BTreeMap, BTreeSet, BinaryHeap};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use alloc_crate::collections::{LinkedList, VecDeque};
+#[unstable(feature = "small_vec", issue = "none")]
+pub use alloc_crate::collections::{small_vec, small_vec_deque};
+#[unstable(feature = "small_vec", issue = "none")]
+pub use alloc_crate::collections::{SmallVec, SmallVecDeque};
 
 #[stable(feature = "rust1", since = "1.0.0")]
 #[doc(inline)]
@@ -1,5 +1,6 @@
 mod buffer;
 
+use crate::cmp;
 use crate::fmt;
 use crate::io::{
     self, uninlined_slow_read_byte, BorrowedCursor, BufRead, IoSliceMut, Read, Seek, SeekFrom,
@@ -93,6 +94,37 @@ pub fn new(inner: R) -> BufReader<R> {
     pub fn with_capacity(capacity: usize, inner: R) -> BufReader<R> {
         BufReader { inner, buf: Buffer::with_capacity(capacity) }
     }
+
+    /// Creates a new `BufReader<R>` with the specified initial buffer capacity, whose buffer
+    /// may grow up to `max_capacity` bytes when more data is requested through
+    /// [`fill_buf_at_least`] or [`peek`].
+    ///
+    /// The buffer never grows on its own: ordinary reads behave exactly as with a
+    /// `BufReader` created by [`with_capacity`]. If `max_capacity` is smaller than
+    /// `capacity`, it is treated as being equal to `capacity`.
+    ///
+    /// [`fill_buf_at_least`]: BufReader::fill_buf_at_least
+    /// [`peek`]: BufRead::peek
+    /// [`with_capacity`]: BufReader::with_capacity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(bufreader_max_buffer)]
+    /// use std::io::BufReader;
+    ///
+    /// let data: &[u8] = &[0; 100];
+    /// let mut reader = BufReader::with_max_buffer(16, 64, data);
+    /// assert_eq!(reader.capacity(), 16);
+    ///
+    /// assert_eq!(reader.fill_buf_at_least(40).unwrap().len(), 40);
+    /// assert!(reader.capacity() >= 40);
+    /// assert!(reader.fill_buf_at_least(65).is_err());
+    /// ```
+    #[unstable(feature = "bufreader_max_buffer", issue = "none")]
+    pub fn with_max_buffer(capacity: usize, max_capacity: usize, inner: R) -> BufReader<R> {
+        BufReader { inner, buf: Buffer::with_max_capacity(capacity, max_capacity) }
+    }
 }
 
 impl<R: ?Sized> BufReader<R> {
@@ -193,6 +225,17 @@ pub fn capacity(&self) -> usize {
         self.buf.capacity()
     }
 
+    /// Returns the largest number of bytes the internal buffer may grow to.
+    ///
+    /// This is equal to [`capacity`] unless the reader was created with
+    /// [`BufReader::with_max_buffer`].
+    ///
+    /// [`capacity`]: BufReader::capacity
+    #[unstable(feature = "bufreader_max_buffer", issue = "none")]
+    pub fn max_capacity(&self) -> usize {
+        self.buf.max_capacity()
+    }
+
     /// Unwraps this `BufReader<R>`, returning the underlying reader.
     ///
     /// Note that any leftover data in the internal buffer is lost. Therefore,
@@ -227,6 +270,46 @@ pub(in crate::io) fn discard_buffer(&mut self) {
     }
 }
 
+impl<R: ?Sized + Read> BufReader<R> {
+    /// Fills the internal buffer until it holds at least `n` unconsumed bytes, and returns
+    /// its contents.
+    ///
+    /// Unlike [`fill_buf`], this keeps reading from the underlying reader while fewer than
+    /// `n` bytes are buffered, moving buffered data to the front of the buffer and growing it
+    /// (up to [`max_capacity`]) as needed. Fewer than `n` bytes are returned only once the
+    /// underlying reader reaches EOF.
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    /// [`max_capacity`]: BufReader::max_capacity
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::InvalidInput`] if `n` exceeds
+    /// [`max_capacity`]. Instances of [`ErrorKind::Interrupted`] are ignored; any other
+    /// error from the underlying reader is returned, and the bytes read so far stay buffered.
+    ///
+    /// [`ErrorKind::InvalidInput`]: io::ErrorKind::InvalidInput
+    /// [`ErrorKind::Interrupted`]: io::ErrorKind::Interrupted
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(bufreader_max_buffer)]
+    /// use std::io::{BufRead, BufReader};
+    ///
+    /// let data: &[u8] = b"HEADER:payload";
+    /// let mut reader = BufReader::with_capacity(8, data);
+    ///
+    /// assert_eq!(reader.fill_buf_at_least(7).unwrap(), b"HEADER:p");
+    /// reader.consume(7);
+    /// assert_eq!(reader.fill_buf_at_least(7).unwrap(), b"payload");
+    /// ```
+    #[unstable(feature = "bufreader_max_buffer", issue = "none")]
+    pub fn fill_buf_at_least(&mut self, n: usize) -> io::Result<&[u8]> {
+        self.buf.fill_buf_at_least(n, &mut self.inner)
+    }
+}
+
 // This is only used by a test which asserts that the initialization-tracking is correct.
 #[cfg(test)]
 impl<R: ?Sized> BufReader<R> {
@@ -399,6 +482,16 @@ fn fill_buf(&mut self) -> io::Result<&[u8]> {
     fn consume(&mut self, amt: usize) {
         self.buf.consume(amt)
     }
+
+    /// Keeps reading until `n` bytes are buffered, growing the buffer up to its
+    /// [`max_capacity`] if needed, like [`fill_buf_at_least`].
+    ///
+    /// [`max_capacity`]: BufReader::max_capacity
+    /// [`fill_buf_at_least`]: BufReader::fill_buf_at_least
+    fn peek(&mut self, n: usize) -> io::Result<&[u8]> {
+        let buf = self.buf.fill_buf_at_least(n, &mut self.inner)?;
+        Ok(&buf[..cmp::min(n, buf.len())])
+    }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -9,7 +9,7 @@
 /// that user code which wants to do reads from a `BufReader` via `buffer` + `consume` can do so
 /// without encountering any runtime bounds checks.
 use crate::cmp;
-use crate::io::{self, BorrowedBuf, Read};
+use crate::io::{self, BorrowedBuf, ErrorKind, Read};
 use crate::mem::MaybeUninit;
 
 pub struct Buffer {
@@ -26,13 +26,21 @@ pub struct Buffer {
     // doesn't need to be. Calls to `fill_buf` are not required to actually fill the buffer, and
     // omitting this is a huge perf regression for `Read` impls that do not.
     initialized: usize,
+    // The largest size `buf` may be grown to by `fill_buf_at_least`. Always >= `buf.len()`.
+    max_capacity: usize,
 }
 
 impl Buffer {
     #[inline]
     pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_max_capacity(capacity, capacity)
+    }
+
+    #[inline]
+    pub fn with_max_capacity(capacity: usize, max_capacity: usize) -> Self {
         let buf = Box::new_uninit_slice(capacity);
-        Self { buf, pos: 0, filled: 0, initialized: 0 }
+        let max_capacity = cmp::max(capacity, max_capacity);
+        Self { buf, pos: 0, filled: 0, initialized: 0, max_capacity }
     }
 
     #[inline]
@@ -47,6 +55,11 @@ pub fn capacity(&self) -> usize {
         self.buf.len()
     }
 
+    #[inline]
+    pub fn max_capacity(&self) -> usize {
+        self.max_capacity
+    }
+
     #[inline]
     pub fn filled(&self) -> usize {
         self.filled
@@ -119,4 +132,61 @@ pub fn fill_buf(&mut self, mut reader: impl Read) -> io::Result<&[u8]> {
         }
         Ok(self.buffer())
     }
+
+    /// Read from `reader` until at least `amt` bytes are buffered or `reader` reaches EOF,
+    /// growing the buffer up to `max_capacity` if necessary.
+    pub fn fill_buf_at_least(&mut self, amt: usize, mut reader: impl Read) -> io::Result<&[u8]> {
+        if amt > self.max_capacity {
+            return Err(io::const_io_error!(
+                ErrorKind::InvalidInput,
+                "requested more bytes than the maximum buffer capacity",
+            ));
+        }
+
+        while self.filled - self.pos < amt {
+            if self.pos + amt > self.buf.len() {
+                self.make_room(amt);
+            }
+
+            let filled = self.filled;
+            let mut buf = BorrowedBuf::from(&mut self.buf[filled..]);
+            // SAFETY: `self.initialized` bytes will always have been initialized, and
+            // `self.initialized >= self.filled`.
+            unsafe {
+                buf.set_init(self.initialized - filled);
+            }
+
+            match reader.read_buf(buf.unfilled()) {
+                Ok(()) => {}
+                Err(e) if e.is_interrupted() => continue,
+                Err(e) => return Err(e),
+            }
+
+            let (read, init) = (buf.len(), buf.init_len());
+            self.initialized = filled + init;
+            if read == 0 {
+                break;
+            }
+            self.filled += read;
+        }
+        Ok(self.buffer())
+    }
+
+    /// Move the unconsumed bytes to the start of the buffer, growing it first if it cannot hold
+    /// `amt` bytes.
+    fn make_room(&mut self, amt: usize) {
+        let len = self.filled - self.pos;
+        if amt > self.buf.len() {
+            let capacity =
+                cmp::min(cmp::max(amt, self.buf.len().saturating_mul(2)), self.max_capacity);
+            let mut buf = Box::new_uninit_slice(capacity);
+            buf[..len].copy_from_slice(&self.buf[self.pos..self.filled]);
+            self.buf = buf;
+            self.initialized = len;
+        } else {
+            self.buf.copy_within(self.pos..self.filled, 0);
+        }
+        self.pos = 0;
+        self.filled = len;
+    }
 }
@@ -405,6 +405,70 @@ fn test_read_until() {
     assert_eq!(v, []);
 }
 
+#[test]
+fn test_read_until_limited() {
+    let inner: &[u8] = b"ab\ncdefgh\nxy";
+    let mut reader = BufReader::with_capacity(2, inner);
+    let mut v = Vec::new();
+    assert_eq!(reader.read_until_limited(b'\n', 3, &mut v).unwrap(), 3);
+    assert_eq!(v, b"ab\n");
+    v.truncate(0);
+    let err = reader.read_until_limited(b'\n', 3, &mut v).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(v, b"cde");
+    v.truncate(0);
+    assert_eq!(reader.read_until_limited(b'\n', 4, &mut v).unwrap(), 4);
+    assert_eq!(v, b"fgh\n");
+    v.truncate(0);
+    // Reaching EOF exactly at the limit is not an error.
+    assert_eq!(reader.read_until_limited(b'\n', 2, &mut v).unwrap(), 2);
+    assert_eq!(v, b"xy");
+    v.truncate(0);
+    assert_eq!(reader.read_until_limited(b'\n', 0, &mut v).unwrap(), 0);
+    assert_eq!(v, []);
+}
+
+#[test]
+fn test_buffered_reader_peek() {
+    let inner: &[u8] = &[0, 1, 2, 3, 4, 5, 6];
+    let mut reader = BufReader::with_capacity(4, inner);
+    assert_eq!(reader.peek(2).unwrap(), [0, 1]);
+    reader.consume(3);
+    // Only one byte is buffered, so peeking has to read more.
+    assert_eq!(reader.buffer(), [3]);
+    assert_eq!(reader.peek(3).unwrap(), [3, 4, 5]);
+    assert_eq!(reader.capacity(), 4);
+    // Requests larger than the buffer can hold fail.
+    assert_eq!(reader.peek(5).unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert_eq!(reader.peek(4).unwrap(), [3, 4, 5, 6]);
+    reader.consume(2);
+    // Fewer bytes are returned at EOF.
+    assert_eq!(reader.peek(4).unwrap(), [5, 6]);
+}
+
+#[test]
+fn test_buffered_reader_fill_buf_at_least() {
+    let inner = ShortReader { lengths: vec![1, 1, 1, 2, 0] };
+    let mut reader = BufReader::with_max_buffer(2, 6, inner);
+    assert_eq!(reader.max_capacity(), 6);
+    assert_eq!(reader.fill_buf_at_least(2).unwrap().len(), 2);
+    assert_eq!(reader.capacity(), 2);
+    reader.consume(1);
+    assert_eq!(reader.fill_buf_at_least(4).unwrap().len(), 4);
+    assert_eq!(reader.capacity(), 4);
+    let err = reader.fill_buf_at_least(7).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    // The underlying reader hit EOF, so fewer bytes than requested are returned.
+    reader.consume(1);
+    assert_eq!(reader.fill_buf_at_least(6).unwrap().len(), 3);
+    assert_eq!(reader.capacity(), 6);
+
+    let inner: &[u8] = &[0; 8];
+    let mut reader = BufReader::with_capacity(4, inner);
+    assert_eq!(reader.max_capacity(), 4);
+    assert!(reader.fill_buf_at_least(5).is_err());
+}
+
 #[test]
 fn test_line_buffer() {
     let mut writer = LineWriter::new(Vec::new());
@@ -377,6 +377,10 @@ fn fill_buf(&mut self) -> io::Result<&[u8]> {
     fn consume(&mut self, amt: usize) {
         self.pos += amt as u64;
     }
+    fn peek(&mut self, n: usize) -> io::Result<&[u8]> {
+        let remaining = self.remaining_slice();
+        Ok(&remaining[..cmp::min(n, remaining.len())])
+    }
 }
 
 // Non-resizing write implementation
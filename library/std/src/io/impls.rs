@@ -118,6 +118,11 @@ fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> io::Result<usize> {
     fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
         (**self).read_line(buf)
     }
+
+    #[inline]
+    fn peek(&mut self, n: usize) -> io::Result<&[u8]> {
+        (**self).peek(n)
+    }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -226,6 +231,11 @@ fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> io::Result<usize> {
     fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
         (**self).read_line(buf)
     }
+
+    #[inline]
+    fn peek(&mut self, n: usize) -> io::Result<&[u8]> {
+        (**self).peek(n)
+    }
 }
 
 // =============================================================================
@@ -347,6 +357,11 @@ fn fill_buf(&mut self) -> io::Result<&[u8]> {
     fn consume(&mut self, amt: usize) {
         *self = &self[amt..];
     }
+
+    #[inline]
+    fn peek(&mut self, n: usize) -> io::Result<&[u8]> {
+        Ok(&self[..cmp::min(n, self.len())])
+    }
 }
 
 /// Write is implemented for `&mut [u8]` by copying into the slice, overwriting
@@ -500,6 +515,16 @@ fn fill_buf(&mut self) -> io::Result<&[u8]> {
     fn consume(&mut self, amt: usize) {
         self.drain(..amt);
     }
+
+    /// Makes the contents of the `VecDeque` contiguous if the first `n` bytes
+    /// aren't already, so they can be returned as one slice.
+    fn peek(&mut self, n: usize) -> io::Result<&[u8]> {
+        let n = cmp::min(n, self.len());
+        if self.as_slices().0.len() < n {
+            self.make_contiguous();
+        }
+        Ok(&self.as_slices().0[..n])
+    }
 }
 
 /// Write is implemented for `VecDeque<u8>` by appending to the `VecDeque`, growing it as needed.
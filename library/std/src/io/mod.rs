@@ -2114,6 +2114,49 @@ fn read_until<R: BufRead + ?Sized>(r: &mut R, delim: u8, buf: &mut Vec<u8>) -> R
     }
 }
 
+fn read_until_limited<R: BufRead + ?Sized>(
+    r: &mut R,
+    delim: u8,
+    max: usize,
+    buf: &mut Vec<u8>,
+) -> Result<usize> {
+    let mut read = 0;
+    loop {
+        let (done, used) = {
+            let available = match r.fill_buf() {
+                Ok(n) => n,
+                Err(ref e) if e.is_interrupted() => continue,
+                Err(e) => return Err(e),
+            };
+            if available.is_empty() {
+                return Ok(read);
+            }
+            if read == max {
+                return Err(error::const_io_error!(
+                    ErrorKind::InvalidData,
+                    "delimiter not found within the read limit",
+                ));
+            }
+            let available = &available[..cmp::min(available.len(), max - read)];
+            match memchr::memchr(delim, available) {
+                Some(i) => {
+                    buf.extend_from_slice(&available[..=i]);
+                    (true, i + 1)
+                }
+                None => {
+                    buf.extend_from_slice(available);
+                    (false, available.len())
+                }
+            }
+        };
+        r.consume(used);
+        read += used;
+        if done {
+            return Ok(read);
+        }
+    }
+}
+
 fn skip_until<R: BufRead + ?Sized>(r: &mut R, delim: u8) -> Result<usize> {
     let mut read = 0;
     loop {
@@ -2401,6 +2444,111 @@ fn skip_until(&mut self, byte: u8) -> Result<usize> {
         skip_until(self, byte)
     }
 
+    /// Read all bytes into `buf` until the delimiter `byte` or EOF is reached,
+    /// appending at most `max` bytes.
+    ///
+    /// This behaves like [`read_until`], except that it refuses to consume more
+    /// than `max` bytes (including the delimiter) from the stream. This makes it
+    /// suitable for parsing untrusted input, where [`read_until`] could otherwise
+    /// be made to buffer an unbounded amount of data.
+    ///
+    /// If successful, this function will return the total number of bytes read.
+    ///
+    /// # Errors
+    ///
+    /// If `max` bytes have been read without encountering the delimiter, and the
+    /// stream has more data, an error of kind [`ErrorKind::InvalidData`] is returned.
+    /// The `max` bytes read will have been consumed from the stream and appended
+    /// to `buf`.
+    ///
+    /// Otherwise this function will ignore all instances of [`ErrorKind::Interrupted`]
+    /// and will return any errors returned by [`fill_buf`], with all bytes read so far
+    /// present in `buf`.
+    ///
+    /// [`read_until`]: BufRead::read_until
+    /// [`fill_buf`]: BufRead::fill_buf
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(bufread_read_until_limited)]
+    /// use std::io::{self, BufRead};
+    ///
+    /// let mut cursor = io::Cursor::new(b"short\nthis line is far too long\n");
+    /// let mut buf = vec![];
+    ///
+    /// let num_bytes = cursor.read_until_limited(b'\n', 10, &mut buf)
+    ///     .expect("line fits within the limit");
+    /// assert_eq!(num_bytes, 6);
+    /// assert_eq!(buf, b"short\n");
+    /// buf.clear();
+    ///
+    /// let err = cursor.read_until_limited(b'\n', 10, &mut buf).unwrap_err();
+    /// assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    /// assert_eq!(buf, b"this line ");
+    /// ```
+    #[unstable(feature = "bufread_read_until_limited", issue = "none")]
+    fn read_until_limited(&mut self, byte: u8, max: usize, buf: &mut Vec<u8>) -> Result<usize> {
+        read_until_limited(self, byte, max, buf)
+    }
+
+    /// Returns the next `n` bytes without consuming them, filling the internal
+    /// buffer from the underlying reader as needed.
+    ///
+    /// The returned slice is shorter than `n` only if the stream reaches EOF
+    /// first. Nothing is consumed: the same bytes are returned again by the
+    /// next call to [`fill_buf`] or [`read`](Read::read).
+    ///
+    /// The default implementation calls [`fill_buf`] once, as it has no way to
+    /// make the buffer larger. Readers which can hold `n` bytes, such as
+    /// [`BufReader`] and the in-memory readers like `&[u8]`, override it to keep
+    /// reading until `n` bytes are available.
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::InvalidInput`] if the reader can't
+    /// buffer `n` bytes. With the default implementation, this is the case
+    /// whenever [`fill_buf`] returns fewer than `n` bytes but not an empty
+    /// buffer, as it can't tell whether more data would follow.
+    ///
+    /// Any error returned while filling the buffer is returned as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(bufread_peek)]
+    /// use std::io::{BufRead, BufReader, Read};
+    ///
+    /// fn is_get_request<R: BufRead>(reader: &mut R) -> std::io::Result<bool> {
+    ///     Ok(reader.peek(4)? == b"GET ")
+    /// }
+    ///
+    /// let data: &[u8] = b"GET / HTTP/1.1";
+    /// let mut reader = BufReader::with_capacity(16, data);
+    /// assert!(is_get_request(&mut reader).unwrap());
+    ///
+    /// // Peeking did not consume anything.
+    /// let mut method = [0; 4];
+    /// reader.read_exact(&mut method).unwrap();
+    /// assert_eq!(&method, b"GET ");
+    ///
+    /// // The buffer can't hold more than 16 bytes.
+    /// assert!(reader.peek(17).is_err());
+    /// ```
+    #[unstable(feature = "bufread_peek", issue = "none")]
+    fn peek(&mut self, n: usize) -> Result<&[u8]> {
+        let buf = self.fill_buf()?;
+        if buf.len() < n && !buf.is_empty() {
+            return Err(error::const_io_error!(
+                ErrorKind::InvalidInput,
+                "the reader can't buffer the requested number of bytes",
+            ));
+        }
+        Ok(&buf[..cmp::min(n, buf.len())])
+    }
+
     /// Read all bytes until a newline (the `0xA` byte) is reached, and append
     /// them to the provided `String` buffer.
     ///
@@ -2980,6 +3128,16 @@ fn consume(&mut self, amt: usize) {
         self.limit -= amt as u64;
         self.inner.consume(amt);
     }
+
+    fn peek(&mut self, n: usize) -> Result<&[u8]> {
+        // Only the bytes within the limit need to be available, the limit is
+        // EOF as far as the caller is concerned.
+        let n = cmp::min(n as u64, self.limit) as usize;
+        if n == 0 {
+            return Ok(&[]);
+        }
+        self.inner.peek(n)
+    }
 }
 
 impl<T> SizeHint for Take<T> {
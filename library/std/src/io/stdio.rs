@@ -554,6 +554,10 @@ fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> io::Result<usize> {
     fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
         self.inner.read_line(buf)
     }
+
+    fn peek(&mut self, n: usize) -> io::Result<&[u8]> {
+        self.inner.peek(n)
+    }
 }
 
 #[stable(feature = "std_debug", since = "1.16.0")]
@@ -218,6 +218,41 @@ fn borrowed_cursor_advance_overflow() {
     buf.unfilled().advance(usize::MAX);
 }
 
+#[test]
+fn peek() {
+    fn peek_twice<R: BufRead>(mut reader: R, n: usize) -> Vec<u8> {
+        let first = reader.peek(n).unwrap().to_vec();
+        assert_eq!(reader.peek(n).unwrap(), first);
+        first
+    }
+
+    assert_eq!(peek_twice(&b"hello"[..], 3), b"hel");
+    assert_eq!(peek_twice(&b"hi"[..], 3), b"hi");
+    assert_eq!(peek_twice(Cursor::new(b"hello"), 4), b"hell");
+    assert_eq!(peek_twice(io::empty(), 1), b"");
+    assert_eq!(peek_twice((&b"hello"[..]).take(2), 3), b"he");
+    assert_eq!(peek_twice(BufReader::with_capacity(2, &b"hello"[..]), 2), b"he");
+
+    // The bytes at the back of the `VecDeque` are moved to the front slice.
+    let mut deque = crate::collections::VecDeque::with_capacity(4);
+    deque.extend(b"xyab");
+    deque.drain(..2);
+    deque.extend(b"cd");
+    assert_eq!(deque.as_slices().0, b"ab");
+    assert_eq!(peek_twice(&mut deque, 3), b"abc");
+    assert_eq!(deque.len(), 4);
+
+    // The default implementation can't make the buffer of `Chain` grow.
+    let mut chain = (&b"ab"[..]).chain(&b"cd"[..]);
+    assert_eq!(chain.peek(2).unwrap(), b"ab");
+    assert_eq!(chain.peek(3).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    chain.consume(2);
+    assert_eq!(chain.peek(3).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(chain.peek(2).unwrap(), b"cd");
+    chain.consume(2);
+    assert_eq!(chain.peek(3).unwrap(), b"");
+}
+
 #[test]
 fn take_eof() {
     struct R;
@@ -42,6 +42,12 @@
 #[unstable(feature = "panic_update_hook", issue = "92649")]
 pub use crate::panicking::update_hook;
 
+#[unstable(feature = "panic_named_hooks", issue = "none")]
+pub use crate::panicking::{register_hook, unregister_hook};
+
+#[unstable(feature = "panic_scoped_hook", issue = "none")]
+pub use crate::panicking::with_hook;
+
 #[stable(feature = "panic_hooks", since = "1.10.0")]
 pub use core::panic::{Location, PanicInfo};
 
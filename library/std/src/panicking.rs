@@ -13,6 +13,7 @@
 use core::panic::{Location, PanicInfo, PanicPayload};
 
 use crate::any::Any;
+use crate::cell::Cell;
 use crate::fmt;
 use crate::intrinsics;
 use crate::mem::{self, ManuallyDrop};
@@ -233,6 +234,151 @@ pub fn update_hook<F>(hook_fn: F)
     *hook = Hook::Custom(Box::new(move |info| hook_fn(&prev, info)));
 }
 
+type NamedHook = (&'static str, Box<dyn Fn(&PanicInfo<'_>) + 'static + Sync + Send>);
+
+/// Named hooks registered with [`register_hook`], in registration order.
+static NAMED_HOOKS: RwLock<Vec<NamedHook>> = RwLock::new(Vec::new());
+
+/// Registers an additional panic hook under `name`.
+///
+/// Unlike [`set_hook`], this does not replace the primary panic hook: every
+/// named hook is invoked, in registration order, before the primary hook runs.
+/// This allows several independent libraries (e.g. a test harness and a
+/// telemetry library) to observe panics in the same process without
+/// clobbering each other.
+///
+/// If a hook is already registered under `name`, it is replaced, keeping its
+/// position in the order, and the previous hook is returned.
+///
+/// [`set_hook`]: ./fn.set_hook.html
+///
+/// # Panics
+///
+/// Panics if called from a panicking thread.
+///
+/// # Examples
+///
+/// The following will print "telemetry", then the normal output of panic.
+///
+/// ```should_panic
+/// #![feature(panic_named_hooks)]
+/// use std::panic;
+///
+/// panic::register_hook("telemetry", Box::new(|_| {
+///     println!("telemetry");
+/// }));
+///
+/// panic!("Normal panic");
+/// ```
+#[unstable(feature = "panic_named_hooks", issue = "none")]
+pub fn register_hook(
+    name: &'static str,
+    hook: Box<dyn Fn(&PanicInfo<'_>) + 'static + Sync + Send>,
+) -> Option<Box<dyn Fn(&PanicInfo<'_>) + 'static + Sync + Send>> {
+    if thread::panicking() {
+        panic!("cannot modify the panic hook from a panicking thread");
+    }
+
+    let mut hooks = NAMED_HOOKS.write().unwrap_or_else(PoisonError::into_inner);
+    let old = match hooks.iter_mut().find(|(n, _)| *n == name) {
+        Some((_, slot)) => Some(mem::replace(slot, hook)),
+        None => {
+            hooks.push((name, hook));
+            None
+        }
+    };
+    drop(hooks);
+    // Only drop the old hook after releasing the lock to avoid deadlocking
+    // if its destructor panics.
+    old
+}
+
+/// Unregisters the panic hook registered under `name` with [`register_hook`]
+/// and returns it.
+///
+/// Returns `None` if no hook is registered under `name`. The primary panic
+/// hook and all other named hooks are left untouched.
+///
+/// [`register_hook`]: ./fn.register_hook.html
+///
+/// # Panics
+///
+/// Panics if called from a panicking thread.
+#[unstable(feature = "panic_named_hooks", issue = "none")]
+pub fn unregister_hook(name: &str) -> Option<Box<dyn Fn(&PanicInfo<'_>) + 'static + Sync + Send>> {
+    if thread::panicking() {
+        panic!("cannot modify the panic hook from a panicking thread");
+    }
+
+    let mut hooks = NAMED_HOOKS.write().unwrap_or_else(PoisonError::into_inner);
+    let index = hooks.iter().position(|(n, _)| *n == name)?;
+    let (_, old) = hooks.remove(index);
+    drop(hooks);
+    Some(old)
+}
+
+thread_local! {
+    /// The innermost hook installed with [`with_hook`] on this thread, if any.
+    ///
+    /// The pointee is borrowed for the duration of the `with_hook` call that
+    /// installed it, which restores the previous value before returning.
+    static SCOPED_HOOK: Cell<Option<*const (dyn Fn(&PanicInfo<'_>) + 'static)>> =
+        const { Cell::new(None) };
+}
+
+/// Runs `f` with `hook` as the panic hook of the current thread.
+///
+/// While `f` runs, panics on the current thread invoke `hook` instead of the
+/// primary hook registered with [`set_hook`]; panics on other threads are not
+/// affected. Hooks registered with [`register_hook`] still run before it. Once
+/// `f` returns or unwinds, the previously active hook is in effect again, so
+/// calls to `with_hook` can be nested.
+///
+/// Unlike the global hooks, `hook` does not need to be `'static`, `Send` or
+/// `Sync`, so it can borrow from the caller's stack.
+///
+/// [`set_hook`]: ./fn.set_hook.html
+/// [`register_hook`]: ./fn.register_hook.html
+///
+/// # Examples
+///
+/// ```
+/// #![feature(panic_scoped_hook)]
+/// use std::cell::Cell;
+/// use std::panic;
+///
+/// let panics = Cell::new(0);
+/// let result = panic::with_hook(
+///     |_| panics.set(panics.get() + 1),
+///     || panic::catch_unwind(|| panic!("silent")),
+/// );
+///
+/// assert!(result.is_err());
+/// assert_eq!(panics.get(), 1);
+/// ```
+#[unstable(feature = "panic_scoped_hook", issue = "none")]
+pub fn with_hook<H, F, R>(hook: H, f: F) -> R
+where
+    H: Fn(&PanicInfo<'_>),
+    F: FnOnce() -> R,
+{
+    /// Reinstates the previously active scoped hook, even if `f` unwinds.
+    struct Restore(Option<*const (dyn Fn(&PanicInfo<'_>) + 'static)>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED_HOOK.with(|scoped| scoped.set(self.0));
+        }
+    }
+
+    let hook: &dyn Fn(&PanicInfo<'_>) = &hook;
+    // SAFETY: only the lifetime bound of the trait object is erased. `Restore`
+    // removes the pointer from `SCOPED_HOOK` before `hook` goes out of scope.
+    let hook: *const (dyn Fn(&PanicInfo<'_>) + 'static) = unsafe { mem::transmute(hook) };
+    let _restore = Restore(SCOPED_HOOK.with(|scoped| scoped.replace(Some(hook))));
+    f()
+}
+
 /// The default panic handler.
 fn default_hook(info: &PanicInfo<'_>) {
     // If this is a double panic, make sure that we print a backtrace
@@ -774,25 +920,43 @@ fn rust_panic_with_hook(
 
     let mut info =
         PanicInfo::internal_constructor(message, location, can_unwind, force_no_backtrace);
+
+    let named_hooks = NAMED_HOOKS.read().unwrap_or_else(PoisonError::into_inner);
+    // A hook installed with `with_hook` takes precedence over the global one.
+    // `try_with` fails if this thread's locals are being destroyed, in which
+    // case no `with_hook` call can be active anymore.
+    let scoped_hook = SCOPED_HOOK.try_with(Cell::get).ok().flatten();
     let hook = HOOK.read().unwrap_or_else(PoisonError::into_inner);
-    match *hook {
-        // Some platforms (like wasm) know that printing to stderr won't ever actually
-        // print anything, and if that's the case we can skip the default
-        // hook. Since string formatting happens lazily when calling `payload`
-        // methods, this means we avoid formatting the string at all!
-        // (The panic runtime might still call `payload.take_box()` though and trigger
-        // formatting.)
-        Hook::Default if panic_output().is_none() => {}
-        Hook::Default => {
-            info.set_payload(payload.get());
-            default_hook(&info);
-        }
-        Hook::Custom(ref hook) => {
-            info.set_payload(payload.get());
-            hook(&info);
+    let run_global_hook = scoped_hook.is_none()
+        && match *hook {
+            // Some platforms (like wasm) know that printing to stderr won't ever actually
+            // print anything, and if that's the case we can skip the default
+            // hook. Since string formatting happens lazily when calling `payload`
+            // methods, this means we avoid formatting the string at all!
+            // (The panic runtime might still call `payload.take_box()` though and trigger
+            // formatting.)
+            Hook::Default => panic_output().is_some(),
+            Hook::Custom(_) => true,
+        };
+
+    if !named_hooks.is_empty() || scoped_hook.is_some() || run_global_hook {
+        info.set_payload(payload.get());
+    }
+    for (_, named_hook) in named_hooks.iter() {
+        named_hook(&info);
+    }
+    if let Some(scoped_hook) = scoped_hook {
+        // SAFETY: the `with_hook` call that installed the hook is still on the
+        // stack, so the hook is still alive.
+        unsafe { (*scoped_hook)(&info) };
+    } else if run_global_hook {
+        match *hook {
+            Hook::Default => default_hook(&info),
+            Hook::Custom(ref hook) => hook(&info),
         }
-    };
+    }
     drop(hook);
+    drop(named_hooks);
 
     // Indicate that we have finished executing the panic hook. After this point
     // it is fine if there is a panic while executing destructors, as long as it
@@ -11,6 +11,10 @@
 }
 
 fn filter_fluent(path: &Path) -> bool {
+    // Translations define the same messages as the English resources they translate.
+    if path.components().any(|c| c.as_os_str() == "locales") {
+        return true;
+    }
     if let Some(ext) = path.extension() { ext.to_str() != Some("ftl") } else { true }
 }
 
@@ -0,0 +1,40 @@
+// We specify incremental here because we want to test the partitioning for
+//@ incremental compilation
+//@ incremental
+//@ compile-flags:-Zprint-mono-items=eager
+
+#![feature(codegen_unit_hints)]
+#![allow(dead_code)]
+#![crate_type = "lib"]
+
+// The items pinned to a codegen unit are placed in it, whatever the module they are defined in,
+// and the innermost `#[codegen_unit]` attribute wins.
+
+//~ MONO_ITEM fn hot_generic::<u32> @@ codegen_unit_hints-hot.pinned[External]
+//~ MONO_ITEM fn hot_generic::<u64> @@ codegen_unit_hints-hot.pinned[External]
+#[codegen_unit = "hot"]
+pub fn hot_generic<T>(x: T) -> T {
+    x
+}
+
+//~ MONO_ITEM fn user @@ codegen_unit_hints[Internal]
+fn user() {
+    let _ = hot_generic(0u32);
+}
+
+#[codegen_unit = "hot"]
+mod fast {
+    //~ MONO_ITEM fn fast::run @@ codegen_unit_hints-hot.pinned[Internal]
+    fn run() {
+        let _ = super::hot_generic(0u64);
+    }
+
+    //~ MONO_ITEM fn fast::rare @@ codegen_unit_hints-cold.pinned[Internal]
+    #[codegen_unit = "cold"]
+    fn rare() {}
+}
+
+mod slow {
+    //~ MONO_ITEM fn slow::run @@ codegen_unit_hints-slow[Internal]
+    fn run() {}
+}
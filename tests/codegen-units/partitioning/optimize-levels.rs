@@ -0,0 +1,40 @@
+// We specify incremental here because we want to test the partitioning for
+//@ incremental compilation
+//@ incremental
+//@ compile-flags:-Zprint-mono-items=eager -Copt-level=2
+
+#![feature(codegen_unit_hints)]
+#![feature(optimize_attribute)]
+#![allow(dead_code)]
+#![crate_type = "lib"]
+
+// The functions optimized at another level than the crate are placed in a codegen unit of their
+// own for that level, even when they are pinned to another codegen unit.
+
+//~ MONO_ITEM fn aggressive @@ optimize_levels-opt-level-3.optimize[External]
+#[optimize(level = "3")]
+pub fn aggressive() {}
+
+//~ MONO_ITEM fn less @@ optimize_levels-opt-level-1.optimize[External]
+#[optimize(level = "1")]
+pub fn less() {}
+
+//~ MONO_ITEM fn pinned @@ optimize_levels-opt-level-3.optimize[External]
+#[codegen_unit = "hot"]
+#[optimize(level = "3")]
+pub fn pinned() {}
+
+// The functions at the level of the crate, and the ones only needing function attributes, stay
+// with the other functions of their module.
+
+//~ MONO_ITEM fn default @@ optimize_levels[External]
+#[optimize(level = "2")]
+pub fn default() {}
+
+//~ MONO_ITEM fn not_optimized @@ optimize_levels[External]
+#[optimize(level = "0")]
+pub fn not_optimized() {}
+
+//~ MONO_ITEM fn size_min @@ optimize_levels[External]
+#[optimize(level = "z")]
+pub fn size_min() {}
@@ -0,0 +1,48 @@
+// Checks the function attributes of `#[optimize(level = "...")]`, and that the functions that
+// aren't optimized neither inline nor get inlined.
+//
+//@ compile-flags: -Copt-level=3 -Ccodegen-units=1
+
+#![feature(optimize_attribute)]
+#![crate_type = "lib"]
+
+#[inline]
+fn helper(x: i32) -> i32 {
+    x + 1
+}
+
+// CHECK-LABEL: define{{.*}}i32 @not_optimized
+// CHECK-SAME: [[NOT_OPTIMIZED_ATTRS:#[0-9]+]]
+// CHECK: call{{.*}}helper
+#[optimize(level = "0")]
+#[no_mangle]
+pub fn not_optimized(x: i32) -> i32 {
+    helper(x)
+}
+
+// CHECK-LABEL: define{{.*}}i32 @caller
+// CHECK: call{{.*}}@not_optimized
+#[no_mangle]
+pub fn caller(x: i32) -> i32 {
+    not_optimized(x)
+}
+
+// CHECK-LABEL: define{{.*}}i32 @size()
+// CHECK-SAME: [[SIZE_ATTRS:#[0-9]+]]
+#[optimize(level = "s")]
+#[no_mangle]
+pub fn size() -> i32 {
+    3 + 3
+}
+
+// CHECK-LABEL: define{{.*}}i32 @size_min()
+// CHECK-SAME: [[SIZE_MIN_ATTRS:#[0-9]+]]
+#[optimize(level = "z")]
+#[no_mangle]
+pub fn size_min() -> i32 {
+    4 + 4
+}
+
+// CHECK-DAG: attributes [[NOT_OPTIMIZED_ATTRS]] = {{.*}}noinline{{.*}}optnone
+// CHECK-DAG: attributes [[SIZE_ATTRS]] = {{.*}}optsize
+// CHECK-DAG: attributes [[SIZE_MIN_ATTRS]] = {{.*}}minsize{{.*}}optsize
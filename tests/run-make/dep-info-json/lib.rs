@@ -0,0 +1,7 @@
+#![crate_type = "rlib"]
+
+mod other;
+
+pub static DATA: &str = include_str!("data.txt");
+pub static VAR: &str = env!("DEP_INFO_JSON_VAR");
+pub static UNSET: Option<&str> = option_env!("DEP_INFO_JSON_UNSET");
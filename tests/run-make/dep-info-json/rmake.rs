@@ -0,0 +1,31 @@
+// Checks the dep-info file written with `-Zdep-info-format=json`.
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir};
+use std::fs;
+
+fn main() {
+    rustc()
+        .input("lib.rs")
+        .emit("metadata,dep-info")
+        .arg("-Zdep-info-format=json")
+        .arg("-Zsrc-hash-algorithm=sha256")
+        .env("DEP_INFO_JSON_VAR", "value")
+        .env_remove("DEP_INFO_JSON_UNSET")
+        .run();
+    let deps = fs::read_to_string(tmp_dir().join("lib.d")).unwrap();
+
+    for file in ["lib.rs", "other.rs", "data.txt"] {
+        assert!(deps.contains(&format!(r#""path": "{file}""#)), "{deps}");
+    }
+    assert!(deps.contains(r#""kind": "source""#), "{deps}");
+    assert!(deps.contains(r#""hash": "sha256:"#), "{deps}");
+    assert!(deps.contains(r#""name": "DEP_INFO_JSON_VAR""#), "{deps}");
+    assert!(deps.contains(r#""value": "value""#), "{deps}");
+    assert!(deps.contains(r#""name": "DEP_INFO_JSON_UNSET""#), "{deps}");
+    assert!(deps.contains(r#""value": null"#), "{deps}");
+    assert!(deps.contains(r#""name": "std""#), "{deps}");
+    assert!(deps.contains(r#""svh": ""#), "{deps}");
+    assert!(deps.contains("libstd-"), "{deps}");
+}
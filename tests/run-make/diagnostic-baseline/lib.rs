@@ -0,0 +1,13 @@
+#![crate_type = "lib"]
+
+pub struct S;
+
+impl S {
+    pub fn f() {
+        let unused = 1;
+    }
+}
+
+pub fn g() {
+    let unused = 2;
+}
@@ -0,0 +1,43 @@
+// Records the warnings of a crate with `-Zdiagnostic-baseline-record`, and checks that
+// `-Zdiagnostic-baseline` then suppresses them, but not the warnings added since.
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir};
+use std::fs;
+
+fn stderr(output: std::process::Output) -> String {
+    String::from_utf8(output.stderr).unwrap()
+}
+
+fn main() {
+    let baseline = tmp_dir().join("baseline.json");
+    // The source is copied, to be extended later without changing its path.
+    let lib = tmp_dir().join("lib.rs");
+    fs::copy("lib.rs", &lib).unwrap();
+
+    let out = stderr(
+        rustc()
+            .input(&lib)
+            .arg(format!("-Zdiagnostic-baseline={}", baseline.display()))
+            .arg("-Zdiagnostic-baseline-record")
+            .run(),
+    );
+    assert_eq!(out.matches("warning: unused variable: `unused`").count(), 2, "{out}");
+    let recorded = fs::read_to_string(&baseline).unwrap();
+    assert_eq!(recorded.matches(r#""lint": "unused_variables""#).count(), 2, "{recorded}");
+
+    let out = stderr(
+        rustc().input(&lib).arg(format!("-Zdiagnostic-baseline={}", baseline.display())).run(),
+    );
+    assert!(!out.contains("warning"), "{out}");
+
+    // A warning added since the baseline was recorded is still reported.
+    let source = fs::read_to_string(&lib).unwrap();
+    fs::write(&lib, source + "\npub fn h() {\n    let unused = 3;\n}\n").unwrap();
+    let out = stderr(
+        rustc().input(&lib).arg(format!("-Zdiagnostic-baseline={}", baseline.display())).run(),
+    );
+    assert_eq!(out.matches("warning: unused variable: `unused`").count(), 1, "{out}");
+    assert!(out.contains("let unused = 3;"), "{out}");
+}
@@ -0,0 +1,12 @@
+#[inline(never)]
+fn wrap<T>(value: T) -> Option<T> {
+    Some(value)
+}
+
+pub fn bytes() -> Option<u8> {
+    wrap(1)
+}
+
+pub fn words() -> Option<u16> {
+    wrap(2)
+}
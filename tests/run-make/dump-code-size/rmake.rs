@@ -0,0 +1,38 @@
+// Checks the code size report written with `-Zdump-code-size`, which attributes the size of the
+// symbols of the object files to their crates, definitions and codegen units.
+
+extern crate run_make_support;
+
+use run_make_support::regex::Regex;
+use run_make_support::{rustc, tmp_dir};
+use std::fs;
+
+fn main() {
+    rustc()
+        .input("foo.rs")
+        .crate_type("lib")
+        .arg(format!("-Zdump-code-size={}", tmp_dir().display()))
+        .arg("-Zdump-code-size-format=json")
+        .run();
+    let report = fs::read_to_string(tmp_dir().join("foo.code_size.json")).unwrap();
+    let object = |name: &str| -> &str {
+        let pattern = Regex::new(&format!(r#"\{{[^{{}}]*"name":"{name}"[^{{}}]*\}}"#)).unwrap();
+        pattern.find(&report).unwrap_or_else(|| panic!("no `{name}` in {report}")).as_str()
+    };
+
+    // Both instantiations of `wrap` are attributed to it.
+    let wrap = object("wrap");
+    assert!(wrap.contains(r#""crate":"foo""#) && wrap.contains(r#""symbols":2"#), "{wrap}");
+    assert!(object("bytes").contains(r#""symbols":1"#), "{report}");
+    assert!(report.contains(r#""codegen_units":[{"#), "{report}");
+
+    rustc()
+        .input("foo.rs")
+        .crate_type("lib")
+        .arg(format!("-Zdump-code-size={}", tmp_dir().display()))
+        .run();
+    let report = fs::read_to_string(tmp_dir().join("foo.code_size.md")).unwrap();
+    assert!(report.starts_with("# Code size of `foo`"), "{report}");
+    assert!(report.contains("| `wrap` | `foo` |"), "{report}");
+    assert!(report.contains("| Codegen unit | Size | Symbols |"), "{report}");
+}
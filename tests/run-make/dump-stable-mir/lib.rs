@@ -0,0 +1,8 @@
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+pub fn norm(point: &Point) -> i32 {
+    point.x.abs() + point.y.abs()
+}
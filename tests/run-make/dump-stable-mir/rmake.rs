@@ -0,0 +1,31 @@
+// Checks the StableMIR file written with `-Zdump-stable-mir`.
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir};
+use std::fs;
+
+fn main() {
+    let path = tmp_dir().join("lib.smir.json");
+    rustc()
+        .input("lib.rs")
+        .emit("metadata")
+        .arg(format!("-Zdump-stable-mir={}", path.display()))
+        .run();
+    let dump = fs::read_to_string(&path).unwrap();
+
+    assert!(dump.starts_with(r#"{"version":1,"#), "{dump}");
+    assert!(dump.contains(r#""name":"norm""#), "{dump}");
+    assert!(dump.contains(r#""name":"Point""#), "{dump}");
+    assert!(dump.contains(r#""body":{"blocks":"#), "{dump}");
+    assert!(dump.contains(r#""pretty":"&Point""#), "{dump}");
+
+    let path = tmp_dir().join("missing").join("lib.smir.json");
+    let output = rustc()
+        .input("lib.rs")
+        .emit("metadata")
+        .arg(format!("-Zdump-stable-mir={}", path.display()))
+        .run_fail();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("failed to write the StableMIR to"), "{stderr}");
+}
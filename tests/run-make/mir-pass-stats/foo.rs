@@ -0,0 +1,4 @@
+pub fn branch(x: u32) -> u32 {
+    let y = if x > 10 { x } else { 10 };
+    if y >= 10 { y * 2 } else { 0 }
+}
@@ -0,0 +1,41 @@
+// Checks the report written with `-Zmir-pass-stats`, which records the changes made by each MIR
+// pass to each body.
+
+extern crate run_make_support;
+
+use run_make_support::regex::Regex;
+use run_make_support::{rustc, tmp_dir};
+use std::fs;
+
+fn main() {
+    rustc()
+        .input("foo.rs")
+        .crate_type("lib")
+        .arg("-O")
+        .arg(format!("-Zmir-pass-stats={}", tmp_dir().display()))
+        .run();
+    let report = fs::read_to_string(tmp_dir().join("foo.mir_pass_stats.json")).unwrap();
+
+    let pass = Regex::new(r#"\{[^{}]*"name":"GVN"[^{}]*\}"#).unwrap();
+    let gvn = pass.find(&report).unwrap_or_else(|| panic!("no `GVN` in {report}")).as_str();
+    assert!(gvn.contains(r#""runs":"#) && gvn.contains(r#""time_ns":"#), "{gvn}");
+
+    // The control flow of `branch` is simplified, which removes basic blocks and statements.
+    let body = Regex::new(r#""body":"branch","runs":\[[^\]]*\]"#).unwrap();
+    let branch = body.find(&report).unwrap_or_else(|| panic!("no `branch` in {report}")).as_str();
+    assert!(branch.contains(r#""pass":"GVN""#), "{branch}");
+    let run = Regex::new(r#"\{(?:[^{}]|\{[^{}]*\})*\}"#).unwrap();
+    let count = |run: &str, pattern: &str| -> usize {
+        let captures = Regex::new(pattern).unwrap().captures(run);
+        captures.unwrap_or_else(|| panic!("no `{pattern}` in {run}"))[1].parse().unwrap()
+    };
+    let runs: Vec<&str> = run.find_iter(branch).map(|run| run.as_str()).collect();
+    assert!(
+        runs.iter().any(|run| {
+            count(run, r#""after":\{[^}]*"basic_blocks":(\d+)"#)
+                < count(run, r#""before":\{[^}]*"basic_blocks":(\d+)"#)
+        }),
+        "{branch}"
+    );
+    assert!(runs.iter().any(|run| count(run, r#""statements_removed":(\d+)"#) > 0), "{branch}");
+}
@@ -0,0 +1,45 @@
+// Checks the output of `--print lints`, `--print unstable-features` and `--print crate-info`,
+// and of their JSON variants.
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir};
+
+fn print(args: &[&str]) -> String {
+    let output = rustc().arg("-Zunstable-options").args(args).run();
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn main() {
+    let lints = print(&["--print", "lints"]);
+    assert!(lints.contains("unused_variables default=warn level=warn"));
+    let lints = print(&["-A", "unused", "--print", "lints"]);
+    assert!(lints.contains("unused_variables default=warn level=allow"));
+    let lints = print(&["-D", "warnings", "--print", "lints"]);
+    assert!(lints.contains("dead_code default=warn level=deny"));
+    let lints = print(&["-D", "warnings", "--cap-lints", "warn", "--print", "lints"]);
+    assert!(lints.contains("dead_code default=warn level=warn"));
+    let lints = print(&["--print", "lints-json"]);
+    assert!(lints.contains(r#""name": "unused_variables""#));
+
+    let features = print(&["--print", "unstable-features"]);
+    assert!(features.contains("\nnever_type status=unstable issue="));
+    assert!(features.contains("\ngeneric_const_exprs status=incomplete issue="));
+    let features = print(&["--print", "unstable-features-json"]);
+    assert!(features.contains(r#""name": "never_type""#));
+
+    rustc().input("foo.rs").edition("2021").run();
+    let rlib = tmp_dir().join("libfoo.rlib");
+    let rlib = rlib.to_str().unwrap();
+    let info = print(&["--print", "crate-info", rlib]);
+    assert!(info.starts_with("name foo\nedition 2021\nhash "));
+    assert!(info.contains("\ndependency std hash "));
+    let info = print(&["--print", "crate-info-json", rlib]);
+    assert!(info.contains(r#""name": "foo""#));
+
+    // Source files have no metadata to read.
+    let output =
+        rustc().arg("-Zunstable-options").args(&["--print", "crate-info", "foo.rs"]).run_fail();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("failed to read the metadata of `foo.rs`"));
+}
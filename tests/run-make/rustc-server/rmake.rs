@@ -0,0 +1,81 @@
+// Checks that `rustc --server` compiles the crates it is asked to over its socket, and sends back
+// their diagnostics and exit code.
+
+//@ ignore-windows
+
+extern crate run_make_support;
+
+use run_make_support::{set_host_rpath, tmp_dir};
+use std::env;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+fn request(socket: &Path, request: &str) -> String {
+    // The server may not be listening yet.
+    let mut stream = loop {
+        match UnixStream::connect(socket) {
+            Ok(stream) => break stream,
+            Err(_) => thread::sleep(Duration::from_millis(10)),
+        }
+    };
+    writeln!(stream, "{request}").unwrap();
+    let mut response = String::new();
+    BufReader::new(stream).read_line(&mut response).unwrap();
+    response
+}
+
+fn compile(socket: &Path, args: &[&str]) -> String {
+    let src = env::current_dir().unwrap();
+    let args: Vec<_> = args.iter().map(|arg| format!("{arg:?}")).collect();
+    request(
+        socket,
+        &format!(
+            r#"{{"args": [{}], "cwd": {:?}}}"#,
+            args.join(", "),
+            src.to_str().unwrap(),
+        ),
+    )
+}
+
+fn main() {
+    let socket = tmp_dir().join("rustc.sock");
+    let mut server = Command::new(env::var("RUSTC").unwrap());
+    set_host_rpath(&mut server);
+    let mut server = server.arg("-Zunstable-options").arg("--server").arg(&socket).spawn().unwrap();
+
+    let out_dir = tmp_dir();
+    let out_dir = out_dir.to_str().unwrap();
+    let lib = format!("lib={out_dir}/liblib.rlib");
+
+    let response = compile(&socket, &["--crate-type=lib", "lib.rs", "--out-dir", out_dir]);
+    assert!(response.contains(r#""exit_code":0"#), "{response}");
+    assert!(tmp_dir().join("liblib.rlib").exists());
+
+    // Diagnostics are sent back, and the metadata of `lib` is loaded in several sessions.
+    for _ in 0..2 {
+        let response = compile(&socket, &["main.rs", "--extern", &lib, "--out-dir", out_dir]);
+        assert!(response.contains(r#""exit_code":0"#), "{response}");
+        assert!(response.contains("unused variable: `unused`"), "{response}");
+    }
+
+    let response = compile(&socket, &["error.rs", "--extern", &lib, "--out-dir", out_dir]);
+    assert!(response.contains(r#""exit_code":1"#), "{response}");
+    assert!(response.contains("error[E0308]: mismatched types"), "{response}");
+
+    let response = request(&socket, r#"{"args": "main.rs"}"#);
+    assert!(response.contains("`args` must be an array of strings"), "{response}");
+
+    // The compiler runs in the working directory and the environment of the server.
+    let response = request(&socket, r#"{"args": ["main.rs"], "cwd": "/"}"#);
+    assert!(response.contains("`cwd` must be the working directory of the server"), "{response}");
+    let response = request(&socket, r#"{"args": ["main.rs"], "env": {"RUSTC_SERVER_TEST": "1"}}"#);
+    assert!(response.contains("differs on `RUSTC_SERVER_TEST`"), "{response}");
+
+    request(&socket, r#"{"shutdown": true}"#);
+    assert!(server.wait().unwrap().success());
+    assert!(!socket.exists());
+}
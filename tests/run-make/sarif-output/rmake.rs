@@ -0,0 +1,35 @@
+// Checks the SARIF log written with `--error-format=sarif`: the rules of lints and error codes are
+// described, and the warnings emitted before the session exists end up in the same log.
+
+extern crate run_make_support;
+
+use run_make_support::rustc;
+
+fn stderr(output: std::process::Output) -> String {
+    String::from_utf8(output.stderr).unwrap()
+}
+
+fn main() {
+    let log = stderr(
+        rustc()
+            .input("warning.rs")
+            .error_format("sarif")
+            .arg("-Zunstable-options")
+            // Makes the options parsing emit a warning before the session exists.
+            .arg("-Zremark-dir=remarks")
+            .run(),
+    );
+    assert_eq!(log.matches(r#""$schema""#).count(), 1, "{log}");
+    assert!(log.contains("using -Z remark-dir without enabling remarks"), "{log}");
+    assert!(log.contains(r#""id": "unused_variables""#), "{log}");
+    assert!(log.contains(r#""text": "detect variables which are not used in any way""#), "{log}");
+    assert!(log.contains(r#""uri": "warning.rs""#), "{log}");
+
+    let log = stderr(
+        rustc().input("error.rs").error_format("sarif").arg("-Zunstable-options").run_fail(),
+    );
+    assert_eq!(log.matches(r#""$schema""#).count(), 1, "{log}");
+    assert!(log.contains(r#""id": "E0308""#), "{log}");
+    assert!(log.contains(r#""text": "Expected type did not match the received type.""#), "{log}");
+    assert!(log.contains(r#""level": "error""#), "{log}");
+}
@@ -0,0 +1,4 @@
+# `foo` and `.label` aren't in the English message, so `-Ztranslate-validate` reports them.
+parse_struct_literal_body_without_path = this is a {$foo} message
+    .suggestion = this is a test suggestion
+    .label = this is a test label
@@ -0,0 +1,3 @@
+# `.suggestion` isn't translated by this resource, so the fallback should be used for it while
+# the translated message is used for the rest of the diagnostic.
+parse_struct_literal_body_without_path = this is a test message
@@ -0,0 +1,2 @@
+# This resource can't be parsed, so it is skipped and the other resources of the locale are used.
+parse_struct_literal_body_without_path
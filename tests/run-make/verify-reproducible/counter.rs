@@ -0,0 +1,14 @@
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static COUNT: AtomicU32 = AtomicU32::new(0);
+
+// Expands to a different value each time it is used in the same process.
+#[proc_macro]
+pub fn count(_: TokenStream) -> TokenStream {
+    COUNT.fetch_add(1, Ordering::Relaxed).to_string().parse().unwrap()
+}
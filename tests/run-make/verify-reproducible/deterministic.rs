@@ -0,0 +1,7 @@
+#![crate_type = "rlib"]
+
+pub fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+pub static NAMES: &[&str] = &["a", "b"];
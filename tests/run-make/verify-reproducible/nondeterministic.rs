@@ -0,0 +1,5 @@
+#![crate_type = "rlib"]
+
+extern crate counter;
+
+pub const COUNT: u32 = counter::count!();
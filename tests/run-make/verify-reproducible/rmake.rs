@@ -0,0 +1,19 @@
+// Checks that `-Zverify-reproducible` accepts a crate compiled deterministically, and reports the
+// outputs that differ when a proc macro expands differently in the second compilation.
+
+//@ ignore-cross-compile
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir};
+
+fn main() {
+    rustc().input("deterministic.rs").arg("-Zverify-reproducible").run();
+    assert!(tmp_dir().join("libdeterministic.rlib").exists());
+
+    rustc().input("counter.rs").run();
+    let output = rustc().input("nondeterministic.rs").arg("-Zverify-reproducible").run_fail();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("nondeterministic.rlib` differs between two compilations"), "{stderr}");
+    assert!(stderr.contains("archive member `lib.rmeta`"), "{stderr}");
+}
@@ -0,0 +1,132 @@
+//@ run-pass
+//! Test that the body of a function can be instrumented with the mutable visitor, and replaced
+//! until it may have been inlined.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use stable_mir::mir::visit::Location;
+use stable_mir::mir::{
+    Constant, MutMirVisitor, Mutability, Operand, Place, Rvalue, Statement, StatementKind,
+    TerminatorKind, RETURN_LOCAL,
+};
+use stable_mir::ty::{Const, RigidTy, Ty, TyKind, UintTy};
+use stable_mir::{CrateDef, CrateItem};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// Replaces the `u32` constants by `42`.
+struct ReplaceConstants {
+    replaced: usize,
+}
+
+impl MutMirVisitor for ReplaceConstants {
+    fn visit_constant(&mut self, constant: &mut Constant, _location: Location) {
+        if constant.literal.ty().kind() == TyKind::RigidTy(RigidTy::Uint(UintTy::U32)) {
+            constant.literal = Const::try_from_uint(42, UintTy::U32).unwrap();
+            self.replaced += 1;
+        }
+    }
+}
+
+fn test_body_replacement() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let find = |name: &str| -> CrateItem {
+        *items.iter().find(|item| item.name() == name).unwrap()
+    };
+    let answer = find("answer");
+    let mut body = answer.body();
+    let mut visitor = ReplaceConstants { replaced: 0 };
+    visitor.visit_body(&mut body);
+    assert_eq!(visitor.replaced, 1);
+
+    // Add a temporary which is only live at the start of the function.
+    let span = body.blocks[0].terminator.span;
+    let tmp = body.new_local(Ty::unsigned_ty(UintTy::U32), span, Mutability::Not);
+    let statements = &mut body.blocks[0].statements;
+    statements.insert(0, Statement { kind: StatementKind::StorageLive(tmp), span });
+    statements.insert(1, Statement { kind: StatementKind::StorageDead(tmp), span });
+    assert_eq!(answer.replace_body(&body), Ok(()));
+
+    let replaced = answer.body();
+    assert_eq!(replaced.locals().len(), body.locals().len());
+    assert_eq!(replaced.blocks[0].statements[0].kind, StatementKind::StorageLive(tmp));
+    let mut visitor = ReplaceConstants { replaced: 0 };
+    visitor.visit_body(&mut replaced.clone());
+    assert_eq!(visitor.replaced, 1);
+
+    // Bodies referring to blocks which don't exist are rejected.
+    let double = find("double");
+    let mut body = double.body();
+    body.blocks[0].terminator.kind = TerminatorKind::Goto { target: 99 };
+    let error = double.replace_body(&body).unwrap_err();
+    assert!(error.to_string().contains("`bb99` doesn't exist"), "{error}");
+
+    // So are the ones the MIR validator rejects, like an assignment of a `bool` to a `u32`.
+    let mut body = double.body();
+    let ret = body.ret_local().ty;
+    assert_matches!(ret.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U32)));
+    let constant = Constant { span, user_ty: None, literal: Const::from_bool(true) };
+    let rvalue = Rvalue::Use(Operand::Constant(constant));
+    let assign = StatementKind::Assign(Place::from(RETURN_LOCAL), rvalue);
+    body.blocks[0].statements.push(Statement { kind: assign, span });
+    assert!(double.replace_body(&body).is_err());
+
+    // Once `caller` may have inlined `answer`, the body of `answer` can't be replaced anymore.
+    let _ = find("caller").body();
+    let error = answer.replace_body(&answer.body()).unwrap_err();
+    assert!(error.to_string().contains("may already have been inlined"), "{error}");
+
+    // Let the compilation continue, to generate code for the replaced body.
+    ControlFlow::Continue(())
+}
+
+fn main() {
+    let path = "body_replacement_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "-Zmir-opt-level=3".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_body_replacement).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub fn answer() -> u32 {{
+            41
+        }}
+
+        pub fn double(value: u32) -> u32 {{
+            value.wrapping_mul(2)
+        }}
+
+        pub fn caller() -> u32 {{
+            answer()
+        }}
+        "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,130 @@
+//@ run-pass
+//! Test the dataflow analyses bundled with StableMIR.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use stable_mir::mir::dataflow::{
+    Analysis, Location, MaybeInitializedPlaces, MaybeLiveLocals, ReachingDefinitions,
+};
+use stable_mir::mir::{Body, Place, TerminatorKind, RETURN_LOCAL};
+use stable_mir::CrateDef;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// The locations of the return terminators of `body`.
+fn returns(body: &Body) -> Vec<Location> {
+    body.blocks
+        .iter()
+        .enumerate()
+        .filter(|(_, data)| matches!(data.terminator.kind, TerminatorKind::Return))
+        .map(|(block, data)| Location { block, statement_index: data.statements.len() })
+        .collect()
+}
+
+fn test_dataflow() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = |name: &str| items.iter().find(|item| item.name() == name).unwrap().body();
+
+    let pick = body("pick");
+    let inner_locals = pick.arg_locals().len() + 1..pick.locals().len();
+    assert!(!returns(&pick).is_empty());
+
+    let live = MaybeLiveLocals.iterate_to_fixpoint(&pick);
+    let mut cursor = live.cursor(&pick);
+    cursor.seek_before(Location::START);
+    assert!(cursor.get().contains(1), "`flag` isn't live on entry: {:?}", cursor.get());
+    assert!(inner_locals.all(|local| !cursor.get().contains(local)));
+    for location in returns(&pick) {
+        cursor.seek_before(location);
+        assert!(cursor.get().contains(RETURN_LOCAL));
+        cursor.seek_after(location);
+        assert!(cursor.get().is_empty());
+    }
+
+    let init = MaybeInitializedPlaces::new(&pick).iterate_to_fixpoint(&pick);
+    let move_paths = init.analysis.move_paths();
+    let mut cursor = init.cursor(&pick);
+    cursor.seek_before(Location::START);
+    let initialized: Vec<_> = cursor.get().iter().map(|path| move_paths.place(path)).collect();
+    assert_eq!(initialized, [&Place::from(1), &Place::from(2)]);
+    for location in returns(&pick) {
+        cursor.seek_before(location);
+        assert!(cursor.get().contains(RETURN_LOCAL));
+    }
+
+    let definitions = ReachingDefinitions::new(&pick);
+    let return_definitions = definitions.definitions_of(RETURN_LOCAL).to_vec();
+    assert!(!return_definitions.is_empty());
+    let reaching = definitions.iterate_to_fixpoint(&pick);
+    let mut cursor = reaching.cursor(&pick);
+    cursor.seek_before(Location::START);
+    let definitions = reaching.analysis.definitions();
+    assert!(cursor.get().iter().all(|index| definitions[index].location.is_none()));
+    for location in returns(&pick) {
+        cursor.seek_before(location);
+        assert!(return_definitions.iter().any(|index| cursor.get().contains(*index)));
+    }
+
+    // A string moved into `drop` isn't initialized anymore when the function returns.
+    let consume = body("consume");
+    let init = MaybeInitializedPlaces::new(&consume).iterate_to_fixpoint(&consume);
+    let string = init.analysis.move_paths().find(&Place::from(1)).unwrap();
+    let mut cursor = init.cursor(&consume);
+    cursor.seek_before(Location::START);
+    assert!(cursor.get().contains(string));
+    for location in returns(&consume) {
+        cursor.seek_before(location);
+        assert!(!cursor.get().contains(string));
+    }
+    ControlFlow::Continue(())
+}
+
+fn main() {
+    let path = "dataflow_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_dataflow).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub fn pick(flag: bool, value: u32) -> u32 {{
+            let mut result = 0;
+            if flag {{
+                result = value;
+            }}
+            result
+        }}
+
+        pub fn consume(string: String) -> usize {{
+            let len = string.len();
+            drop(string);
+            len
+        }}
+        "#
+    )?;
+    Ok(())
+}
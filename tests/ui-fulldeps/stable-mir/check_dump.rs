@@ -0,0 +1,134 @@
+//@ run-pass
+//! Test that the StableMIR of a crate can be dumped, and then analyzed without the compiler.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+#![feature(control_flow_enum)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use stable_mir::dump::CrateDump;
+use stable_mir::mir::MirVisitor;
+use stable_mir::mir::visit::Location;
+use stable_mir::ty::{RigidTy, Ty, TyKind};
+use stable_mir::CrateDef;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::ops::ControlFlow;
+use std::path::Path;
+
+const CRATE_NAME: &str = "input";
+
+/// Records the dump, and what the APIs return when running in the compiler.
+fn collect() -> ControlFlow<(), (CrateDump, String)> {
+    ControlFlow::Continue((CrateDump::collect(), describe_crate()))
+}
+
+/// Describes the local items, with the types, layouts and ADT definitions they refer to.
+fn describe_crate() -> String {
+    let mut description = Vec::new();
+    for item in stable_mir::all_local_items() {
+        writeln!(description, "{:?} {:?}", item.kind(), item.span().get_lines()).unwrap();
+        item.emit_mir(&mut description).unwrap();
+    }
+    let mut description = String::from_utf8(description).unwrap();
+
+    struct Types(Vec<Ty>);
+    impl MirVisitor for Types {
+        fn visit_ty(&mut self, ty: &Ty, _location: Location) {
+            self.0.push(*ty);
+        }
+    }
+    let mut types = Types(Vec::new());
+    for item in stable_mir::all_local_items() {
+        types.visit_body(&item.body());
+    }
+    for ty in types.0 {
+        let size = ty.layout().map(|layout| layout.shape().size.bytes());
+        writeln!(description, "{ty}: {size:?}").unwrap();
+        let TyKind::RigidTy(RigidTy::Adt(def, _)) = ty.kind() else { continue };
+        writeln!(description, "  {} {}", def.kind(), def.trimmed_name()).unwrap();
+        for variant in def.variants() {
+            let fields: Vec<_> = variant
+                .fields()
+                .iter()
+                .map(|field| format!("{}: {}", field.name, field.ty()))
+                .collect();
+            writeln!(description, "  {}({})", variant.name(), fields.join(", ")).unwrap();
+        }
+    }
+    description
+}
+
+fn main() {
+    let path = "dump_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "-Cpanic=abort".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    let (dump, expected) = run!(args, collect).unwrap();
+    assert!(expected.contains("enum Shape"), "{expected}");
+    assert!(expected.contains("Square(side: u32)"), "{expected}");
+
+    let dump_path = Path::new("dump_input.smir.json");
+    dump.write(dump_path).unwrap();
+    let dump = CrateDump::read(dump_path).unwrap();
+    let description = dump.run(describe_crate).unwrap();
+    assert_eq!(description, expected);
+    dump.run(|| {
+        assert_eq!(stable_mir::local_crate().name, CRATE_NAME);
+        assert!(stable_mir::entry_fn().is_none());
+    })
+    .unwrap();
+
+    // Dumps in another version of the format are rejected.
+    let json = std::fs::read_to_string(dump_path).unwrap();
+    let json = json.replacen(r#"{"version":1,"#, r#"{"version":0,"#, 1);
+    std::fs::write(dump_path, json).unwrap();
+    let error = CrateDump::read(dump_path).err().unwrap();
+    assert!(error.to_string().contains("has version 0 of the format"), "{error}");
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub enum Shape {{
+            Circle(f64),
+            Square {{ side: u32 }},
+        }}
+
+        pub fn area(shape: &Shape) -> f64 {{
+            match shape {{
+                Shape::Circle(radius) => 3.14 * radius * radius,
+                Shape::Square {{ side }} => (side * side) as f64,
+            }}
+        }}
+
+        pub fn sum(values: &[u64]) -> u64 {{
+            let mut total = 0;
+            for value in values {{
+                total += value;
+            }}
+            total
+        }}
+        "#
+    )?;
+    Ok(())
+}
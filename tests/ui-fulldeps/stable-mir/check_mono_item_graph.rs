@@ -0,0 +1,104 @@
+//@ run-pass
+//! Test that the mono items collected for code generation, and their uses, are exposed.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use stable_mir::mir::mono::{MonoItem, MonoItemUse, UseKind};
+use stable_mir::CrateDef;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn name(item: &MonoItem) -> String {
+    match item {
+        MonoItem::Fn(instance) => instance.name(),
+        MonoItem::Static(def) => def.name(),
+        MonoItem::GlobalAsm(_) => String::new(),
+    }
+}
+
+fn test_mono_item_graph() -> ControlFlow<()> {
+    let items = stable_mir::all_mono_items();
+    let entry = items.iter().find(|item| name(item) == "input::entry").unwrap();
+    let uses = entry.uses();
+    let find = |kind: UseKind, pattern: &str| -> &MonoItemUse {
+        uses.iter()
+            .find(|used| used.kind == kind && name(&used.item).contains(pattern))
+            .unwrap_or_else(|| panic!("no {kind:?} use of `{pattern}` in {uses:?}"))
+    };
+
+    let helper = find(UseKind::Call, "input::helper");
+    assert_eq!(helper.span.get_lines().start_line, 22);
+    find(UseKind::FnPointer, "input::double");
+    find(UseKind::VtableMethod, "<input::Square as input::Shape>::area");
+    let guard = find(UseKind::DropGlue, "Guard");
+    assert!(guard.item.uses().iter().any(|used| {
+        used.kind == UseKind::Call && name(&used.item).ends_with("Guard as std::ops::Drop>::drop")
+    }));
+
+    // The uses of the collected items were collected too.
+    for item in &items {
+        for used in item.uses() {
+            assert!(items.contains(&used.item), "{:?} isn't collected", used.item);
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+fn main() {
+    let path = "mono_item_graph_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_mono_item_graph).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub struct Guard;
+        impl Drop for Guard {{
+            fn drop(&mut self) {{}}
+        }}
+
+        pub trait Shape {{
+            fn area(&self) -> u32;
+        }}
+        pub struct Square(pub u32);
+        impl Shape for Square {{
+            fn area(&self) -> u32 {{ self.0 * self.0 }}
+        }}
+
+        fn helper(value: u32) -> u32 {{ value / 2 }}
+        fn double(value: u32) -> u32 {{ value * 2 }}
+
+        pub fn entry(value: u32) -> u32 {{
+            let _guard = Guard;
+            let shape: &dyn Shape = &Square(value);
+            let function: fn(u32) -> u32 = double;
+            helper(shape.area()) + function(value)
+        }}
+        "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,121 @@
+//@ run-pass
+//! Test that trait implementations can be checked, and types normalized.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use stable_mir::ty::{
+    AssocKind, Const, GenericArgKind, GenericArgs, RigidTy, TraitDef, Ty, TyKind, UintTy,
+};
+use stable_mir::CrateDef;
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_trait_solving() -> ControlFlow<()> {
+    let traits = stable_mir::local_crate().trait_decls();
+    let shape = traits.iter().find(|def| def.name() == "Shape").unwrap();
+    let convert = traits.iter().find(|def| def.name() == "Convert").unwrap();
+
+    let items = TraitDef::declaration(shape).associated_items();
+    let items: Vec<_> = items
+        .iter()
+        .map(|item| (item.name.as_str(), item.kind, item.fn_has_self_parameter, item.has_value))
+        .collect();
+    assert_eq!(
+        items,
+        [
+            ("Area", AssocKind::Type, false, false),
+            ("SIDES", AssocKind::Const, false, false),
+            ("area", AssocKind::Fn, true, false),
+            ("name", AssocKind::Fn, false, true),
+        ]
+    );
+
+    let square_area = stable_mir::all_local_items()
+        .into_iter()
+        .find(|item| item.name() == "square_area")
+        .unwrap();
+    let TyKind::RigidTy(RigidTy::Ref(_, square, _)) = square_area.body().arg_locals()[0].ty.kind()
+    else {
+        unreachable!()
+    };
+
+    let no_args = GenericArgs(vec![]);
+    let u64_arg = GenericArgs(vec![GenericArgKind::Type(Ty::unsigned_ty(UintTy::U64))]);
+    let u32_arg = GenericArgs(vec![GenericArgKind::Type(Ty::unsigned_ty(UintTy::U32))]);
+    assert_eq!(square.implements(*shape, &no_args), Ok(true));
+    assert_eq!(Ty::bool_ty().implements(*shape, &no_args), Ok(false));
+    assert_eq!(square.implements(*convert, &u64_arg), Ok(true));
+    assert_eq!(square.implements(*convert, &u32_arg), Ok(false));
+    assert!(square.implements(*convert, &no_args).is_err());
+    // The generic arguments must be of the kinds of the parameters.
+    let const_arg = GenericArgs(vec![GenericArgKind::Const(Const::from_bool(true))]);
+    assert!(square.implements(*convert, &const_arg).is_err());
+
+    // The signature of a function isn't normalized.
+    let sig = square_area.ty().kind().fn_sig().unwrap().skip_binder();
+    assert_matches!(sig.output().kind(), TyKind::Alias(..));
+    assert_eq!(sig.output().normalize().unwrap().kind(), Ty::unsigned_ty(UintTy::U64).kind());
+
+    ControlFlow::Continue(())
+}
+
+fn main() {
+    let path = "trait_solving.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_trait_solving).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub trait Shape {{
+            type Area;
+            const SIDES: u32;
+            fn area(&self) -> Self::Area;
+            fn name() -> &'static str {{ "shape" }}
+        }}
+
+        pub trait Convert<T> {{}}
+
+        pub struct Square(pub u32);
+
+        impl Shape for Square {{
+            type Area = u64;
+            const SIDES: u32 = 4;
+            fn area(&self) -> u64 {{ self.0 as u64 * self.0 as u64 }}
+        }}
+
+        impl Convert<u64> for Square {{}}
+
+        pub fn square_area(square: &Square) -> <Square as Shape>::Area {{
+            square.area()
+        }}
+        "#
+    )?;
+    Ok(())
+}
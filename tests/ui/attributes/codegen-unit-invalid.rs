@@ -0,0 +1,15 @@
+#![feature(codegen_unit_hints)]
+
+// Test that `#[codegen_unit]` is only accepted on items containing code, and with names which can
+// be used in file names.
+
+#[codegen_unit = "types"]
+//~^ ERROR `codegen_unit` attribute should be applied to a function, static, module or implementation
+struct Foo;
+
+#[codegen_unit = "hot path"]
+//~^ ERROR invalid codegen unit name `hot path`
+fn hot() {}
+
+#[codegen_unit = "hot"]
+fn main() {}
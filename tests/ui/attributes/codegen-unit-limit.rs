@@ -0,0 +1,14 @@
+//@ build-pass
+//@ compile-flags: -Ccodegen-units=1
+
+#![feature(codegen_unit_hints)]
+
+// Test that the `#[codegen_unit]` attributes are ignored when the codegen units they name leave no
+// room for the other items within the maximum number of codegen units.
+
+#[codegen_unit = "hot"]
+fn hot() {}
+
+fn main() {
+    hot();
+}
@@ -0,0 +1,14 @@
+#![feature(optimize_attribute)]
+#![crate_type = "lib"]
+
+#[optimize(level = "4")] //~ ERROR E0722
+pub fn unknown() {}
+
+#[optimize(level = 3)] //~ ERROR E0722
+pub fn not_a_string() {}
+
+#[optimize(level)] //~ ERROR E0722
+pub fn missing() {}
+
+#[optimize(level = "z")]
+pub fn size_min() {}
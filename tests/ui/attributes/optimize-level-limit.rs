@@ -0,0 +1,15 @@
+//@ build-pass
+//@ compile-flags: -Ccodegen-units=1 -Copt-level=2
+
+#![feature(optimize_attribute)]
+
+// Test that the `#[optimize(level = "...")]` attributes are ignored by LLVM when the codegen units
+// of their levels leave no room for the other items within the maximum number of codegen units.
+
+#[inline(never)]
+#[optimize(level = "3")]
+fn aggressive() {}
+
+fn main() {
+    aggressive();
+}
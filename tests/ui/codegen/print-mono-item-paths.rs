@@ -0,0 +1,29 @@
+//@ compile-flags: -Z print-mono-item-paths=wrap:: --crate-type=lib
+//@ build-pass
+//@ ignore-pass
+// ^-- needed because `--pass check` does not emit the output needed.
+
+// Check that the shortest chain of uses from a root is printed for the matching mono items,
+// even when a longer one is found first.
+
+pub struct Wrapper<T>(T);
+
+fn wrap<T>(value: T) -> Wrapper<T> {
+    Wrapper(value)
+}
+
+fn forward<T>(value: T) -> Wrapper<T> {
+    wrap(value)
+}
+
+pub fn entry() -> Wrapper<u8> {
+    forward(1)
+}
+
+pub fn longer() -> Wrapper<u16> {
+    forward(2)
+}
+
+pub fn shortcut() -> fn(u16) -> Wrapper<u16> {
+    wrap::<u16>
+}
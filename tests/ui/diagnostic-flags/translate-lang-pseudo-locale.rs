@@ -0,0 +1,16 @@
+//@ compile-flags: -Ztranslate-lang=en-XA
+// Checks that diagnostics are translated with the pseudo-locale shipped with the compiler, and that
+// the messages it doesn't translate use the English messages.
+
+struct Foo {
+    val: (),
+}
+
+fn foo() -> Foo { //~ ERROR šţȓũçţ ĺĩţȩȓàĺ ƀöđŷ ŵĩţĥöũţ þàţĥ
+    val: (),
+}
+
+fn main() {
+    let x = foo();
+    x.val == 42; //~ ERROR mismatched types
+}
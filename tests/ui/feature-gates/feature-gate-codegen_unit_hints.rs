@@ -0,0 +1,2 @@
+#[codegen_unit = "hot"] //~ ERROR the `#[codegen_unit]` attribute is an experimental feature
+fn main() {}
@@ -0,0 +1,3 @@
+//@ compile-flags: -Zunstable-options --lint-config={{src-base}}/lint/lint-config/invalid.toml
+
+fn main() {}
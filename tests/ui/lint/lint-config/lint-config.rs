@@ -0,0 +1,31 @@
+// Checks the lint levels set by a `--lint-config` file for the whole crate and for the modules
+// matching a pattern, and that they are overridden by attributes.
+
+//@ compile-flags: -Zunstable-options --lint-config={{src-base}}/lint/lint-config/lint-config.toml
+
+#![crate_type = "lib"]
+
+pub fn read() -> u8 {
+    unsafe { *(&0 as *const u8) } //~ ERROR usage of an `unsafe` block
+}
+
+pub mod ffi {
+    pub fn read() -> u8 {
+        unsafe { *(&0 as *const u8) }
+    }
+
+    #[deny(unsafe_code)]
+    pub mod checked {
+        pub fn read() -> u8 {
+            unsafe { *(&0 as *const u8) } //~ ERROR usage of an `unsafe` block
+        }
+    }
+
+    mod internal {
+        fn unused() {} //~ ERROR function `unused` is never used
+    }
+}
+
+mod internal {
+    fn unused() {}
+}
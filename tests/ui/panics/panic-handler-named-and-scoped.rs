@@ -0,0 +1,62 @@
+//@ run-pass
+//@ needs-unwind
+
+//@ ignore-emscripten no threads support
+
+#![feature(panic_named_hooks)]
+#![feature(panic_scoped_hook)]
+
+use std::cell::Cell;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+static GLOBAL: AtomicUsize = AtomicUsize::new(0);
+static TELEMETRY: AtomicUsize = AtomicUsize::new(0);
+static HARNESS: AtomicUsize = AtomicUsize::new(0);
+
+fn main() {
+    panic::set_hook(Box::new(|_| { GLOBAL.fetch_add(1, Ordering::SeqCst); }));
+    assert!(panic::register_hook("telemetry", Box::new(|_| {
+        TELEMETRY.fetch_add(1, Ordering::SeqCst);
+    })).is_none());
+    assert!(panic::register_hook("harness", Box::new(|_| {
+        HARNESS.fetch_add(1, Ordering::SeqCst);
+    })).is_none());
+
+    // Named hooks run alongside the primary hook.
+    let _ = thread::spawn(|| panic!()).join();
+    assert_eq!(GLOBAL.load(Ordering::SeqCst), 1);
+    assert_eq!(TELEMETRY.load(Ordering::SeqCst), 1);
+    assert_eq!(HARNESS.load(Ordering::SeqCst), 1);
+
+    // Removing one named hook leaves the others in place.
+    assert!(panic::unregister_hook("harness").is_some());
+    assert!(panic::unregister_hook("harness").is_none());
+    let _ = thread::spawn(|| panic!()).join();
+    assert_eq!(GLOBAL.load(Ordering::SeqCst), 2);
+    assert_eq!(TELEMETRY.load(Ordering::SeqCst), 2);
+    assert_eq!(HARNESS.load(Ordering::SeqCst), 1);
+
+    // A scoped hook replaces the primary hook on the current thread only, and
+    // scoped hooks nest.
+    let outer = Cell::new(0);
+    let inner = Cell::new(0);
+    panic::with_hook(|_| outer.set(outer.get() + 1), || {
+        let _ = panic::catch_unwind(|| panic!());
+        panic::with_hook(|_| inner.set(inner.get() + 1), || {
+            let _ = panic::catch_unwind(|| panic!());
+        });
+        let _ = panic::catch_unwind(|| panic!());
+        let _ = thread::spawn(|| panic!()).join();
+    });
+    assert_eq!(outer.get(), 2);
+    assert_eq!(inner.get(), 1);
+    assert_eq!(GLOBAL.load(Ordering::SeqCst), 3);
+    assert_eq!(TELEMETRY.load(Ordering::SeqCst), 6);
+
+    // Once the scope is left, the primary hook is back in effect.
+    let _ = panic::catch_unwind(|| panic!());
+    assert_eq!(GLOBAL.load(Ordering::SeqCst), 4);
+    assert_eq!(outer.get(), 2);
+}